@@ -1,4 +1,8 @@
-use crate::{bloom::Bloom, error::BlockchainHashchainError, merkle::StreamCompactMerkleTree};
+use crate::{
+    bloom::Bloom,
+    error::BlockchainHashchainError,
+    merkle::{MerkleProof, MerkleTree, StreamCompactMerkleTree},
+};
 use aurora_engine_sdk::keccak;
 use aurora_engine_types::{
     account_id::AccountId,
@@ -238,20 +242,9 @@ impl BlockHashchainComputer {
 
     /// Adds a transaction.
     pub fn add_tx(&mut self, method_name: &str, input: &[u8], output: &[u8], log_bloom: &Bloom) {
-        let data = [
-            &saturating_cast(method_name.len()).to_be_bytes(),
-            method_name.as_bytes(),
-            &saturating_cast(input.len()).to_be_bytes(),
-            input,
-            &saturating_cast(output.len()).to_be_bytes(),
-            output,
-        ]
-        .concat();
-
-        let tx_hash = keccak(&data).0;
-
         self.txs_logs_bloom.accrue_bloom(log_bloom);
-        self.txs_merkle_tree.add(tx_hash);
+        self.txs_merkle_tree
+            .add(compute_tx_hash(method_name, input, output));
     }
 
     /// Computes the block hashchain.
@@ -262,19 +255,14 @@ impl BlockHashchainComputer {
         current_block_height: u64,
         previous_block_hashchain: RawH256,
     ) -> RawH256 {
-        let txs_hash = self.txs_merkle_tree.compute_hash();
-
-        let data = [
+        compute_block_hashchain(
             chain_id,
             contract_account_id,
-            &current_block_height.to_be_bytes(),
-            &previous_block_hashchain,
-            &txs_hash,
-            self.txs_logs_bloom.as_bytes(),
-        ]
-        .concat();
-
-        keccak(&data).0
+            current_block_height,
+            previous_block_hashchain,
+            self.txs_merkle_tree.compute_hash(),
+            &self.txs_logs_bloom,
+        )
     }
 
     /// Clears the transactions added.
@@ -289,6 +277,160 @@ impl BlockHashchainComputer {
     }
 }
 
+/// Block Transactions Prover.
+/// Keeps all the transactions of a block to build inclusion proofs of them against the block
+/// hashchain. Space used is O(n) where n is the number of transactions, so it is meant for
+/// off-chain tooling rather than the engine itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTxsProver {
+    chain_id: [u8; 32],
+    contract_account_id: AccountId,
+    block_height: u64,
+    previous_block_hashchain: RawH256,
+    txs_logs_bloom: Bloom,
+    txs_merkle_tree: MerkleTree,
+}
+
+impl BlockTxsProver {
+    #[must_use]
+    pub fn new(
+        chain_id: [u8; 32],
+        contract_account_id: AccountId,
+        block_height: u64,
+        previous_block_hashchain: RawH256,
+    ) -> Self {
+        Self {
+            chain_id,
+            contract_account_id,
+            block_height,
+            previous_block_hashchain,
+            txs_logs_bloom: Bloom::default(),
+            txs_merkle_tree: MerkleTree::new(),
+        }
+    }
+
+    /// Adds the next transaction of the block.
+    pub fn add_tx(&mut self, method_name: &str, input: &[u8], output: &[u8], log_bloom: &Bloom) {
+        self.txs_logs_bloom.accrue_bloom(log_bloom);
+        self.txs_merkle_tree
+            .add(compute_tx_hash(method_name, input, output));
+    }
+
+    /// Computes the block hashchain with the transactions added so far.
+    pub fn compute_block_hashchain(&self) -> RawH256 {
+        compute_block_hashchain(
+            &self.chain_id,
+            self.contract_account_id.as_bytes(),
+            self.block_height,
+            self.previous_block_hashchain,
+            self.txs_merkle_tree.compute_hash(),
+            &self.txs_logs_bloom,
+        )
+    }
+
+    /// Builds the inclusion proof of the transaction at the given index within the block.
+    /// Returns `None` if there is no such transaction.
+    pub fn prove(&self, index: usize) -> Option<BlockTxProof> {
+        let merkle_proof = self.txs_merkle_tree.prove(index)?;
+
+        Some(BlockTxProof {
+            chain_id: self.chain_id,
+            contract_account_id: self.contract_account_id.clone(),
+            block_height: self.block_height,
+            previous_block_hashchain: self.previous_block_hashchain,
+            txs_logs_bloom: self.txs_logs_bloom,
+            index: u64::try_from(index).ok()?,
+            tx_count: u64::try_from(self.txs_merkle_tree.len()).ok()?,
+            merkle_proof,
+        })
+    }
+}
+
+/// Inclusion proof of a transaction in the hashchain of a block.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+pub struct BlockTxProof {
+    pub chain_id: [u8; 32],
+    pub contract_account_id: AccountId,
+    pub block_height: u64,
+    pub previous_block_hashchain: RawH256,
+    /// The logs bloom of all the transactions of the block.
+    pub txs_logs_bloom: Bloom,
+    /// The position of the transaction within the block.
+    pub index: u64,
+    /// The number of the transactions of the block. The hashchain doesn't commit to it, so the
+    /// verifier should check it against the block.
+    pub tx_count: u64,
+    /// The proof of the transaction hash in the Merkle Tree of the transactions of the block.
+    pub merkle_proof: MerkleProof,
+}
+
+impl BlockTxProof {
+    /// Checks the transaction with the given method name, input and output was executed in the
+    /// block with the given hashchain at the position `index` of the proof.
+    pub fn verify(
+        &self,
+        method_name: &str,
+        input: &[u8],
+        output: &[u8],
+        block_hashchain: RawH256,
+    ) -> bool {
+        let Some(txs_hash) = self.merkle_proof.compute_root_at(
+            compute_tx_hash(method_name, input, output),
+            self.index,
+            self.tx_count,
+        ) else {
+            return false;
+        };
+
+        compute_block_hashchain(
+            &self.chain_id,
+            self.contract_account_id.as_bytes(),
+            self.block_height,
+            self.previous_block_hashchain,
+            txs_hash,
+            &self.txs_logs_bloom,
+        ) == block_hashchain
+    }
+}
+
+/// Computes the hash of a transaction, which is a leaf of the Merkle Tree of the block.
+pub fn compute_tx_hash(method_name: &str, input: &[u8], output: &[u8]) -> RawH256 {
+    let data = [
+        &saturating_cast(method_name.len()).to_be_bytes(),
+        method_name.as_bytes(),
+        &saturating_cast(input.len()).to_be_bytes(),
+        input,
+        &saturating_cast(output.len()).to_be_bytes(),
+        output,
+    ]
+    .concat();
+
+    keccak(&data).0
+}
+
+/// Computes the hashchain of a block from the hash and logs bloom of its transactions.
+pub fn compute_block_hashchain(
+    chain_id: &[u8; 32],
+    contract_account_id: &[u8],
+    block_height: u64,
+    previous_block_hashchain: RawH256,
+    txs_hash: RawH256,
+    txs_logs_bloom: &Bloom,
+) -> RawH256 {
+    let data = [
+        chain_id,
+        contract_account_id,
+        &block_height.to_be_bytes(),
+        &previous_block_hashchain,
+        &txs_hash,
+        txs_logs_bloom.as_bytes(),
+    ]
+    .concat();
+
+    keccak(&data).0
+}
+
 fn saturating_cast(x: usize) -> u32 {
     x.try_into().unwrap_or(u32::MAX)
 }
@@ -131,6 +131,164 @@ struct CompactMerkleSubtree {
     pub hash: RawH256,
 }
 
+/// Merkle Tree keeping all the leaves hashes.
+/// It has the same shape and hash as the `StreamCompactMerkleTree` fed with the same leaves,
+/// but it can also build inclusion proofs for them. Space used is O(n).
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+pub struct MerkleTree {
+    leaves: Vec<RawH256>,
+}
+
+impl MerkleTree {
+    pub const fn new() -> Self {
+        Self { leaves: Vec::new() }
+    }
+
+    /// Returns the number of leaves added to the tree.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns `true` if no data has been added to the tree.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Adds a leaf hash to the right of the tree.
+    pub fn add(&mut self, leaf_hash: RawH256) {
+        self.leaves.push(leaf_hash);
+    }
+
+    /// Computes the hash of the Merkle Tree.
+    /// For n leaves hashes added, this function is O(n).
+    pub fn compute_hash(&self) -> RawH256 {
+        if self.leaves.is_empty() {
+            return [0; 32];
+        }
+
+        let mut level = self.leaves.clone();
+        while level.len() > 1 {
+            level = parent_level(&level);
+        }
+        level[0]
+    }
+
+    /// Builds the inclusion proof of the leaf at the given index.
+    /// Returns `None` if there is no such leaf.
+    pub fn prove(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaves.len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut level = self.leaves.clone();
+        let mut index = index;
+        while level.len() > 1 {
+            let sibling = if index % 2 == 0 {
+                // the last node of a level with an odd length is its own sibling
+                MerkleSibling::Right(*level.get(index + 1).unwrap_or(&level[index]))
+            } else {
+                MerkleSibling::Left(level[index - 1])
+            };
+            siblings.push(sibling);
+            level = parent_level(&level);
+            index /= 2;
+        }
+
+        Some(MerkleProof { siblings })
+    }
+
+    /// Clears the structure leaving it empty.
+    pub fn clear(&mut self) {
+        self.leaves.clear();
+    }
+}
+
+/// Hashes the nodes of a level in pairs, duplicating the last node if the level length is odd.
+fn parent_level(level: &[RawH256]) -> Vec<RawH256> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            let right = pair.get(1).unwrap_or(&pair[0]);
+            keccak(&[pair[0], *right].concat()).0
+        })
+        .collect()
+}
+
+/// Sibling of a node in the path from a leaf to the root of a Merkle Tree.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+pub enum MerkleSibling {
+    /// The sibling is the left child of the parent node.
+    Left(RawH256),
+    /// The sibling is the right child of the parent node.
+    Right(RawH256),
+}
+
+/// Inclusion proof of a leaf in a Merkle Tree.
+/// Holds the siblings of the nodes in the path from the leaf to the root, starting at the leaf.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+pub struct MerkleProof {
+    pub siblings: Vec<MerkleSibling>,
+}
+
+impl MerkleProof {
+    /// Computes the root hash of the tree the leaf belongs to according to the proof.
+    pub fn compute_root(&self, leaf_hash: RawH256) -> RawH256 {
+        self.siblings
+            .iter()
+            .fold(leaf_hash, |hash, sibling| match sibling {
+                MerkleSibling::Left(left) => keccak(&[*left, hash].concat()).0,
+                MerkleSibling::Right(right) => keccak(&[hash, *right].concat()).0,
+            })
+    }
+
+    /// Checks the leaf belongs to the tree with the given root hash.
+    pub fn verify(&self, leaf_hash: RawH256, root_hash: RawH256) -> bool {
+        self.compute_root(leaf_hash) == root_hash
+    }
+
+    /// Computes the root hash of the tree with `leaves_count` leaves according to the proof of
+    /// the leaf at the given index. Returns `None` if the path of the proof doesn't start at
+    /// that position, including paths through the duplicate of the last node of a level.
+    pub fn compute_root_at(
+        &self,
+        leaf_hash: RawH256,
+        index: u64,
+        leaves_count: u64,
+    ) -> Option<RawH256> {
+        if index >= leaves_count {
+            return None;
+        }
+
+        let mut hash = leaf_hash;
+        let mut index = index;
+        let mut level_len = leaves_count;
+        for sibling in &self.siblings {
+            if level_len <= 1 {
+                return None;
+            }
+            hash = match *sibling {
+                MerkleSibling::Right(right) if index % 2 == 0 => {
+                    // the last node of a level with an odd length is its own sibling
+                    if index + 1 == level_len && right != hash {
+                        return None;
+                    }
+                    keccak(&[hash, right].concat()).0
+                }
+                MerkleSibling::Left(left) if index % 2 == 1 => keccak(&[left, hash].concat()).0,
+                _ => return None,
+            };
+            index /= 2;
+            level_len = level_len.div_ceil(2);
+        }
+
+        (level_len == 1).then_some(hash)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,6 +517,64 @@ mod tests {
         assert_eq!(merkle_tree.subtrees.len(), 0);
     }
 
+    #[test]
+    fn merkle_tree_matches_stream_tree() {
+        let mut merkle_tree = MerkleTree::new();
+        let mut stream_tree = StreamCompactMerkleTree::new();
+        assert_eq!(merkle_tree.compute_hash(), stream_tree.compute_hash());
+
+        for number in 1..=17 {
+            merkle_tree.add(hash(number));
+            stream_tree.add(hash(number));
+            assert_eq!(merkle_tree.compute_hash(), stream_tree.compute_hash());
+        }
+    }
+
+    #[test]
+    fn merkle_proofs() {
+        let mut merkle_tree = MerkleTree::new();
+        assert!(merkle_tree.prove(0).is_none());
+
+        for number in 1..=9 {
+            merkle_tree.add(hash(number));
+            let root_hash = merkle_tree.compute_hash();
+
+            for (index, leaf_hash) in (1..=number).map(hash).enumerate() {
+                let proof = merkle_tree.prove(index).unwrap();
+                assert!(proof.verify(leaf_hash, root_hash));
+                assert!(!proof.verify(hash(0), root_hash));
+            }
+            assert!(merkle_tree.prove(usize::from(number)).is_none());
+        }
+    }
+
+    #[test]
+    fn three_leaf_tree_proof() {
+        let one_hash = hash(1);
+        let two_hash = hash(2);
+        let three_hash = hash(3);
+
+        let mut merkle_tree = MerkleTree::new();
+        merkle_tree.add(one_hash);
+        merkle_tree.add(two_hash);
+        merkle_tree.add(three_hash);
+
+        let proof = merkle_tree.prove(2).unwrap();
+        assert_eq!(
+            proof.siblings,
+            [
+                MerkleSibling::Right(three_hash),
+                MerkleSibling::Left(hash_concatenation(one_hash, two_hash)),
+            ]
+        );
+
+        // Swapping the position of a sibling breaks the proof
+        let mut wrong_proof = proof.clone();
+        wrong_proof.siblings[1] = MerkleSibling::Right(hash_concatenation(one_hash, two_hash));
+        assert!(proof.verify(three_hash, merkle_tree.compute_hash()));
+        assert!(!wrong_proof.verify(three_hash, merkle_tree.compute_hash()));
+    }
+
     fn hash(number: u16) -> RawH256 {
         keccak(&number.to_be_bytes()).0
     }
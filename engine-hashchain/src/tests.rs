@@ -1,6 +1,7 @@
 use crate::{
     bloom::Bloom,
-    hashchain::{Hashchain, HashchainBuilder},
+    hashchain::{compute_tx_hash, BlockTxsProver, Hashchain, HashchainBuilder},
+    merkle::MerkleSibling,
 };
 use aurora_engine_types::account_id::AccountId;
 
@@ -175,6 +176,81 @@ fn test_serialization_round_trip() {
     assert_eq!(round_trip, hashchain);
 }
 
+#[test]
+fn test_block_tx_proofs() {
+    let chain_id = [1; 32];
+    let contract_account_id: AccountId = "aurora".parse().unwrap();
+    let block_height = 2;
+    let initial_hashchain = aurora_engine_sdk::keccak(b"seed").0;
+    let txs: Vec<_> = (0..5u8)
+        .map(|i| {
+            let mut bloom = Bloom::default();
+            bloom.accrue(&[i]);
+            ([i], [i + 1], bloom)
+        })
+        .collect();
+
+    let mut hashchain = HashchainBuilder::default()
+        .with_account_id(contract_account_id.clone())
+        .with_chain_id(chain_id)
+        .with_current_block_height(block_height)
+        .with_previous_hashchain(initial_hashchain)
+        .build();
+    let mut prover = BlockTxsProver::new(
+        chain_id,
+        contract_account_id,
+        block_height,
+        initial_hashchain,
+    );
+    for (input, output, bloom) in &txs {
+        hashchain
+            .add_block_tx(block_height, "submit", input, output, bloom)
+            .unwrap();
+        prover.add_tx("submit", input, output, bloom);
+    }
+    hashchain.move_to_block(block_height + 1).unwrap();
+
+    let block_hashchain = hashchain.get_previous_block_hashchain();
+    assert_eq!(prover.compute_block_hashchain(), block_hashchain);
+
+    for (index, (input, output, _)) in txs.iter().enumerate() {
+        let proof = prover.prove(index).unwrap();
+        assert!(proof.verify("submit", input, output, block_hashchain));
+        // The proof doesn't hold for a different output or another block
+        assert!(!proof.verify("submit", input, b"other", block_hashchain));
+        assert!(!proof.verify("submit", input, output, initial_hashchain));
+    }
+    assert!(prover.prove(txs.len()).is_none());
+}
+
+#[test]
+fn test_block_tx_proof_checks_position() {
+    let mut prover = BlockTxsProver::new([1; 32], "aurora".parse().unwrap(), 2, [0; 32]);
+    for input in [b"a", b"b", b"c"] {
+        prover.add_tx("submit", input, &[], &Bloom::default());
+    }
+    let block_hashchain = prover.compute_block_hashchain();
+    let proof = prover.prove(2).unwrap();
+    assert!(proof.verify("submit", b"c", &[], block_hashchain));
+
+    // The proof doesn't hold at another position.
+    for index in [0, 1, 3] {
+        let mut wrong_proof = proof.clone();
+        wrong_proof.index = index;
+        assert!(!wrong_proof.verify("submit", b"c", &[], block_hashchain));
+    }
+
+    // The last transaction of a block with an odd number of transactions is duplicated in the
+    // tree, but it can't be proven at the padding position.
+    let c_hash = compute_tx_hash("submit", b"c", &[]);
+    let mut padding_proof = proof;
+    padding_proof.merkle_proof.siblings[0] = MerkleSibling::Left(c_hash);
+    for index in [2, 3] {
+        padding_proof.index = index;
+        assert!(!padding_proof.verify("submit", b"c", &[], block_hashchain));
+    }
+}
+
 fn len_be_bytes(arr: &[u8]) -> [u8; 4] {
     let len = arr.len();
     u32::try_from(len).unwrap().to_be_bytes()
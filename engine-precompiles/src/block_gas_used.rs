@@ -0,0 +1,184 @@
+//! EVM gas used per NEAR block.
+//!
+//! The engine adds the gas used by every EVM execution (transactions, `call`, `deploy_code`,
+//! cross-contract call callbacks and `ft_on_transfer`) to the usage of the current block, which
+//! is checked against the block gas limit. The usage of the last `HISTORY_SIZE` blocks is kept
+//! in a ring buffer and served by the precompile.
+
+use super::{EvmPrecompileResult, Precompile};
+use crate::prelude::{
+    sdk::io::{StorageIntermediate, IO},
+    storage::{bytes_to_key, KeyPrefix},
+    types::{make_address, Address, EthGas},
+    Cow::Borrowed,
+    Vec, U256,
+};
+use crate::{utils, PrecompileOutput};
+use aurora_engine_sdk::env::Env;
+use evm::{Context, ExitError};
+
+/// `block_gas_used` precompile address
+///
+/// Address: `0xbb714b7f4cdfbdb3729a1411dcf00713f4ef2fa6`
+/// This address is computed as: `&keccak("blockGasUsed")[12..]`
+pub const ADDRESS: Address = make_address(0xbb714b7f, 0x4cdfbdb3729a1411dcf00713f4ef2fa6);
+
+/// Number of the most recent NEAR blocks whose EVM gas usage is kept.
+pub const HISTORY_SIZE: u64 = 256;
+
+mod costs {
+    use crate::prelude::types::EthGas;
+
+    /// The cost of a cold `SLOAD` since the lookup is a single storage read.
+    pub(super) const BLOCK_GAS_USED_COST: EthGas = EthGas::new(2_100);
+}
+
+/// Records the EVM gas used by the block with the given height.
+pub fn record<I: IO>(io: &mut I, block_height: u64, gas_used: EthGas) {
+    io.write_borsh(&slot_key(block_height), &(block_height, gas_used));
+}
+
+/// Returns the EVM gas used by the block with the given height. Returns `None` if nothing
+/// has been executed at the height or it is no longer in the buffer.
+pub fn get<I: IO>(io: &I, block_height: u64) -> Option<EthGas> {
+    io.read_storage(&slot_key(block_height))
+        .and_then(|bytes| bytes.to_value::<(u64, EthGas)>().ok())
+        .and_then(|(recorded, gas_used)| (recorded == block_height).then_some(gas_used))
+}
+
+fn slot_key(block_height: u64) -> Vec<u8> {
+    bytes_to_key(
+        KeyPrefix::BlockGasUsed,
+        &(block_height % HISTORY_SIZE).to_le_bytes(),
+    )
+}
+
+/// Returns the EVM gas used by a block as a 32-byte big-endian word. The input is the block
+/// height as a 32-byte big-endian word. For the current block, the usage doesn't include the
+/// gas of the ongoing execution.
+pub struct BlockGasUsed<'a, I, E> {
+    io: I,
+    env: &'a E,
+}
+
+impl<'a, I, E> BlockGasUsed<'a, I, E> {
+    pub const fn new(io: I, env: &'a E) -> Self {
+        Self { io, env }
+    }
+}
+
+impl<'a, I: IO, E: Env> Precompile for BlockGasUsed<'a, I, E> {
+    fn required_gas(_input: &[u8]) -> Result<EthGas, ExitError> {
+        Ok(costs::BLOCK_GAS_USED_COST)
+    }
+
+    fn run(
+        &self,
+        input: &[u8],
+        target_gas: Option<EthGas>,
+        context: &Context,
+        _is_static: bool,
+    ) -> EvmPrecompileResult {
+        utils::validate_no_value_attached_to_precompile(context.apparent_value)?;
+        let cost = Self::required_gas(input)?;
+        if let Some(target_gas) = target_gas {
+            if cost > target_gas {
+                return Err(ExitError::OutOfGas);
+            }
+        }
+
+        if input.len() != 32 {
+            return Err(ExitError::Other(Borrowed("ERR_INVALID_INPUT")));
+        }
+        let block_height = U256::from_big_endian(input);
+        let current_height = U256::from(self.env.block_height());
+        // Future blocks and blocks which are no longer in the buffer are rejected, the blocks
+        // of the buffer without EVM executions used no gas.
+        if block_height > current_height || current_height - block_height >= HISTORY_SIZE.into() {
+            return Err(ExitError::Other(Borrowed("ERR_BLOCK_HEIGHT_OUT_OF_RANGE")));
+        }
+
+        // `block_height` isn't above the current height here, so it fits into `u64`.
+        let gas_used = get(&self.io, block_height.low_u64()).unwrap_or_default();
+        let mut output = [0u8; 32];
+        gas_used.as_u256().to_big_endian(&mut output);
+        Ok(PrecompileOutput::without_logs(cost, output.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, record, BlockGasUsed, HISTORY_SIZE};
+    use crate::prelude::{sdk, types::EthGas, H160, U256};
+    use crate::Precompile;
+    use aurora_engine_sdk::env::Fixed;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    fn context() -> evm::Context {
+        evm::Context {
+            address: H160::default(),
+            caller: H160::default(),
+            apparent_value: U256::zero(),
+        }
+    }
+
+    fn input(block_height: u64) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        U256::from(block_height).to_big_endian(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_precompile_id() {
+        assert_eq!(
+            super::ADDRESS.as_bytes(),
+            &sdk::keccak(b"blockGasUsed").as_bytes()[12..]
+        );
+    }
+
+    #[test]
+    fn test_record_overwrites_old_slots() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+
+        record(&mut io, 10, EthGas::new(21_000));
+        assert_eq!(get(&io, 10), Some(EthGas::new(21_000)));
+        assert_eq!(get(&io, 11), None);
+
+        record(&mut io, 10 + HISTORY_SIZE, EthGas::new(42_000));
+        assert_eq!(get(&io, 10), None);
+        assert_eq!(get(&io, 10 + HISTORY_SIZE), Some(EthGas::new(42_000)));
+    }
+
+    #[test]
+    fn test_precompile_serves_block_gas_used() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        record(&mut io, HISTORY_SIZE + 5, EthGas::new(21_000));
+        let env = Fixed {
+            block_height: HISTORY_SIZE + 10,
+            ..Fixed::default()
+        };
+        let precompile = BlockGasUsed::new(io, &env);
+
+        let output = precompile
+            .run(&input(HISTORY_SIZE + 5), None, &context(), false)
+            .unwrap();
+        assert_eq!(U256::from_big_endian(&output.output), U256::from(21_000));
+        // Blocks without EVM executions used no gas.
+        for block_height in [11, HISTORY_SIZE + 10] {
+            let output = precompile
+                .run(&input(block_height), None, &context(), false)
+                .unwrap();
+            assert_eq!(U256::from_big_endian(&output.output), U256::zero());
+        }
+
+        for block_height in [10, HISTORY_SIZE + 11] {
+            assert!(precompile
+                .run(&input(block_height), None, &context(), false)
+                .is_err());
+        }
+        assert!(precompile.run(&[0; 31], None, &context(), false).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Block hash history served through the [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935)
+//! system contract interface.
+//!
+//! The `BLOCKHASH` opcode only covers the last 256 blocks. Aurora block hashes are derived from
+//! the block height (see [`compute_block_hash`]), so instead of keeping a history in the storage
+//! the precompile computes the hash of any block within the last `HISTORY_SERVE_WINDOW` blocks.
+//! Contracts query it with the standard `get` calldata (the block number as a 32-byte big-endian
+//! word).
+
+use super::{EvmPrecompileResult, Precompile};
+use crate::prelude::{
+    account_id::AccountId,
+    sdk,
+    types::{make_address, Address, EthGas},
+    Cow::Borrowed,
+    Vec, H256, U256,
+};
+use crate::{utils, PrecompileOutput};
+use aurora_engine_sdk::env::Env;
+use core::mem;
+use evm::{Context, ExitError};
+
+/// EIP-2935 history storage contract address
+///
+/// Address: `0x0000f90827f1c53a10cb7a02335b175320002935`
+/// This address is defined by EIP-2935.
+pub const ADDRESS: Address = make_address(0x0000f908, 0x27f1c53a10cb7a02335b175320002935);
+
+/// Number of the most recent blocks whose hashes are served.
+pub const HISTORY_SERVE_WINDOW: u64 = 8191;
+
+/// Used as the first byte in the concatenation of data used to compute the blockhash.
+/// Could be useful in the future as a version byte, or to distinguish different types of blocks.
+const BLOCK_HASH_PREFIX: u8 = 0;
+const BLOCK_HASH_PREFIX_SIZE: usize = 1;
+const BLOCK_HEIGHT_SIZE: usize = 8;
+const CHAIN_ID_SIZE: usize = 32;
+
+mod costs {
+    use crate::prelude::types::EthGas;
+
+    /// The cost of a cold `SLOAD`, which is what the EIP-2935 contract spends on a lookup.
+    pub(super) const BLOCK_HASH_HISTORY_COST: EthGas = EthGas::new(2_100);
+}
+
+/// There is one Aurora block per NEAR block height (note: when heights in NEAR are skipped
+/// they are interpreted as empty blocks on Aurora). The blockhash is derived from the height
+/// according to
+/// ```text
+/// block_hash = sha256(concat(
+///     BLOCK_HASH_PREFIX,
+///     block_height as u64,
+///     chain_id,
+///     engine_account_id,
+/// ))
+/// ```
+#[must_use]
+pub fn compute_block_hash(chain_id: [u8; 32], block_height: u64, account_id: &[u8]) -> H256 {
+    debug_assert_eq!(BLOCK_HASH_PREFIX_SIZE, mem::size_of_val(&BLOCK_HASH_PREFIX));
+    debug_assert_eq!(BLOCK_HEIGHT_SIZE, mem::size_of_val(&block_height));
+    debug_assert_eq!(CHAIN_ID_SIZE, mem::size_of_val(&chain_id));
+    let mut data = Vec::with_capacity(
+        BLOCK_HASH_PREFIX_SIZE + BLOCK_HEIGHT_SIZE + CHAIN_ID_SIZE + account_id.len(),
+    );
+    data.push(BLOCK_HASH_PREFIX);
+    data.extend_from_slice(&chain_id);
+    data.extend_from_slice(account_id);
+    data.extend_from_slice(&block_height.to_be_bytes());
+
+    sdk::sha256(&data)
+}
+
+pub struct BlockHashHistory<'a, E> {
+    chain_id: [u8; 32],
+    current_account_id: AccountId,
+    env: &'a E,
+}
+
+impl<'a, E> BlockHashHistory<'a, E> {
+    pub const fn new(chain_id: [u8; 32], current_account_id: AccountId, env: &'a E) -> Self {
+        Self {
+            chain_id,
+            current_account_id,
+            env,
+        }
+    }
+}
+
+impl<'a, E: Env> Precompile for BlockHashHistory<'a, E> {
+    fn required_gas(_input: &[u8]) -> Result<EthGas, ExitError> {
+        Ok(costs::BLOCK_HASH_HISTORY_COST)
+    }
+
+    fn run(
+        &self,
+        input: &[u8],
+        target_gas: Option<EthGas>,
+        context: &Context,
+        _is_static: bool,
+    ) -> EvmPrecompileResult {
+        utils::validate_no_value_attached_to_precompile(context.apparent_value)?;
+        let cost = Self::required_gas(input)?;
+        if let Some(target_gas) = target_gas {
+            if cost > target_gas {
+                return Err(ExitError::OutOfGas);
+            }
+        }
+
+        if input.len() != 32 {
+            return Err(ExitError::Other(Borrowed("ERR_INVALID_INPUT")));
+        }
+        let number = U256::from_big_endian(input);
+        let block_height = self.env.block_height();
+        // Same as in EIP-2935, requests for the current block, future blocks
+        // or blocks outside of the window fail.
+        if number >= U256::from(block_height)
+            || U256::from(block_height) - number > U256::from(HISTORY_SERVE_WINDOW)
+        {
+            return Err(ExitError::Other(Borrowed("ERR_BLOCK_NUMBER_OUT_OF_RANGE")));
+        }
+
+        // `number` is below `block_height` here, so it fits into `u64`.
+        let hash = compute_block_hash(
+            self.chain_id,
+            number.low_u64(),
+            self.current_account_id.as_bytes(),
+        );
+        Ok(PrecompileOutput::without_logs(
+            cost,
+            hash.as_bytes().to_vec(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_block_hash, BlockHashHistory, HISTORY_SERVE_WINDOW};
+    use crate::prelude::{account_id::AccountId, H160, U256};
+    use crate::Precompile;
+    use aurora_engine_sdk::env::Fixed;
+
+    const CHAIN_ID: [u8; 32] = [1; 32];
+
+    fn context() -> evm::Context {
+        evm::Context {
+            address: H160::default(),
+            caller: H160::default(),
+            apparent_value: U256::zero(),
+        }
+    }
+
+    fn env(block_height: u64) -> Fixed {
+        Fixed {
+            block_height,
+            ..Fixed::default()
+        }
+    }
+
+    fn input(number: u64) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        U256::from(number).to_big_endian(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_precompile_computes_block_hashes() {
+        let account_id: AccountId = "aurora".parse().unwrap();
+        let env = env(HISTORY_SERVE_WINDOW + 10);
+        let precompile = BlockHashHistory::new(CHAIN_ID, account_id.clone(), &env);
+
+        // Every block within the window is served, including the oldest and the latest one.
+        for number in [10, 11, HISTORY_SERVE_WINDOW + 9] {
+            let output = precompile
+                .run(&input(number), None, &context(), false)
+                .unwrap();
+            let expected = compute_block_hash(CHAIN_ID, number, account_id.as_bytes());
+            assert_eq!(output.output, expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_precompile_rejects_invalid_requests() {
+        let env = env(HISTORY_SERVE_WINDOW + 10);
+        let precompile = BlockHashHistory::new(CHAIN_ID, AccountId::default(), &env);
+
+        for number in [HISTORY_SERVE_WINDOW + 10, HISTORY_SERVE_WINDOW + 11, 9] {
+            assert!(precompile
+                .run(&input(number), None, &context(), false)
+                .is_err());
+        }
+        assert!(precompile.run(&[0u8; 31], None, &context(), false).is_err());
+    }
+}
@@ -0,0 +1,321 @@
+//! History of the block hashchain values.
+//!
+//! The engine only keeps the hashchain of the previous block in its state. When the hashchain
+//! moves to a new block, the value of the block left behind is recorded in a ring buffer of
+//! `HISTORY_SIZE` slots together with the height of the previously recorded block. The blocks
+//! skipped by the move have no transactions, so their values aren't recorded but computed from
+//! the last recorded block when they are read. The hashchain of a block is only known once the
+//! engine processes a transaction in a later block.
+
+use super::{EvmPrecompileResult, Precompile};
+use crate::prelude::{
+    account_id::AccountId,
+    sdk::io::{StorageIntermediate, IO},
+    storage::{bytes_to_key, KeyPrefix},
+    types::{make_address, Address, EthGas, RawH256},
+    Cow::Borrowed,
+    Vec, U256,
+};
+use crate::{utils, PrecompileOutput};
+use aurora_engine_hashchain::{bloom::Bloom, hashchain::compute_block_hashchain};
+use evm::{Context, ExitError};
+
+/// `hashchain_history` precompile address
+///
+/// Address: `0x0dc1fa0dc704930652ee9a97d864d579087870f1`
+/// This address is computed as: `&keccak("hashchainHistory")[12..]`
+pub const ADDRESS: Address = make_address(0x0dc1fa0d, 0xc704930652ee9a97d864d579087870f1);
+
+/// Number of the most recent blocks whose hashchain values are served.
+pub const HISTORY_SIZE: u64 = 8192;
+
+const HISTORY_KEY: &[u8] = b"HC_HISTORY";
+const HISTORY_HEAD_KEY: &[u8] = b"HC_HISTORY_HEAD";
+
+mod costs {
+    use crate::prelude::types::EthGas;
+
+    /// The cost of a cold `SLOAD`, charged for every storage read of the lookup.
+    pub(super) const HASHCHAIN_HISTORY_READ_COST: EthGas = EthGas::new(2_100);
+
+    /// The cost of the `KECCAK256` of the data hashed per empty block, which is at most
+    /// 14 words long: `30 + 6 * 14`.
+    pub(super) const EMPTY_BLOCK_HASHCHAIN_COST: EthGas = EthGas::new(114);
+}
+
+/// Records the hashchain of the block left behind by the hashchain, which has moved to
+/// `next_block_height`.
+pub fn record<I: IO>(io: &mut I, block_height: u64, hashchain: RawH256, next_block_height: u64) {
+    let last_recorded_height = read_head(io).map(|(last_recorded_height, _)| last_recorded_height);
+    io.write_borsh(
+        &slot_key(block_height),
+        &(block_height, hashchain, last_recorded_height),
+    );
+    io.write_borsh(&head_key(), &(block_height, next_block_height));
+}
+
+/// Unlinks the history from the blocks recorded so far, e.g. when the hashchain is restarted.
+pub fn reset<I: IO>(io: &mut I) {
+    io.remove_storage(&head_key());
+}
+
+/// Returns the hashchain of the block with the given height, if it is still in the history.
+pub fn get<I: IO>(
+    io: &I,
+    chain_id: &[u8; 32],
+    contract_account_id: &AccountId,
+    block_height: u64,
+) -> Option<RawH256> {
+    find_recorded_block(io, block_height)
+        .map(|recorded| recorded.compute_hashchain(chain_id, contract_account_id, block_height))
+}
+
+/// The closest recorded block at or below the requested height.
+struct RecordedBlock {
+    block_height: u64,
+    hashchain: RawH256,
+    storage_reads: u64,
+}
+
+impl RecordedBlock {
+    /// Computes the hashchain of the requested block from the recorded one. The blocks in
+    /// between have no transactions.
+    fn compute_hashchain(
+        &self,
+        chain_id: &[u8; 32],
+        contract_account_id: &AccountId,
+        block_height: u64,
+    ) -> RawH256 {
+        (self.block_height + 1..=block_height).fold(self.hashchain, |hashchain, height| {
+            compute_block_hashchain(
+                chain_id,
+                contract_account_id.as_bytes(),
+                height,
+                hashchain,
+                [0; 32],
+                &Bloom::default(),
+            )
+        })
+    }
+}
+
+/// Walks the recorded blocks from the most recent one down to the given height.
+fn find_recorded_block<I: IO>(io: &I, block_height: u64) -> Option<RecordedBlock> {
+    let (mut recorded_height, next_block_height) = read_head(io)?;
+    if block_height >= next_block_height || next_block_height - block_height > HISTORY_SIZE {
+        return None;
+    }
+
+    let mut storage_reads = 1;
+    loop {
+        storage_reads += 1;
+        let (height, hashchain, last_recorded_height) = io
+            .read_storage(&slot_key(recorded_height))
+            .and_then(|bytes| bytes.to_value::<(u64, RawH256, Option<u64>)>().ok())
+            // The slot has been overwritten by a later block.
+            .filter(|(height, _, _)| *height == recorded_height)?;
+
+        if height <= block_height {
+            return Some(RecordedBlock {
+                block_height: height,
+                hashchain,
+                storage_reads,
+            });
+        }
+        recorded_height = last_recorded_height?;
+    }
+}
+
+fn read_head<I: IO>(io: &I) -> Option<(u64, u64)> {
+    io.read_storage(&head_key())
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+fn slot_key(block_height: u64) -> Vec<u8> {
+    let slot = block_height % HISTORY_SIZE;
+    bytes_to_key(
+        KeyPrefix::Hashchain,
+        &[HISTORY_KEY, &slot.to_le_bytes()].concat(),
+    )
+}
+
+fn head_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::Hashchain, HISTORY_HEAD_KEY)
+}
+
+/// Returns the hashchain of a block. The input is the block height as a 32-byte big-endian
+/// word.
+pub struct HashchainHistory<I> {
+    io: I,
+    chain_id: [u8; 32],
+    current_account_id: AccountId,
+}
+
+impl<I> HashchainHistory<I> {
+    pub const fn new(io: I, chain_id: [u8; 32], current_account_id: AccountId) -> Self {
+        Self {
+            io,
+            chain_id,
+            current_account_id,
+        }
+    }
+}
+
+impl<I: IO> Precompile for HashchainHistory<I> {
+    fn required_gas(_input: &[u8]) -> Result<EthGas, ExitError> {
+        Ok(costs::HASHCHAIN_HISTORY_READ_COST)
+    }
+
+    fn run(
+        &self,
+        input: &[u8],
+        target_gas: Option<EthGas>,
+        context: &Context,
+        _is_static: bool,
+    ) -> EvmPrecompileResult {
+        utils::validate_no_value_attached_to_precompile(context.apparent_value)?;
+        let min_cost = Self::required_gas(input)?;
+        if let Some(target_gas) = target_gas {
+            if min_cost > target_gas {
+                return Err(ExitError::OutOfGas);
+            }
+        }
+
+        if input.len() != 32 {
+            return Err(ExitError::Other(Borrowed("ERR_INVALID_INPUT")));
+        }
+        let block_height = U256::from_big_endian(input);
+        let (block_height, recorded) = u64::try_from(block_height)
+            .ok()
+            .and_then(|block_height| {
+                find_recorded_block(&self.io, block_height).map(|recorded| (block_height, recorded))
+            })
+            .ok_or(ExitError::Other(Borrowed("ERR_HASHCHAIN_NOT_FOUND")))?;
+
+        // The lookup costs a storage read per visited block and a hash per empty block.
+        let cost = costs::HASHCHAIN_HISTORY_READ_COST * recorded.storage_reads
+            + costs::EMPTY_BLOCK_HASHCHAIN_COST * (block_height - recorded.block_height);
+        if let Some(target_gas) = target_gas {
+            if cost > target_gas {
+                return Err(ExitError::OutOfGas);
+            }
+        }
+
+        let hashchain =
+            recorded.compute_hashchain(&self.chain_id, &self.current_account_id, block_height);
+        Ok(PrecompileOutput::without_logs(cost, hashchain.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, record, reset, HashchainHistory, HISTORY_SIZE};
+    use crate::prelude::{account_id::AccountId, sdk, H160, U256};
+    use crate::Precompile;
+    use aurora_engine_hashchain::hashchain::Hashchain;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    const CHAIN_ID: [u8; 32] = [1; 32];
+
+    fn context() -> evm::Context {
+        evm::Context {
+            address: H160::default(),
+            caller: H160::default(),
+            apparent_value: U256::zero(),
+        }
+    }
+
+    fn input(block_height: u64) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        U256::from(block_height).to_big_endian(&mut buf);
+        buf
+    }
+
+    fn account_id() -> AccountId {
+        "aurora".parse().unwrap()
+    }
+
+    /// Returns the hashchain of the block computed by the hashchain itself.
+    fn expected_hashchain(from: u64, hashchain: [u8; 32], block_height: u64) -> [u8; 32] {
+        let mut expected = Hashchain::new(CHAIN_ID, account_id(), from + 1, hashchain);
+        expected.move_to_block(block_height + 1).unwrap();
+        expected.get_previous_block_hashchain()
+    }
+
+    #[test]
+    fn test_precompile_id() {
+        assert_eq!(
+            super::ADDRESS.as_bytes(),
+            &sdk::keccak(b"hashchainHistory").as_bytes()[12..]
+        );
+    }
+
+    #[test]
+    fn test_empty_blocks_are_computed_from_recorded_ones() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let account_id = account_id();
+
+        record(&mut io, 5, [1; 32], 8);
+        record(&mut io, 8, [2; 32], 12);
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 4), None);
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 5), Some([1; 32]));
+        for block_height in 6..8 {
+            assert_eq!(
+                get(&io, &CHAIN_ID, &account_id, block_height),
+                Some(expected_hashchain(5, [1; 32], block_height))
+            );
+        }
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 8), Some([2; 32]));
+        assert_eq!(
+            get(&io, &CHAIN_ID, &account_id, 11),
+            Some(expected_hashchain(8, [2; 32], 11))
+        );
+        // The hashchain of the current block isn't known yet.
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 12), None);
+
+        // A restarted hashchain isn't linked to the blocks recorded before.
+        reset(&mut io);
+        record(&mut io, 20, [3; 32], 21);
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 20), Some([3; 32]));
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 8), None);
+    }
+
+    #[test]
+    fn test_old_blocks_are_dropped() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let account_id = account_id();
+
+        record(&mut io, 5, [1; 32], 6);
+        record(&mut io, 6, [2; 32], 5 + HISTORY_SIZE);
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 5), Some([1; 32]));
+        record(&mut io, 5 + HISTORY_SIZE, [3; 32], 6 + HISTORY_SIZE);
+        assert_eq!(get(&io, &CHAIN_ID, &account_id, 5), None);
+        assert_eq!(
+            get(&io, &CHAIN_ID, &account_id, 5 + HISTORY_SIZE),
+            Some([3; 32])
+        );
+    }
+
+    #[test]
+    fn test_precompile_serves_hashchains() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        record(&mut io, 10, [1; 32], 13);
+
+        let precompile = HashchainHistory::new(io, CHAIN_ID, account_id());
+        let output = precompile.run(&input(10), None, &context(), false).unwrap();
+        assert_eq!(output.output, [1; 32]);
+        let output = precompile.run(&input(12), None, &context(), false).unwrap();
+        assert_eq!(output.output, expected_hashchain(10, [1; 32], 12));
+
+        assert!(precompile.run(&input(13), None, &context(), false).is_err());
+        assert!(precompile.run(&input(9), None, &context(), false).is_err());
+        assert!(precompile
+            .run(&[0xff; 32], None, &context(), false)
+            .is_err());
+        assert!(precompile.run(&[0; 31], None, &context(), false).is_err());
+    }
+}
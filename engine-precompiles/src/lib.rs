@@ -11,7 +11,10 @@
 pub mod account_ids;
 pub mod alt_bn256;
 pub mod blake2;
+pub mod block_gas_used;
+pub mod block_hash_history;
 pub mod hash;
+pub mod hashchain_history;
 pub mod identity;
 pub mod modexp;
 pub mod native;
@@ -21,12 +24,16 @@ pub mod promise_result;
 pub mod random;
 pub mod secp256k1;
 mod utils;
+pub mod withdrawal_limits;
 pub mod xcc;
 
 use crate::account_ids::{predecessor_account, CurrentAccount, PredecessorAccount};
 use crate::alt_bn256::{Bn256Add, Bn256Mul, Bn256Pair};
 use crate::blake2::Blake2F;
+use crate::block_gas_used::BlockGasUsed;
+use crate::block_hash_history::BlockHashHistory;
 use crate::hash::{RIPEMD160, SHA256};
+use crate::hashchain_history::HashchainHistory;
 use crate::identity::Identity;
 use crate::modexp::ModExp;
 use crate::native::{exit_to_ethereum, exit_to_near, ExitToEthereum, ExitToNear};
@@ -35,6 +42,7 @@ use crate::prelude::{Vec, H256};
 use crate::prepaid_gas::PrepaidGas;
 use crate::random::RandomSeed;
 use crate::secp256k1::ECRecover;
+use crate::withdrawal_limits::WithdrawalLimiter;
 use crate::xcc::CrossContractCall;
 use aurora_engine_modexp::ModExpAlgorithm;
 use aurora_engine_sdk::env::Env;
@@ -149,6 +157,9 @@ impl<'a, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> executor::stack::Preco
             AllPrecompiles::PredecessorAccount(p) => process_precompile(p, handle),
             AllPrecompiles::PrepaidGas(p) => process_precompile(p, handle),
             AllPrecompiles::PromiseResult(p) => process_precompile(p, handle),
+            AllPrecompiles::BlockHashHistory(p) => process_precompile(p, handle),
+            AllPrecompiles::HashchainHistory(p) => process_precompile(p, handle),
+            AllPrecompiles::BlockGasUsed(p) => process_precompile(p, handle),
             AllPrecompiles::CrossContractCall(p) => process_handle_based_precompile(p, handle),
             AllPrecompiles::Generic(p) => process_precompile(p.as_ref(), handle),
         };
@@ -197,6 +208,7 @@ fn post_process(
 
 pub struct PrecompileConstructorContext<'a, I, E, H, M> {
     pub current_account_id: AccountId,
+    pub chain_id: [u8; 32],
     pub random_seed: H256,
     pub io: I,
     pub env: &'a E,
@@ -355,15 +367,26 @@ impl<'a, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> Precompiles<'a, I, E,
         mut generic_precompiles: BTreeMap<Address, AllPrecompiles<'a, I, E, H>>,
         ctx: PrecompileConstructorContext<'a, I, E, H, M>,
     ) -> Self {
-        let near_exit = ExitToNear::new(ctx.current_account_id.clone(), ctx.io);
+        let withdrawal_limiter = WithdrawalLimiter::new(ctx.env.block_timestamp().secs());
+        let near_exit = ExitToNear::new(
+            ctx.current_account_id.clone(),
+            ctx.io,
+            withdrawal_limiter.clone(),
+        );
         #[cfg(not(feature = "ext-connector"))]
-        let ethereum_exit = ExitToEthereum::new(ctx.current_account_id.clone(), ctx.io);
+        let ethereum_exit =
+            ExitToEthereum::new(ctx.current_account_id.clone(), ctx.io, withdrawal_limiter);
         #[cfg(feature = "ext-connector")]
-        let ethereum_exit = ExitToEthereum::new(ctx.io);
+        let ethereum_exit = ExitToEthereum::new(ctx.io, withdrawal_limiter);
+        let block_hash_history =
+            BlockHashHistory::new(ctx.chain_id, ctx.current_account_id.clone(), ctx.env);
+        let hashchain_history =
+            HashchainHistory::new(ctx.io, ctx.chain_id, ctx.current_account_id.clone());
         let cross_contract_call = CrossContractCall::new(ctx.current_account_id, ctx.io);
         let predecessor_account_id = PredecessorAccount::new(ctx.env);
         let prepaid_gas = PrepaidGas::new(ctx.env);
         let promise_results = PromiseResult::new(ctx.promise_handler);
+        let block_gas_used = BlockGasUsed::new(ctx.io, ctx.env);
 
         generic_precompiles.insert(exit_to_near::ADDRESS, AllPrecompiles::ExitToNear(near_exit));
         generic_precompiles.insert(
@@ -386,6 +409,18 @@ impl<'a, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> Precompiles<'a, I, E,
             promise_result::ADDRESS,
             AllPrecompiles::PromiseResult(promise_results),
         );
+        generic_precompiles.insert(
+            block_hash_history::ADDRESS,
+            AllPrecompiles::BlockHashHistory(block_hash_history),
+        );
+        generic_precompiles.insert(
+            hashchain_history::ADDRESS,
+            AllPrecompiles::HashchainHistory(hashchain_history),
+        );
+        generic_precompiles.insert(
+            block_gas_used::ADDRESS,
+            AllPrecompiles::BlockGasUsed(block_gas_used),
+        );
 
         Self {
             all_precompiles: generic_precompiles,
@@ -401,6 +436,9 @@ pub enum AllPrecompiles<'a, I, E, H> {
     PredecessorAccount(PredecessorAccount<'a, E>),
     PrepaidGas(PrepaidGas<'a, E>),
     PromiseResult(PromiseResult<H>),
+    BlockHashHistory(BlockHashHistory<'a, E>),
+    HashchainHistory(HashchainHistory<I>),
+    BlockGasUsed(BlockGasUsed<'a, I, E>),
     Generic(Box<dyn Precompile>),
 }
 
@@ -1,5 +1,5 @@
 use super::{EvmPrecompileResult, Precompile};
-use crate::prelude::types::EthGas;
+use crate::prelude::types::{EthGas, NearGas};
 use crate::prelude::{
     format,
     parameters::{PromiseArgs, PromiseCreateArgs},
@@ -7,28 +7,43 @@ use crate::prelude::{
     storage::{bytes_to_key, KeyPrefix},
     str,
     types::{Address, Yocto},
-    vec, Cow, ToString, Vec, U256,
+    Box, Cow, String, ToString, Vec, U256,
 };
 #[cfg(feature = "error_refund")]
-use crate::prelude::{parameters::RefundCallArgs, types};
+use crate::prelude::{
+    parameters::{mt::MtExitCallbackArgs, RefundCallArgs},
+    types,
+};
+use crate::withdrawal_limits::{self, WithdrawalLimiter};
 use crate::xcc::state::get_wnear_address;
 use crate::PrecompileOutput;
-use aurora_engine_types::parameters::connector::WithdrawSerializeType;
+use aurora_engine_types::parameters::connector::{NEP141FtTransferCallArgs, WithdrawSerializeType};
 use aurora_engine_types::parameters::WithdrawCallArgs;
 use aurora_engine_types::storage::EthConnectorStorageId;
 use aurora_engine_types::{
     account_id::AccountId,
     borsh,
     parameters::{
+        mt::{NEP245MtBatchTransferArgs, NEP245MtTransferArgs},
+        nft::NEP171NftTransferArgs,
+        withdrawal_limits::WithdrawalLimitTarget,
         ExitToNearPrecompileCallbackCallArgs, PromiseWithCallbackArgs, TransferNearCallArgs,
     },
-    types::NEP141Wei,
+    types::{u256_to_arr, Balance, NEP141Wei},
 };
 use evm::backend::Log;
 use evm::{Context, ExitError};
 
 const ERR_TARGET_TOKEN_NOT_FOUND: &str = "Target token not found";
 const UNWRAP_WNEAR_MSG: &str = "unwrap";
+/// Prefix of the message turning an exit to NEAR into a `ft_transfer_call`, the rest of
+/// the message is passed to the receiver. Other messages are ignored (except `unwrap`).
+const FT_TRANSFER_CALL_MSG_PREFIX: &str = "ft_transfer_call:";
+/// Maximum length of the message passed to the receiver of a `ft_transfer_call`.
+const MAX_FT_TRANSFER_CALL_MSG_LEN: usize = 1024;
+/// Flag, refund address, amount, recipient account id, `:` separator and the message.
+const MAX_EXIT_TO_NEAR_INPUT_LEN: usize =
+    117 + 1 + FT_TRANSFER_CALL_MSG_PREFIX.len() + MAX_FT_TRANSFER_CALL_MSG_LEN;
 
 mod costs {
     use crate::prelude::types::{EthGas, NearGas};
@@ -43,6 +58,23 @@ mod costs {
     /// `https://explorer.mainnet.near.org/transactions/5CD7NrqWpK3H8MAAU4mYEPuuWz9AqR9uJkkZJzw5b8PM#D1b5NVRrAsJKUX2ZGs3poKViu1Rgt4RJZXtTfMgdxH4S`
     pub(super) const FT_TRANSFER_GAS: NearGas = NearGas::new(10_000_000_000_000);
 
+    /// Leaves about 40 Tgas to the `ft_on_transfer` of the receiver once the NEP-141
+    /// contract reserved the gas of `ft_transfer_call` and `ft_resolve_transfer`.
+    pub(super) const FT_TRANSFER_CALL_GAS: NearGas = NearGas::new(70_000_000_000_000);
+
+    /// Gas of `nft_transfer`, which only moves the token on the NEP-171 contract (there is no
+    /// receiver to call, unlike with `nft_transfer_call`). The gas attached to the promise is
+    /// checked by the NFT bridge tests of `engine-tests`.
+    pub(super) const NFT_TRANSFER_GAS: NearGas = NearGas::new(15_000_000_000_000);
+
+    /// Gas of `mt_transfer`, which only moves the balance of one token on the NEP-245 contract
+    /// (there is no receiver to call, unlike with `mt_transfer_call`).
+    pub(super) const MT_TRANSFER_GAS: NearGas = NearGas::new(20_000_000_000_000);
+
+    /// Gas of `mt_batch_transfer` per token of the batch, on top of `MT_TRANSFER_GAS`:
+    /// the balances of every token are moved and logged.
+    pub(super) const MT_BATCH_TRANSFER_GAS_PER_TOKEN: NearGas = NearGas::new(2_000_000_000_000);
+
     /// Value determined experimentally based on tests.
     pub(super) const EXIT_TO_NEAR_CALLBACK_GAS: NearGas = NearGas::new(10_000_000_000_000);
 
@@ -59,6 +91,16 @@ pub mod events {
         0xd0c23f45cd28abb31403a5392f6dd0c7,
     );
     /// Derived from event signature (see `tests::test_exit_signatures`)
+    pub const EXIT_NFT_TO_NEAR_SIGNATURE: H256 = crate::make_h256(
+        0x2563126e568f1a461f925bda19c37ce8,
+        0x4b71cb61649367073897a9f95f3f260d,
+    );
+    /// Derived from event signature (see `tests::test_exit_signatures`)
+    pub const EXIT_MULTI_TOKEN_TO_NEAR_SIGNATURE: H256 = crate::make_h256(
+        0x932586038263068432fd90273a388800,
+        0x075ab611a33f9c5b6ac436afdf7ae75c,
+    );
+    /// Derived from event signature (see `tests::test_exit_signatures`)
     pub const EXIT_TO_ETH_SIGNATURE: H256 = crate::make_h256(
         0xd046c2bb01a5622bc4b9696332391d87,
         0x491373762eeac0831c48400e2d5a5f07,
@@ -102,6 +144,71 @@ pub mod events {
         }
     }
 
+    /// `ExitNftToNear`(
+    ///    Address indexed sender,
+    ///    Address indexed `erc721_address`,
+    ///    string indexed dest,
+    ///    uint `token_id`
+    /// )
+    /// Note: `sender` == `erc721_address` because it is the ERC-721 contract which
+    /// calls the exit precompile.
+    pub struct ExitNftToNear {
+        pub sender: Address,
+        pub erc721_address: Address,
+        pub dest: String,
+        pub token_id: U256,
+    }
+
+    impl ExitNftToNear {
+        #[must_use]
+        pub fn encode(self) -> ethabi::RawLog {
+            let data = ethabi::encode(&[ethabi::Token::Int(self.token_id)]);
+            let topics = vec![
+                EXIT_NFT_TO_NEAR_SIGNATURE,
+                encode_address(self.sender),
+                encode_address(self.erc721_address),
+                aurora_engine_sdk::keccak(&ethabi::encode(&[ethabi::Token::String(self.dest)])),
+            ];
+
+            ethabi::RawLog { topics, data }
+        }
+    }
+
+    /// `ExitMultiTokenToNear`(
+    ///    Address indexed sender,
+    ///    Address indexed `erc1155_address`,
+    ///    string indexed dest,
+    ///    uint `token_id`,
+    ///    uint amount
+    /// )
+    /// Note: `sender` == `erc1155_address` because it is the ERC-1155 contract which
+    /// calls the exit precompile. One event is emitted per token of the batch.
+    pub struct ExitMultiTokenToNear {
+        pub sender: Address,
+        pub erc1155_address: Address,
+        pub dest: String,
+        pub token_id: U256,
+        pub amount: U256,
+    }
+
+    impl ExitMultiTokenToNear {
+        #[must_use]
+        pub fn encode(self) -> ethabi::RawLog {
+            let data = ethabi::encode(&[
+                ethabi::Token::Int(self.token_id),
+                ethabi::Token::Int(self.amount),
+            ]);
+            let topics = vec![
+                EXIT_MULTI_TOKEN_TO_NEAR_SIGNATURE,
+                encode_address(self.sender),
+                encode_address(self.erc1155_address),
+                aurora_engine_sdk::keccak(&ethabi::encode(&[ethabi::Token::String(self.dest)])),
+            ];
+
+            ethabi::RawLog { topics, data }
+        }
+    }
+
     /// `ExitToEth`(
     ///    Address indexed sender,
     ///    Address indexed `erc20_address`,
@@ -170,6 +277,71 @@ pub mod events {
         }
     }
 
+    #[must_use]
+    pub fn exit_nft_to_near_schema() -> ethabi::Event {
+        ethabi::Event {
+            name: "ExitNftToNear".to_string(),
+            inputs: vec![
+                ethabi::EventParam {
+                    name: "sender".to_string(),
+                    kind: ethabi::ParamType::Address,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "erc721_address".to_string(),
+                    kind: ethabi::ParamType::Address,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "dest".to_string(),
+                    kind: ethabi::ParamType::String,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "token_id".to_string(),
+                    kind: ethabi::ParamType::Uint(256),
+                    indexed: false,
+                },
+            ],
+            anonymous: false,
+        }
+    }
+
+    #[must_use]
+    pub fn exit_multi_token_to_near_schema() -> ethabi::Event {
+        ethabi::Event {
+            name: "ExitMultiTokenToNear".to_string(),
+            inputs: vec![
+                ethabi::EventParam {
+                    name: "sender".to_string(),
+                    kind: ethabi::ParamType::Address,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "erc1155_address".to_string(),
+                    kind: ethabi::ParamType::Address,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "dest".to_string(),
+                    kind: ethabi::ParamType::String,
+                    indexed: true,
+                },
+                ethabi::EventParam {
+                    name: "token_id".to_string(),
+                    kind: ethabi::ParamType::Uint(256),
+                    indexed: false,
+                },
+                ethabi::EventParam {
+                    name: "amount".to_string(),
+                    kind: ethabi::ParamType::Uint(256),
+                    indexed: false,
+                },
+            ],
+            anonymous: false,
+        }
+    }
+
     #[must_use]
     pub fn exit_to_eth_schema() -> ethabi::Event {
         ethabi::Event {
@@ -205,6 +377,7 @@ pub mod events {
 pub struct ExitToNear<I> {
     current_account_id: AccountId,
     io: I,
+    withdrawal_limiter: WithdrawalLimiter,
 }
 
 pub mod exit_to_near {
@@ -218,14 +391,205 @@ pub mod exit_to_near {
 }
 
 impl<I> ExitToNear<I> {
-    pub const fn new(current_account_id: AccountId, io: I) -> Self {
+    pub const fn new(
+        current_account_id: AccountId,
+        io: I,
+        withdrawal_limiter: WithdrawalLimiter,
+    ) -> Self {
         Self {
             current_account_id,
             io,
+            withdrawal_limiter,
         }
     }
 }
 
+/// Token withdrawn by `exitToNear`: the withdrawal limits and the refund on error
+/// apply to it. The amount is the token id in the case of NFT exit.
+struct ExitedToken {
+    /// `events::ETH_ADDRESS` in the case of ETH exit.
+    address: Address,
+    amount: U256,
+}
+
+/// Maximum number of tokens that can be withdrawn with a single `mt_batch_transfer`.
+const MAX_MT_BATCH_SIZE: usize = 32;
+
+impl<I: IO> ExitToNear<I> {
+    /// Multi-token transfer, expected to be called from the ERC-1155 `withdrawToNear`
+    /// function after the tokens were burned.
+    ///
+    /// Input slice format (without the flag):
+    ///      `refund_address` (20 bytes) - present even without the `error_refund` feature
+    ///      abi-encoded `(bytes recipient_account_id, uint256[] token_ids, uint256[] amounts)`
+    #[allow(clippy::too_many_lines)]
+    fn exit_multi_token(&self, input: &[u8], context: &Context) -> EvmPrecompileResult {
+        if context.apparent_value != U256::from(0) {
+            return Err(ExitError::Other(Cow::from("ERR_ETH_ATTACHED_FOR_MT_EXIT")));
+        }
+        if input.len() < 20 {
+            return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
+        }
+        let (refund_address, input) = input.split_at(20);
+
+        let erc1155_address = Address::new(context.caller);
+        let nep245_address = get_nep245_from_erc1155(erc1155_address.as_bytes(), &self.io)?;
+
+        let mut tokens = ethabi::decode(
+            &[
+                ethabi::ParamType::Bytes,
+                ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256))),
+                ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256))),
+            ],
+            input,
+        )
+        .map_err(|_| ExitError::Other(Cow::from("ERR_INVALID_INPUT")))?
+        .into_iter();
+        let (
+            Some(ethabi::Token::Bytes(recipient)),
+            Some(ethabi::Token::Array(token_ids)),
+            Some(ethabi::Token::Array(amounts)),
+        ) = (tokens.next(), tokens.next(), tokens.next())
+        else {
+            return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
+        };
+        if token_ids.is_empty()
+            || token_ids.len() != amounts.len()
+            || token_ids.len() > MAX_MT_BATCH_SIZE
+        {
+            return Err(ExitError::Other(Cow::from("ERR_INVALID_MT_BATCH")));
+        }
+        let token_ids = token_ids
+            .into_iter()
+            .filter_map(ethabi::Token::into_uint)
+            .map(|id| u256_to_arr(&id))
+            .collect::<Vec<_>>();
+        let amounts = amounts
+            .into_iter()
+            .filter_map(ethabi::Token::into_uint)
+            .collect::<Vec<_>>();
+        for amount in &amounts {
+            validate_amount(*amount)?;
+        }
+
+        let recipient = parse_recipient(&recipient)?;
+        let near_token_ids = token_ids
+            .iter()
+            .map(|token_id| {
+                mt::get_near_token_id(&self.io, &erc1155_address, token_id)
+                    .ok_or(ExitError::Other(Cow::from("ERR_MT_TOKEN_NOT_FOUND")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The refund of a rejected withdrawal can't mint back multi-tokens,
+        // so the multi-token exits are never queued.
+        let withdrawal = self.withdrawal_limiter.check(
+            &self.io,
+            WithdrawalLimitTarget::Erc1155(erc1155_address),
+            amounts.iter().fold(0u128, |total, amount| {
+                total.saturating_add(amount.as_u128())
+            }),
+            false,
+        )?;
+
+        // The NEAR token ids are arbitrary strings, so the arguments are serialized
+        // instead of formatted to prevent json injection.
+        let batch_len = near_token_ids.len() as u64;
+        let (method, args, attached_gas) = if batch_len == 1 {
+            let args = NEP245MtTransferArgs {
+                receiver_id: recipient.receiver_account_id.clone(),
+                token_id: near_token_ids[0].clone(),
+                amount: Balance::new(amounts[0].as_u128()),
+                approval: None,
+                memo: None,
+            };
+            (
+                "mt_transfer",
+                serde_json::to_vec(&args),
+                costs::MT_TRANSFER_GAS,
+            )
+        } else {
+            let args = NEP245MtBatchTransferArgs {
+                receiver_id: recipient.receiver_account_id.clone(),
+                token_ids: near_token_ids,
+                amounts: amounts
+                    .iter()
+                    .map(|amount| Balance::new(amount.as_u128()))
+                    .collect(),
+                approvals: None,
+                memo: None,
+            };
+            let per_token_gas = costs::MT_BATCH_TRANSFER_GAS_PER_TOKEN.as_u64() * batch_len;
+            (
+                "mt_batch_transfer",
+                serde_json::to_vec(&args),
+                costs::MT_TRANSFER_GAS + NearGas::new(per_token_gas),
+            )
+        };
+        let transfer_promise = PromiseCreateArgs {
+            target_account_id: nep245_address,
+            method: method.to_string(),
+            args: args.map_err(|_| ExitError::Other(Cow::from("ERR_SERIALIZE")))?,
+            attached_balance: Yocto::new(1),
+            attached_gas,
+        };
+
+        #[cfg(feature = "error_refund")]
+        let promise = {
+            let callback_args = MtExitCallbackArgs {
+                refund_address: Address::try_from_slice(refund_address)
+                    .map_err(|_| ExitError::Other(Cow::from("ERR_INVALID_INPUT")))?,
+                erc1155: erc1155_address,
+                token_ids: token_ids.clone(),
+                amounts: amounts.iter().map(u256_to_arr).collect(),
+            };
+            PromiseArgs::Callback(PromiseWithCallbackArgs {
+                base: transfer_promise,
+                callback: PromiseCreateArgs {
+                    target_account_id: self.current_account_id.clone(),
+                    method: "mt_exit_callback".to_string(),
+                    args: borsh::to_vec(&callback_args).unwrap(),
+                    attached_balance: Yocto::new(0),
+                    attached_gas: costs::EXIT_TO_NEAR_CALLBACK_GAS,
+                },
+            })
+        };
+        #[cfg(not(feature = "error_refund"))]
+        let promise = {
+            let _ = refund_address;
+            PromiseArgs::Create(transfer_promise)
+        };
+
+        let mut logs = withdrawal_limits::exit_logs(
+            exit_to_near::ADDRESS.raw(),
+            Some(withdrawal),
+            promise,
+            None,
+        );
+        for (token_id, amount) in token_ids.iter().zip(amounts) {
+            let exit_event = events::ExitMultiTokenToNear {
+                sender: erc1155_address,
+                erc1155_address,
+                dest: recipient.receiver_account_id.to_string(),
+                token_id: U256::from_big_endian(token_id),
+                amount,
+            }
+            .encode();
+            logs.push(Log {
+                address: exit_to_near::ADDRESS.raw(),
+                topics: exit_event.topics,
+                data: exit_event.data,
+            });
+        }
+
+        Ok(PrecompileOutput {
+            logs,
+            cost: costs::EXIT_TO_NEAR_GAS,
+            output: Vec::new(),
+        })
+    }
+}
+
 fn validate_input_size(input: &[u8], min: usize, max: usize) -> Result<(), ExitError> {
     if input.len() < min || input.len() > max {
         return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
@@ -233,6 +597,103 @@ fn validate_input_size(input: &[u8], min: usize, max: usize) -> Result<(), ExitE
     Ok(())
 }
 
+pub mod nft {
+    //! Functions for reading and writing state related to the NEP-171 NFT bridge
+
+    use aurora_engine_sdk::io::{StorageIntermediate, IO};
+    use aurora_engine_types::storage::{self, KeyPrefix};
+    use aurora_engine_types::types::Address;
+    use aurora_engine_types::{String, Vec};
+
+    /// Key of the bytecode of the ERC-721 contract deployed for every NEP-171 contract.
+    pub const TEMPLATE_KEY: &[u8] = b"erc721_template";
+
+    /// Storage key of the NEAR id of the token with the given ERC-721 id.
+    #[must_use]
+    pub fn token_id_key(erc721: &Address, token_id: &[u8; 32]) -> Vec<u8> {
+        storage::bytes_to_key(KeyPrefix::Nft, &[erc721.as_bytes(), token_id].concat())
+    }
+
+    /// Get the NEAR id of the token with the given ERC-721 id (if the token was ever bridged).
+    pub fn get_near_token_id<I: IO>(
+        io: &I,
+        erc721: &Address,
+        token_id: &[u8; 32],
+    ) -> Option<String> {
+        io.read_storage(&token_id_key(erc721, token_id))
+            .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+    }
+
+    /// Set the NEAR id of the token with the given ERC-721 id.
+    pub fn set_near_token_id<I: IO>(
+        io: &mut I,
+        erc721: &Address,
+        token_id: &[u8; 32],
+        near_token_id: &str,
+    ) {
+        io.write_storage(&token_id_key(erc721, token_id), near_token_id.as_bytes());
+    }
+}
+
+pub mod mt {
+    //! Functions for reading and writing state related to the NEP-245 multi-token bridge
+
+    use aurora_engine_sdk::io::{StorageIntermediate, IO};
+    use aurora_engine_types::storage::{self, KeyPrefix};
+    use aurora_engine_types::types::Address;
+    use aurora_engine_types::{String, Vec};
+
+    /// Key of the bytecode of the ERC-1155 contract deployed for every NEP-245 contract.
+    pub const TEMPLATE_KEY: &[u8] = b"erc1155_template";
+
+    /// Storage key of the NEAR id of the token with the given ERC-1155 id.
+    #[must_use]
+    pub fn token_id_key(erc1155: &Address, token_id: &[u8; 32]) -> Vec<u8> {
+        storage::bytes_to_key(
+            KeyPrefix::MultiToken,
+            &[erc1155.as_bytes(), token_id].concat(),
+        )
+    }
+
+    /// Get the NEAR id of the token with the given ERC-1155 id (if the token was ever bridged).
+    pub fn get_near_token_id<I: IO>(
+        io: &I,
+        erc1155: &Address,
+        token_id: &[u8; 32],
+    ) -> Option<String> {
+        io.read_storage(&token_id_key(erc1155, token_id))
+            .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+    }
+
+    /// Set the NEAR id of the token with the given ERC-1155 id.
+    pub fn set_near_token_id<I: IO>(
+        io: &mut I,
+        erc1155: &Address,
+        token_id: &[u8; 32],
+        near_token_id: &str,
+    ) {
+        io.write_storage(&token_id_key(erc1155, token_id), near_token_id.as_bytes());
+    }
+}
+
+fn get_nep245_from_erc1155<I: IO>(erc1155_token: &[u8], io: &I) -> Result<AccountId, ExitError> {
+    AccountId::try_from(
+        io.read_storage(bytes_to_key(KeyPrefix::Erc1155Nep245Map, erc1155_token).as_slice())
+            .map(|s| s.to_vec())
+            .ok_or(ExitError::Other(Cow::Borrowed(ERR_TARGET_TOKEN_NOT_FOUND)))?,
+    )
+    .map_err(|_| ExitError::Other(Cow::Borrowed("ERR_INVALID_NEP245_ACCOUNT")))
+}
+
+fn get_nep171_from_erc721<I: IO>(erc721_token: &[u8], io: &I) -> Result<AccountId, ExitError> {
+    AccountId::try_from(
+        io.read_storage(bytes_to_key(KeyPrefix::Erc721Nep171Map, erc721_token).as_slice())
+            .map(|s| s.to_vec())
+            .ok_or(ExitError::Other(Cow::Borrowed(ERR_TARGET_TOKEN_NOT_FOUND)))?,
+    )
+    .map_err(|_| ExitError::Other(Cow::Borrowed("ERR_INVALID_NEP171_ACCOUNT")))
+}
+
 fn get_nep141_from_erc20<I: IO>(erc20_token: &[u8], io: &I) -> Result<AccountId, ExitError> {
     AccountId::try_from(
         io.read_storage(bytes_to_key(KeyPrefix::Erc20Nep141Map, erc20_token).as_slice())
@@ -298,6 +759,45 @@ fn parse_recipient(recipient: &[u8]) -> Result<Recipient<'_>, ExitError> {
     })
 }
 
+/// Arguments and method of the NEP-141 transfer of `amount` to the recipient: the exit
+/// is turned into a `ft_transfer_call` if the message starts with `ft_transfer_call:`.
+/// The receiver may keep only a part of the tokens, so the `ft_transfer_call` requires
+/// the `error_refund` feature to refund the rest.
+fn ft_transfer_args(
+    recipient: &Recipient<'_>,
+    amount: u128,
+) -> Result<(String, &'static str), ExitError> {
+    match recipient
+        .message
+        .and_then(|msg| msg.strip_prefix(FT_TRANSFER_CALL_MSG_PREFIX))
+    {
+        Some(_) if cfg!(not(feature = "error_refund")) => Err(ExitError::Other(Cow::from(
+            "ERR_FT_TRANSFER_CALL_WITHOUT_REFUND",
+        ))),
+        Some(msg) => {
+            // The message is arbitrary, so the arguments are serialized instead of formatted
+            // to prevent json injection.
+            let args = serde_json::to_string(&NEP141FtTransferCallArgs {
+                receiver_id: recipient.receiver_account_id.clone(),
+                amount: Balance::new(amount),
+                memo: None,
+                msg: msg.to_string(),
+            })
+            .map_err(|_| ExitError::Other(Cow::from("ERR_SERIALIZE")))?;
+            Ok((args, "ft_transfer_call"))
+        }
+        // There is no way to inject json, given the encoding of both arguments
+        // as decimal and valid account id respectively.
+        _ => Ok((
+            format!(
+                r#"{{"receiver_id": "{}", "amount": "{}", "memo": null}}"#,
+                recipient.receiver_account_id, amount
+            ),
+            "ft_transfer",
+        )),
+    }
+}
+
 impl<I: IO> Precompile for ExitToNear<I> {
     fn required_gas(_input: &[u8]) -> Result<EthGas, ExitError> {
         Ok(costs::EXIT_TO_NEAR_GAS)
@@ -311,18 +811,29 @@ impl<I: IO> Precompile for ExitToNear<I> {
         context: &Context,
         is_static: bool,
     ) -> EvmPrecompileResult {
-        // ETH transfer input format: (85 bytes)
+        // ETH transfer input format: (max 85 bytes without message)
         //  - flag (1 byte)
         //  - refund_address (20 bytes)
-        //  - recipient_account_id (max 64 bytes)
-        // ERC20 transfer input format: (117 bytes)
+        //  - recipient_account_id (max 64 bytes), optionally followed by `:message`
+        // ERC20 transfer input format: (max 117 bytes without message)
         //  - flag (1 byte)
         //  - refund_address (20 bytes)
         //  - amount (32 bytes)
+        //  - recipient_account_id (max 64 bytes), optionally followed by `:message`
+        // A `ft_transfer_call:msg` message (with `msg` of max 1024 bytes) turns the transfer
+        // into a `ft_transfer_call`.
+        // NFT transfer input format: (117 bytes)
+        //  - flag (1 byte)
+        //  - refund_address (20 bytes), present even without the `error_refund` feature
+        //  - token_id (32 bytes)
         //  - recipient_account_id (max 64 bytes)
+        // Multi-token transfer input format: (unbounded, see `exit_multi_token`)
+        //  - flag (1 byte)
+        //  - refund_address (20 bytes), present even without the `error_refund` feature
+        //  - abi-encoded `(bytes recipient_account_id, uint256[] token_ids, uint256[] amounts)`
         #[cfg(feature = "error_refund")]
         fn parse_input(input: &[u8]) -> Result<(Address, &[u8]), ExitError> {
-            validate_input_size(input, 21, 117)?;
+            validate_input_size(input, 21, MAX_EXIT_TO_NEAR_INPUT_LEN)?;
             let mut buffer = [0; 20];
             buffer.copy_from_slice(&input[1..21]);
             let refund_address = Address::from_array(buffer);
@@ -330,7 +841,7 @@ impl<I: IO> Precompile for ExitToNear<I> {
         }
         #[cfg(not(feature = "error_refund"))]
         fn parse_input(input: &[u8]) -> Result<&[u8], ExitError> {
-            validate_input_size(input, 3, 117)?;
+            validate_input_size(input, 3, MAX_EXIT_TO_NEAR_INPUT_LEN)?;
             Ok(&input[1..])
         }
 
@@ -350,7 +861,12 @@ impl<I: IO> Precompile for ExitToNear<I> {
         // First byte of the input is a flag, selecting the behavior to be triggered:
         //      0x0 -> Eth transfer
         //      0x1 -> Erc20 transfer
+        //      0x2 -> Erc721 (NFT) transfer
+        //      0x3 -> Erc1155 (multi-token) transfer
         let flag = input.first().copied().unwrap_or_default();
+        if flag == 0x3 {
+            return self.exit_multi_token(&input[1..], context);
+        }
         #[cfg(feature = "error_refund")]
         let (refund_address, mut input) = parse_input(input)?;
         #[cfg(not(feature = "error_refund"))]
@@ -360,37 +876,34 @@ impl<I: IO> Precompile for ExitToNear<I> {
         #[cfg(feature = "ext-connector")]
         let eth_connector_account_id = get_eth_connector_contract_account(&self.io)?;
 
-        let (nep141_address, args, exit_event, method, transfer_near_args) = match flag {
+        let (nep141_address, args, exited, exit_event, method, transfer_near_args) = match flag {
             0x0 => {
                 // ETH transfer
                 //
                 // Input slice format:
-                // recipient_account_id (bytes) - the NEAR recipient account which will receive NEP-141 ETH tokens
+                // recipient_account_id (bytes) - the NEAR recipient account which will receive NEP-141 ETH tokens,
+                //      optionally followed by `:ft_transfer_call:msg` to call `ft_transfer_call` instead of `ft_transfer`
 
-                if let Ok(dest_account) = AccountId::try_from(input) {
-                    (
-                        eth_connector_account_id,
-                        // There is no way to inject json, given the encoding of both arguments
-                        // as decimal and valid account id respectively.
-                        format!(
-                            r#"{{"receiver_id": "{}", "amount": "{}", "memo": null}}"#,
-                            dest_account,
-                            context.apparent_value.as_u128()
-                        ),
-                        events::ExitToNear {
-                            sender: Address::new(context.caller),
-                            erc20_address: events::ETH_ADDRESS,
-                            dest: dest_account.to_string(),
-                            amount: context.apparent_value,
-                        },
-                        "ft_transfer",
-                        None,
-                    )
-                } else {
-                    return Err(ExitError::Other(Cow::from(
-                        "ERR_INVALID_RECEIVER_ACCOUNT_ID",
-                    )));
-                }
+                let recipient = parse_recipient(input)?;
+                let (args, method) =
+                    ft_transfer_args(&recipient, context.apparent_value.as_u128())?;
+                (
+                    eth_connector_account_id,
+                    args,
+                    ExitedToken {
+                        address: events::ETH_ADDRESS,
+                        amount: context.apparent_value,
+                    },
+                    events::ExitToNear {
+                        sender: Address::new(context.caller),
+                        erc20_address: events::ETH_ADDRESS,
+                        dest: recipient.receiver_account_id.to_string(),
+                        amount: context.apparent_value,
+                    }
+                    .encode(),
+                    method,
+                    None,
+                )
             }
             0x1 => {
                 // ERC-20 transfer
@@ -399,7 +912,8 @@ impl<I: IO> Precompile for ExitToNear<I> {
                 //
                 // Input slice format:
                 //      amount (U256 big-endian bytes) - the amount that was burned
-                //      recipient_account_id (bytes) - the NEAR recipient account which will receive NEP-141 tokens
+                //      recipient_account_id (bytes) - the NEAR recipient account which will receive NEP-141 tokens,
+                //          optionally followed by `:ft_transfer_call:msg` to call `ft_transfer_call` instead of `ft_transfer`
 
                 if context.apparent_value != U256::from(0) {
                     return Err(ExitError::Other(Cow::from(
@@ -429,48 +943,130 @@ impl<I: IO> Precompile for ExitToNear<I> {
                         }),
                     )
                 } else {
-                    // There is no way to inject json, given the encoding of both arguments
-                    // as decimal and valid account id respectively.
-                    (
-                        format!(
-                            r#"{{"receiver_id": "{}", "amount": "{}", "memo": null}}"#,
-                            recipient.receiver_account_id,
-                            amount.as_u128()
-                        ),
-                        "ft_transfer",
-                        None,
-                    )
+                    let (args, method) = ft_transfer_args(&recipient, amount.as_u128())?;
+                    (args, method, None)
                 };
 
                 (
                     nep141_address,
                     args,
+                    ExitedToken {
+                        address: Address::new(erc20_address),
+                        amount,
+                    },
                     events::ExitToNear {
                         sender: Address::new(erc20_address),
                         erc20_address: Address::new(erc20_address),
                         dest: recipient.receiver_account_id.to_string(),
                         amount,
-                    },
+                    }
+                    .encode(),
                     method,
                     transfer_near_args,
                 )
             }
+            0x2 => {
+                // NFT transfer
+                //
+                // This precompile branch is expected to be called from the ERC-721 `withdrawToNear`
+                // function, after the token was burned.
+                //
+                // Input slice format:
+                //      token_id (U256 big-endian bytes) - the id of the burned ERC-721 token
+                //      recipient_account_id (bytes) - the NEAR recipient account which will receive the NEP-171 token
+
+                if context.apparent_value != U256::from(0) {
+                    return Err(ExitError::Other(Cow::from("ERR_ETH_ATTACHED_FOR_NFT_EXIT")));
+                }
+
+                // The ERC-721 template always passes the refund address, so the same template
+                // works with and without the `error_refund` feature.
+                #[cfg(not(feature = "error_refund"))]
+                {
+                    input = input
+                        .get(20..)
+                        .ok_or(ExitError::Other(Cow::from("ERR_INVALID_INPUT")))?;
+                }
+                if input.len() < 32 {
+                    return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
+                }
+
+                let erc721_address = Address::new(context.caller);
+                let nep171_address = get_nep171_from_erc721(erc721_address.as_bytes(), &self.io)?;
+
+                let mut token_id = [0u8; 32];
+                token_id.copy_from_slice(&input[..32]);
+                input = &input[32..];
+
+                let near_token_id = nft::get_near_token_id(&self.io, &erc721_address, &token_id)
+                    .ok_or(ExitError::Other(Cow::from("ERR_NFT_TOKEN_NOT_FOUND")))?;
+                let recipient = parse_recipient(input)?;
+                // The NEAR token id is an arbitrary string, so the arguments are serialized
+                // instead of formatted to prevent json injection.
+                let args = serde_json::to_string(&NEP171NftTransferArgs {
+                    receiver_id: recipient.receiver_account_id.clone(),
+                    token_id: near_token_id,
+                    approval_id: None,
+                    memo: None,
+                })
+                .map_err(|_| ExitError::Other(Cow::from("ERR_SERIALIZE")))?;
+
+                let token_id = U256::from_big_endian(&token_id);
+                (
+                    nep171_address,
+                    args,
+                    ExitedToken {
+                        address: erc721_address,
+                        amount: token_id,
+                    },
+                    events::ExitNftToNear {
+                        sender: erc721_address,
+                        erc721_address,
+                        dest: recipient.receiver_account_id.to_string(),
+                        token_id,
+                    }
+                    .encode(),
+                    "nft_transfer",
+                    None,
+                )
+            }
             _ => return Err(ExitError::Other(Cow::from("ERR_INVALID_FLAG"))),
         };
 
+        // The NFT exits aren't covered by the withdrawal limits.
+        let withdrawal = match flag {
+            0x0 => Some(WithdrawalLimitTarget::Eth),
+            0x1 => Some(WithdrawalLimitTarget::Erc20(exited.address)),
+            _ => None,
+        }
+        .map(|target| {
+            self.withdrawal_limiter.check(
+                &self.io,
+                target,
+                u128::try_from(exited.amount).unwrap_or(u128::MAX),
+                cfg!(feature = "error_refund"),
+            )
+        })
+        .transpose()?;
+
         #[cfg(feature = "error_refund")]
         let erc20_address = if flag == 0 {
             None
         } else {
-            Some(exit_event.erc20_address)
+            Some(exited.address)
         };
         #[cfg(feature = "error_refund")]
         let refund_args = RefundCallArgs {
             recipient_address: refund_address,
             erc20_address,
-            amount: types::u256_to_arr(&exit_event.amount),
+            amount: types::u256_to_arr(&exited.amount),
         };
 
+        #[cfg(feature = "error_refund")]
+        let queued_refund = Some(refund_args.clone());
+        #[cfg(not(feature = "error_refund"))]
+        let queued_refund = None;
+
         let callback_args = ExitToNearPrecompileCallbackCallArgs {
             #[cfg(feature = "error_refund")]
             refund: Some(refund_args),
@@ -484,7 +1080,13 @@ impl<I: IO> Precompile for ExitToNear<I> {
             method: method.to_string(),
             args: args.as_bytes().to_vec(),
             attached_balance: Yocto::new(1),
-            attached_gas: costs::FT_TRANSFER_GAS,
+            attached_gas: if flag == 0x2 {
+                costs::NFT_TRANSFER_GAS
+            } else if method == "ft_transfer_call" {
+                costs::FT_TRANSFER_CALL_GAS
+            } else {
+                costs::FT_TRANSFER_GAS
+            },
         };
 
         let promise = if callback_args == ExitToNearPrecompileCallbackCallArgs::default() {
@@ -501,20 +1103,20 @@ impl<I: IO> Precompile for ExitToNear<I> {
                 },
             })
         };
-        let promise_log = Log {
-            address: exit_to_near::ADDRESS.raw(),
-            topics: Vec::new(),
-            data: borsh::to_vec(&promise).unwrap(),
-        };
-        let exit_event_log = exit_event.encode();
-        let exit_event_log = Log {
+        let mut logs = withdrawal_limits::exit_logs(
+            exit_to_near::ADDRESS.raw(),
+            withdrawal,
+            promise,
+            queued_refund,
+        );
+        logs.push(Log {
             address: exit_to_near::ADDRESS.raw(),
-            topics: exit_event_log.topics,
-            data: exit_event_log.data,
-        };
+            topics: exit_event.topics,
+            data: exit_event.data,
+        });
 
         Ok(PrecompileOutput {
-            logs: vec![promise_log, exit_event_log],
+            logs,
             cost: Self::required_gas(input)?,
             output: Vec::new(),
         })
@@ -525,6 +1127,7 @@ pub struct ExitToEthereum<I> {
     io: I,
     #[cfg(not(feature = "ext-connector"))]
     current_account_id: AccountId,
+    withdrawal_limiter: WithdrawalLimiter,
 }
 
 pub mod exit_to_ethereum {
@@ -539,16 +1142,24 @@ pub mod exit_to_ethereum {
 
 impl<I> ExitToEthereum<I> {
     #[cfg(not(feature = "ext-connector"))]
-    pub const fn new(current_account_id: AccountId, io: I) -> Self {
+    pub const fn new(
+        current_account_id: AccountId,
+        io: I,
+        withdrawal_limiter: WithdrawalLimiter,
+    ) -> Self {
         Self {
             io,
             current_account_id,
+            withdrawal_limiter,
         }
     }
 
     #[cfg(feature = "ext-connector")]
-    pub const fn new(io: I) -> Self {
-        Self { io }
+    pub const fn new(io: I, withdrawal_limiter: WithdrawalLimiter) -> Self {
+        Self {
+            io,
+            withdrawal_limiter,
+        }
     }
 }
 
@@ -691,21 +1302,35 @@ impl<I: IO> Precompile for ExitToEthereum<I> {
             attached_gas: costs::WITHDRAWAL_GAS,
         };
 
-        let promise = borsh::to_vec(&PromiseArgs::Create(withdraw_promise)).unwrap();
-        let promise_log = Log {
-            address: exit_to_ethereum::ADDRESS.raw(),
-            topics: Vec::new(),
-            data: promise,
+        let target = if flag == 0x0 {
+            WithdrawalLimitTarget::Eth
+        } else {
+            WithdrawalLimitTarget::Erc20(exit_event.erc20_address)
         };
+        // The sender of the burned ERC-20 tokens isn't known here, so the exits to Ethereum
+        // can't be refunded and are never queued.
+        let withdrawal = self.withdrawal_limiter.check(
+            &self.io,
+            target,
+            u128::try_from(exit_event.amount).unwrap_or(u128::MAX),
+            false,
+        )?;
+
+        let mut logs = withdrawal_limits::exit_logs(
+            exit_to_ethereum::ADDRESS.raw(),
+            Some(withdrawal),
+            PromiseArgs::Create(withdraw_promise),
+            None,
+        );
         let exit_event_log = exit_event.encode();
-        let exit_event_log = Log {
+        logs.push(Log {
             address: exit_to_ethereum::ADDRESS.raw(),
             topics: exit_event_log.topics,
             data: exit_event_log.data,
-        };
+        });
 
         Ok(PrecompileOutput {
-            logs: vec![promise_log, exit_event_log],
+            logs,
             cost: Self::required_gas(input)?,
             output: Vec::new(),
         })
@@ -733,7 +1358,8 @@ fn borsh_args(address: Address, amount: U256) -> Result<Vec<u8>, ExitError> {
 #[cfg(test)]
 mod tests {
     use super::{
-        exit_to_ethereum, exit_to_near, parse_recipient, validate_amount, validate_input_size,
+        exit_to_ethereum, exit_to_near, ft_transfer_args, parse_recipient, validate_amount,
+        validate_input_size,
     };
     use crate::{native::Recipient, prelude::sdk::types::near_account_to_evm_address};
     use aurora_engine_types::U256;
@@ -753,12 +1379,22 @@ mod tests {
     #[test]
     fn test_exit_signatures() {
         let exit_to_near = super::events::exit_to_near_schema();
+        let exit_nft_to_near = super::events::exit_nft_to_near_schema();
+        let exit_multi_token_to_near = super::events::exit_multi_token_to_near_schema();
         let exit_to_eth = super::events::exit_to_eth_schema();
 
         assert_eq!(
             exit_to_near.signature(),
             super::events::EXIT_TO_NEAR_SIGNATURE
         );
+        assert_eq!(
+            exit_nft_to_near.signature(),
+            super::events::EXIT_NFT_TO_NEAR_SIGNATURE
+        );
+        assert_eq!(
+            exit_multi_token_to_near.signature(),
+            super::events::EXIT_MULTI_TOKEN_TO_NEAR_SIGNATURE
+        );
         assert_eq!(
             exit_to_eth.signature(),
             super::events::EXIT_TO_ETH_SIGNATURE
@@ -843,4 +1479,36 @@ mod tests {
         assert!(parse_recipient(b"test@.near:msg").is_err());
         assert!(parse_recipient(&[0xc2]).is_err());
     }
+
+    #[test]
+    fn test_ft_transfer_args() {
+        let (args, method) = ft_transfer_args(&parse_recipient(b"test.near").unwrap(), 10).unwrap();
+        assert_eq!(method, "ft_transfer");
+        assert_eq!(
+            args,
+            r#"{"receiver_id": "test.near", "amount": "10", "memo": null}"#
+        );
+
+        // Only the messages with the `ft_transfer_call:` prefix turn the exit into
+        // a `ft_transfer_call`.
+        let (_, method) = ft_transfer_args(&parse_recipient(b"test.near:").unwrap(), 10).unwrap();
+        assert_eq!(method, "ft_transfer");
+        let (_, method) =
+            ft_transfer_args(&parse_recipient(br#"pool.near:{"pool_id":1}"#).unwrap(), 10).unwrap();
+        assert_eq!(method, "ft_transfer");
+
+        let recipient = parse_recipient(br#"pool.near:ft_transfer_call:{"pool_id":1}"#).unwrap();
+        let result = ft_transfer_args(&recipient, 10);
+        if cfg!(feature = "error_refund") {
+            let (args, method) = result.unwrap();
+            assert_eq!(method, "ft_transfer_call");
+            assert_eq!(
+                args,
+                r#"{"receiver_id":"pool.near","amount":"10","memo":null,"msg":"{\"pool_id\":1}"}"#
+            );
+        } else {
+            // The tokens not used by the receiver couldn't be refunded.
+            assert!(result.is_err());
+        }
+    }
 }
@@ -0,0 +1,194 @@
+//! Rolling-window limits of the withdrawals made through the exit precompiles.
+//!
+//! The precompiles check the limits and pass the accepted withdrawals to the engine in logs,
+//! the engine updates the usage once the transaction succeeded (so reverted exits can't
+//! use up the limits) and stores the queued withdrawals.
+
+extern crate alloc;
+
+use crate::prelude::{
+    parameters::{
+        withdrawal_limits::{
+            ExceedAction, QueuedWithdrawal, WithdrawalLimit, WithdrawalLimitTarget,
+            WithdrawalRecord, WithdrawalUsage,
+        },
+        PromiseArgs, RefundCallArgs,
+    },
+    sdk::io::{StorageIntermediate, IO},
+    storage::{bytes_to_key, KeyPrefix},
+    vec, Cow, Vec, H160, H256,
+};
+use alloc::rc::Rc;
+use aurora_engine_types::borsh;
+use core::cell::RefCell;
+use evm::backend::Log;
+use evm::ExitError;
+
+/// Sentinel value used to indicate the log data contains a `WithdrawalRecord`.
+pub const WITHDRAWAL_TOPIC: H256 =
+    crate::make_h256(0x007769746864726177616c, 0x007769746864726177616c);
+
+/// Sentinel value used to indicate the log data contains a `QueuedWithdrawal`.
+pub const QUEUED_WITHDRAWAL_TOPIC: H256 =
+    crate::make_h256(0x0071756575656465786974, 0x0071756575656465786974);
+
+const LIMIT_KEY: &[u8] = b"limit";
+const USAGE_KEY: &[u8] = b"usage";
+
+/// Get the limit of the target (if any).
+pub fn get_limit<I: IO>(io: &I, target: &WithdrawalLimitTarget) -> Option<WithdrawalLimit> {
+    io.read_storage(&limit_key(target))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+/// Set or remove the limit of the target.
+pub fn set_limit<I: IO>(
+    io: &mut I,
+    target: &WithdrawalLimitTarget,
+    limit: Option<&WithdrawalLimit>,
+) {
+    let key = limit_key(target);
+    match limit {
+        Some(limit) => {
+            io.write_borsh(&key, limit);
+        }
+        None => {
+            io.remove_storage(&key);
+            io.remove_storage(&usage_key(target));
+        }
+    }
+}
+
+/// Get the amount withdrawn by the successful transactions.
+pub fn get_usage<I: IO>(io: &I, target: &WithdrawalLimitTarget) -> WithdrawalUsage {
+    io.read_storage(&usage_key(target))
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_default()
+}
+
+pub fn set_usage<I: IO>(io: &mut I, target: &WithdrawalLimitTarget, usage: &WithdrawalUsage) {
+    io.write_borsh(&usage_key(target), usage);
+}
+
+fn limit_key(target: &WithdrawalLimitTarget) -> Vec<u8> {
+    bytes_to_key(
+        KeyPrefix::WithdrawalLimits,
+        &[LIMIT_KEY, &target.to_key_bytes()].concat(),
+    )
+}
+
+fn usage_key(target: &WithdrawalLimitTarget) -> Vec<u8> {
+    bytes_to_key(
+        KeyPrefix::WithdrawalLimits,
+        &[USAGE_KEY, &target.to_key_bytes()].concat(),
+    )
+}
+
+/// Outcome of the limits check of a withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allow,
+    Queue,
+}
+
+/// Checks the withdrawals of a transaction against the limits. Both exit precompiles share
+/// the same limiter, so the withdrawals already made in the transaction are accounted for.
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawalLimiter {
+    /// Block timestamp in seconds.
+    timestamp: u64,
+    pending: Rc<RefCell<Vec<WithdrawalRecord>>>,
+}
+
+impl WithdrawalLimiter {
+    #[must_use]
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            pending: Rc::default(),
+        }
+    }
+
+    /// Checks the withdrawal of `amount` of ETH, an ERC-20 or an ERC-1155 token (`target`)
+    /// against the limits of the target and the global limit. The withdrawal fails if a limit
+    /// with the `Reject` action is exceeded, or a limit with the `Queue` action if the exit
+    /// isn't `refundable`: the tokens of a rejected withdrawal are minted back to the sender.
+    pub fn check<I: IO>(
+        &self,
+        io: &I,
+        target: WithdrawalLimitTarget,
+        amount: u128,
+        refundable: bool,
+    ) -> Result<(Decision, WithdrawalRecord), ExitError> {
+        let mut decision = Decision::Allow;
+        for limited in [target, WithdrawalLimitTarget::Global] {
+            let Some(limit) = get_limit(io, &limited) else {
+                continue;
+            };
+            let usage = self
+                .pending
+                .borrow()
+                .iter()
+                .filter(|record| {
+                    limited == WithdrawalLimitTarget::Global || record.target == limited
+                })
+                .fold(get_usage(io, &limited), |usage, record| {
+                    usage.record(limit.window_secs, record.timestamp, record.amount)
+                });
+            match limit.check(&usage, self.timestamp, amount) {
+                Some(ExceedAction::Queue) if refundable => decision = Decision::Queue,
+                Some(ExceedAction::Reject | ExceedAction::Queue) => {
+                    return Err(ExitError::Other(Cow::from("ERR_WITHDRAWAL_LIMIT_EXCEEDED")));
+                }
+                None => (),
+            }
+        }
+
+        let record = WithdrawalRecord {
+            target,
+            amount,
+            timestamp: self.timestamp,
+        };
+        self.pending.borrow_mut().push(record.clone());
+        Ok((decision, record))
+    }
+}
+
+/// Logs of an exit precompile: the withdrawal record (if the exit is covered by the limits)
+/// followed by the promise, or the queued withdrawal if the promise waits for an approval.
+#[must_use]
+pub fn exit_logs(
+    address: H160,
+    withdrawal: Option<(Decision, WithdrawalRecord)>,
+    promise: PromiseArgs,
+    refund: Option<RefundCallArgs>,
+) -> Vec<Log> {
+    let mut logs = Vec::with_capacity(2);
+    if let Some((_, record)) = &withdrawal {
+        logs.push(Log {
+            address,
+            topics: vec![WITHDRAWAL_TOPIC],
+            data: borsh::to_vec(record).unwrap(),
+        });
+    }
+    // `WithdrawalLimiter::check` only queues the exits which can be refunded.
+    if let (Some((Decision::Queue, record)), Some(refund)) = (withdrawal, refund) {
+        let queued = QueuedWithdrawal {
+            record,
+            promise,
+            refund,
+        };
+        logs.push(Log {
+            address,
+            topics: vec![QUEUED_WITHDRAWAL_TOPIC],
+            data: borsh::to_vec(&queued).unwrap(),
+        });
+    } else {
+        logs.push(Log {
+            address,
+            topics: Vec::new(),
+            data: borsh::to_vec(&promise).unwrap(),
+        });
+    }
+    logs
+}
@@ -8,14 +8,19 @@ use aurora_engine_types::{
     account_id::AccountId,
     borsh::{self, BorshDeserialize},
     format,
-    parameters::{CrossContractCallArgs, PromiseCreateArgs},
-    types::{balance::ZERO_YOCTO, Address, EthGas, NearGas},
+    parameters::{
+        xcc::{ScheduleWithDeadlineArgs, XccEvmCallbackArgs},
+        CrossContractCallArgs, PromiseArgs, PromiseCreateArgs,
+    },
+    types::{balance::ZERO_YOCTO, Address, EthGas, NearGas, Yocto},
     vec, Cow, Vec, H160, H256, U256,
 };
 use evm::backend::Log;
 use evm::executor::stack::{PrecompileFailure, PrecompileHandle};
 use evm::ExitError;
 
+pub mod abi;
+
 pub mod costs {
     use crate::prelude::types::{EthGas, NearGas};
 
@@ -44,6 +49,10 @@ pub mod costs {
     pub const ROUTER_EXEC_BASE: NearGas = NearGas::new(7_000_000_000_000);
     pub const ROUTER_EXEC_PER_CALLBACK: NearGas = NearGas::new(12_000_000_000_000);
     pub const ROUTER_SCHEDULE: NearGas = NearGas::new(5_000_000_000_000);
+    /// Covers wrapping the refunded NEAR and transferring it to the engine.
+    pub const ROUTER_CANCEL_SCHEDULED: NearGas = NearGas::new(60_000_000_000_000);
+    /// Covers unwrapping the wNEAR of the router and deleting its account.
+    pub const ROUTER_CLOSE: NearGas = NearGas::new(60_000_000_000_000);
 }
 
 mod consts {
@@ -52,8 +61,12 @@ mod consts {
     pub(super) const ERR_STATIC: &str = "ERR_INVALID_IN_STATIC";
     pub(super) const ERR_DELEGATE: &str = "ERR_INVALID_IN_DELEGATE";
     pub(super) const ERR_XCC_ACCOUNT_ID: &str = "ERR_FAILED_TO_CREATE_XCC_ACCOUNT_ID";
+    pub(super) const ERR_NO_ROUTER: &str = "ERR_XCC_ACCOUNT_NOT_FOUND";
     pub(super) const ROUTER_EXEC_NAME: &str = "execute";
     pub(super) const ROUTER_SCHEDULE_NAME: &str = "schedule";
+    pub(super) const ROUTER_SCHEDULE_WITH_DEADLINE_NAME: &str = "schedule_with_deadline";
+    pub(super) const ROUTER_CANCEL_SCHEDULED_NAME: &str = "cancel_scheduled";
+    pub(super) const ROUTER_CLOSE_NAME: &str = "close";
     /// Solidity selector for the ERC-20 transferFrom function
     /// `https://www.4byte.directory/signatures/?bytes4_signature=0x23b872dd`
     pub(super) const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
@@ -91,6 +104,11 @@ pub mod cross_contract_call {
         0x0072657175697265645f6e656172,
         0x0072657175697265645f6e656172,
     );
+
+    /// Sentinel value used to indicate the log data contains the EVM callback which should
+    /// be attached to the promise from the next log.
+    pub const EVM_CALLBACK_TOPIC: H256 =
+        crate::make_h256(0x0065766d5f63616c6c6261636b, 0x0065766d5f63616c6c6261636b);
 }
 
 impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
@@ -131,44 +149,75 @@ impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
 
         let sender = context.caller;
         let target_account_id = create_target_account_id(sender, self.engine_account_id.as_ref())?;
-        let args = CrossContractCallArgs::try_from_slice(input)
-            .map_err(|_| ExitError::Other(Cow::from(consts::ERR_INVALID_INPUT)))?;
-        let (promise, attached_near) = match args {
+        let args = abi::decode_args(input)
+            .ok_or_else(|| ExitError::Other(Cow::from(consts::ERR_INVALID_INPUT)))?;
+        let (promise, attached_near, callback) = match args {
             CrossContractCallArgs::Eager(call) => {
-                let call_gas = call.total_gas();
+                let (promise, attached_near) = eager_promise(target_account_id, &call)?;
+                (promise, attached_near, None)
+            }
+            CrossContractCallArgs::EagerWithCallback(call, callback) => {
+                let (promise, attached_near) = eager_promise(target_account_id, &call)?;
+                (promise, attached_near, Some(callback))
+            }
+            CrossContractCallArgs::Delayed(call) => {
                 let attached_near = call.total_near();
-                let callback_count = call
-                    .promise_count()
-                    .checked_sub(1)
-                    .ok_or_else(|| ExitError::Other(Cow::from(consts::ERR_INVALID_INPUT)))?;
-                let router_exec_cost = costs::ROUTER_EXEC_BASE
-                    + NearGas::new(callback_count * costs::ROUTER_EXEC_PER_CALLBACK.as_u64());
                 let promise = PromiseCreateArgs {
                     target_account_id,
-                    method: consts::ROUTER_EXEC_NAME.into(),
+                    method: consts::ROUTER_SCHEDULE_NAME.into(),
                     args: borsh::to_vec(&call)
                         .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
                     attached_balance: ZERO_YOCTO,
-                    attached_gas: router_exec_cost.saturating_add(call_gas),
+                    // We don't need to add any gas to the amount need for the schedule call
+                    // since the promise is not executed right away.
+                    attached_gas: costs::ROUTER_SCHEDULE,
                 };
-                (promise, attached_near)
+                (promise, attached_near, None)
             }
-            CrossContractCallArgs::Delayed(call) => {
+            CrossContractCallArgs::DelayedWithDeadline(call, deadline) => {
                 let attached_near = call.total_near();
+                let args = ScheduleWithDeadlineArgs {
+                    promise: call,
+                    deadline,
+                };
                 let promise = PromiseCreateArgs {
                     target_account_id,
-                    method: consts::ROUTER_SCHEDULE_NAME.into(),
-                    args: borsh::to_vec(&call)
+                    method: consts::ROUTER_SCHEDULE_WITH_DEADLINE_NAME.into(),
+                    args: borsh::to_vec(&args)
                         .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
                     attached_balance: ZERO_YOCTO,
-                    // We don't need to add any gas to the amount need for the schedule call
-                    // since the promise is not executed right away.
                     attached_gas: costs::ROUTER_SCHEDULE,
                 };
-                (promise, attached_near)
+                (promise, attached_near, None)
+            }
+            CrossContractCallArgs::CancelScheduled(nonce) => {
+                let promise = PromiseCreateArgs {
+                    target_account_id,
+                    method: consts::ROUTER_CANCEL_SCHEDULED_NAME.into(),
+                    args: format!(r#"{{"nonce":"{nonce}"}}"#).into_bytes(),
+                    attached_balance: ZERO_YOCTO,
+                    attached_gas: costs::ROUTER_CANCEL_SCHEDULED,
+                };
+                (promise, ZERO_YOCTO, None)
+            }
+            CrossContractCallArgs::CloseAccount(beneficiary_id) => {
+                if state::get_code_version_of_address(&self.io, &Address::new(sender)).is_none() {
+                    return Err(revert_with_message(consts::ERR_NO_ROUTER));
+                }
+                let promise = PromiseCreateArgs {
+                    target_account_id,
+                    method: consts::ROUTER_CLOSE_NAME.into(),
+                    args: format!(r#"{{"beneficiary_id":"{beneficiary_id}"}}"#).into_bytes(),
+                    attached_balance: ZERO_YOCTO,
+                    attached_gas: costs::ROUTER_CLOSE,
+                };
+                (promise, ZERO_YOCTO, None)
             }
         };
         cost += EthGas::new(promise.attached_gas.as_u64() / costs::CROSS_CONTRACT_CALL_NEAR_GAS);
+        if let Some(callback) = &callback {
+            cost += EthGas::new(callback.gas.as_u64() / costs::CROSS_CONTRACT_CALL_NEAR_GAS);
+        }
         check_cost(cost)?;
 
         let required_near =
@@ -220,15 +269,31 @@ impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
             ))),
         ];
 
-        let promise_log = Log {
+        let mut logs = Vec::with_capacity(2);
+
+        // The callback log precedes the log of the promise it is attached to.
+        if let Some(callback) = callback {
+            let args = XccEvmCallbackArgs {
+                sender: Address::new(sender),
+                callback,
+            };
+            logs.push(Log {
+                address: cross_contract_call::ADDRESS.raw(),
+                topics: vec![cross_contract_call::EVM_CALLBACK_TOPIC],
+                data: borsh::to_vec(&args)
+                    .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
+            });
+        }
+
+        logs.push(Log {
             address: cross_contract_call::ADDRESS.raw(),
             topics,
             data: borsh::to_vec(&promise)
                 .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
-        };
+        });
 
         Ok(PrecompileOutput {
-            logs: vec![promise_log],
+            logs,
             cost,
             ..Default::default()
         })
@@ -286,6 +351,31 @@ pub mod state {
     }
 }
 
+/// Creates the promise calling the router's `execute` method. The promise gets enough gas
+/// for the router to schedule the call and all its callbacks.
+fn eager_promise(
+    target_account_id: AccountId,
+    call: &PromiseArgs,
+) -> Result<(PromiseCreateArgs, Yocto), PrecompileFailure> {
+    let call_gas = call.total_gas();
+    let attached_near = call.total_near();
+    let callback_count = call
+        .promise_count()
+        .checked_sub(1)
+        .ok_or_else(|| ExitError::Other(Cow::from(consts::ERR_INVALID_INPUT)))?;
+    let router_exec_cost = costs::ROUTER_EXEC_BASE
+        + NearGas::new(callback_count * costs::ROUTER_EXEC_PER_CALLBACK.as_u64());
+    let promise = PromiseCreateArgs {
+        target_account_id,
+        method: consts::ROUTER_EXEC_NAME.into(),
+        args: borsh::to_vec(call)
+            .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
+        attached_balance: ZERO_YOCTO,
+        attached_gas: router_exec_cost.saturating_add(call_gas),
+    };
+    Ok((promise, attached_near))
+}
+
 fn transfer_from_args(from: H160, to: H160, amount: U256) -> Vec<u8> {
     let args = ethabi::encode(&[
         ethabi::Token::Address(from),
@@ -0,0 +1,639 @@
+//! ABI-encoded input of the cross contract call precompile.
+//!
+//! The input is dispatched on the first four bytes. If they match one of the selectors below,
+//! the rest of the input is decoded as the ABI-encoded arguments of the corresponding function
+//! of the `ICrossContractCall` interface (see `etc/eth-contracts/contracts/ICrossContractCall.sol`).
+//! Otherwise, the input is decoded as the borsh-encoded `CrossContractCallArgs`. The first
+//! byte of the selectors doesn't match any variant of `CrossContractCallArgs`, so both
+//! encodings are unambiguous.
+
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh::BorshDeserialize,
+    parameters::{
+        CrossContractCallArgs, NearPromise, PromiseAction, PromiseArgs, PromiseBatchAction,
+        PromiseCreateArgs, SimpleNearPromise,
+    },
+    public_key::PublicKey,
+    types::{NearGas, Yocto},
+    vec, Box, Vec, U256,
+};
+use ethabi::{ParamType, Token};
+
+/// Selector of `functionCall((string,string,bytes,uint128,uint64),bool)`.
+pub const FUNCTION_CALL_SELECTOR: [u8; 4] = [0x32, 0x16, 0x51, 0xc2];
+/// Selector of `batch((string,(uint8,bytes)[]),bool)`.
+pub const BATCH_SELECTOR: [u8; 4] = [0x0e, 0xf3, 0x64, 0x1b];
+/// Selector of `promiseTree((uint8,bytes)[],bool)`.
+pub const PROMISE_TREE_SELECTOR: [u8; 4] = [0xa0, 0xd5, 0xf0, 0xf7];
+/// Selector of `scheduleWithDeadline((uint8,bytes)[],uint64)`.
+pub const SCHEDULE_WITH_DEADLINE_SELECTOR: [u8; 4] = [0xf6, 0x50, 0x1d, 0xdd];
+/// Selector of `cancelScheduled(uint64)`.
+pub const CANCEL_SCHEDULED_SELECTOR: [u8; 4] = [0xa0, 0x24, 0x47, 0x50];
+/// Selector of `closeAccount(string)`.
+pub const CLOSE_ACCOUNT_SELECTOR: [u8; 4] = [0xa7, 0x72, 0x75, 0x97];
+
+const ACTION_CREATE_ACCOUNT: u8 = 0;
+const ACTION_TRANSFER: u8 = 1;
+const ACTION_DEPLOY_CONTRACT: u8 = 2;
+const ACTION_FUNCTION_CALL: u8 = 3;
+const ACTION_STAKE: u8 = 4;
+const ACTION_ADD_FULL_ACCESS_KEY: u8 = 5;
+const ACTION_ADD_FUNCTION_CALL_KEY: u8 = 6;
+const ACTION_DELETE_KEY: u8 = 7;
+const ACTION_DELETE_ACCOUNT: u8 = 8;
+
+const NODE_FUNCTION_CALL: u8 = 0;
+const NODE_BATCH: u8 = 1;
+const NODE_THEN: u8 = 2;
+const NODE_AND: u8 = 3;
+
+/// Decodes the input of the precompile in either of the supported encodings.
+#[must_use]
+pub fn decode_args(input: &[u8]) -> Option<CrossContractCallArgs> {
+    let decode_input: fn(&[u8]) -> Option<CrossContractCallArgs> = match input.get(..4)? {
+        selector if selector == FUNCTION_CALL_SELECTOR => decode_function_call_input,
+        selector if selector == BATCH_SELECTOR => decode_batch_input,
+        selector if selector == PROMISE_TREE_SELECTOR => decode_promise_tree_input,
+        selector if selector == SCHEDULE_WITH_DEADLINE_SELECTOR => {
+            decode_schedule_with_deadline_input
+        }
+        selector if selector == CANCEL_SCHEDULED_SELECTOR => decode_cancel_scheduled_input,
+        selector if selector == CLOSE_ACCOUNT_SELECTOR => decode_close_account_input,
+        _ => return CrossContractCallArgs::try_from_slice(input).ok(),
+    };
+    decode_input(&input[4..])
+}
+
+fn decode_function_call_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let mut tokens = decode(&[function_call_type(), ParamType::Bool], data)?;
+    let call = function_call(tokens.next()?)?;
+    let delayed = tokens.next()?.into_bool()?;
+    Some(eager_or_delayed(PromiseArgs::Create(call), delayed))
+}
+
+fn decode_batch_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let mut tokens = decode(&[batch_type(), ParamType::Bool], data)?;
+    let batch = batch(tokens.next()?)?;
+    let delayed = tokens.next()?.into_bool()?;
+    let promise = NearPromise::Simple(SimpleNearPromise::Batch(batch));
+    Some(eager_or_delayed(PromiseArgs::Recursive(promise), delayed))
+}
+
+fn decode_promise_tree_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let mut tokens = decode(&[promise_nodes_type(), ParamType::Bool], data)?;
+    let promise = promise_tree(tokens.next()?.into_array()?)?;
+    let delayed = tokens.next()?.into_bool()?;
+    Some(eager_or_delayed(PromiseArgs::Recursive(promise), delayed))
+}
+
+fn decode_schedule_with_deadline_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let mut tokens = decode(&[promise_nodes_type(), ParamType::Uint(64)], data)?;
+    let promise = promise_tree(tokens.next()?.into_array()?)?;
+    let deadline = uint(tokens.next()?, 64)?.low_u64();
+    Some(CrossContractCallArgs::DelayedWithDeadline(
+        PromiseArgs::Recursive(promise),
+        deadline,
+    ))
+}
+
+fn decode_cancel_scheduled_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let nonce = uint(decode_single(ParamType::Uint(64), data)?, 64)?.low_u64();
+    Some(CrossContractCallArgs::CancelScheduled(nonce))
+}
+
+fn decode_close_account_input(data: &[u8]) -> Option<CrossContractCallArgs> {
+    let beneficiary_id = account_id(decode_single(ParamType::String, data)?)?;
+    Some(CrossContractCallArgs::CloseAccount(beneficiary_id))
+}
+
+const fn eager_or_delayed(promise: PromiseArgs, delayed: bool) -> CrossContractCallArgs {
+    if delayed {
+        CrossContractCallArgs::Delayed(promise)
+    } else {
+        CrossContractCallArgs::Eager(promise)
+    }
+}
+
+/// Builds the promise from the flat list of nodes. The nodes can only reference the preceding
+/// nodes and each of them must be referenced exactly once, so the size of the promise is
+/// bounded by the size of the input. The last node is the root of the promise.
+fn promise_tree(nodes: Vec<Token>) -> Option<NearPromise> {
+    let mut built: Vec<Option<NearPromise>> = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        let mut fields = node.into_tuple()?.into_iter();
+        let kind = uint8(fields.next()?)?;
+        let data = fields.next()?.into_bytes()?;
+
+        let promise = match kind {
+            NODE_FUNCTION_CALL => {
+                let call = decode_single(function_call_type(), &data)?;
+                NearPromise::Simple(SimpleNearPromise::Create(function_call(call)?))
+            }
+            NODE_BATCH => {
+                let batch = decode_single(batch_type(), &data)?;
+                NearPromise::Simple(SimpleNearPromise::Batch(self::batch(batch)?))
+            }
+            NODE_THEN => {
+                let mut tokens =
+                    ethabi::decode(&[ParamType::Uint(256), ParamType::Uint(256)], &data)
+                        .ok()?
+                        .into_iter();
+                let base = take_node(&mut built, tokens.next()?)?;
+                let NearPromise::Simple(callback) = take_node(&mut built, tokens.next()?)? else {
+                    return None;
+                };
+                NearPromise::Then {
+                    base: Box::new(base),
+                    callback,
+                }
+            }
+            NODE_AND => {
+                let indices =
+                    decode_single(ParamType::Array(Box::new(ParamType::Uint(256))), &data)?
+                        .into_array()?;
+                let promises = indices
+                    .into_iter()
+                    .map(|index| take_node(&mut built, index))
+                    .collect::<Option<Vec<_>>>()?;
+                NearPromise::And(promises)
+            }
+            _ => return None,
+        };
+
+        built.push(Some(promise));
+    }
+
+    let root = built.pop()??;
+    // All the other nodes must be a part of the promise.
+    built.iter().all(Option::is_none).then_some(root)
+}
+
+fn take_node(built: &mut [Option<NearPromise>], index: Token) -> Option<NearPromise> {
+    let index = usize::try_from(uint(index, 64)?.low_u64()).ok()?;
+    built.get_mut(index)?.take()
+}
+
+fn promise_nodes_type() -> ParamType {
+    let node_type = ParamType::Tuple(vec![ParamType::Uint(8), ParamType::Bytes]);
+    ParamType::Array(Box::new(node_type))
+}
+
+fn function_call_type() -> ParamType {
+    ParamType::Tuple(vec![
+        ParamType::String,
+        ParamType::String,
+        ParamType::Bytes,
+        ParamType::Uint(128),
+        ParamType::Uint(64),
+    ])
+}
+
+fn batch_type() -> ParamType {
+    let action_type = ParamType::Tuple(vec![ParamType::Uint(8), ParamType::Bytes]);
+    ParamType::Tuple(vec![
+        ParamType::String,
+        ParamType::Array(Box::new(action_type)),
+    ])
+}
+
+fn function_call(token: Token) -> Option<PromiseCreateArgs> {
+    let mut fields = token.into_tuple()?.into_iter();
+    Some(PromiseCreateArgs {
+        target_account_id: account_id(fields.next()?)?,
+        method: fields.next()?.into_string()?,
+        args: fields.next()?.into_bytes()?,
+        attached_balance: yocto(fields.next()?)?,
+        attached_gas: gas(fields.next()?)?,
+    })
+}
+
+fn batch(token: Token) -> Option<PromiseBatchAction> {
+    let mut fields = token.into_tuple()?.into_iter();
+    let target_account_id = account_id(fields.next()?)?;
+    let actions = fields
+        .next()?
+        .into_array()?
+        .into_iter()
+        .map(action)
+        .collect::<Option<Vec<_>>>()?;
+    Some(PromiseBatchAction {
+        target_account_id,
+        actions,
+    })
+}
+
+fn action(token: Token) -> Option<PromiseAction> {
+    let mut fields = token.into_tuple()?.into_iter();
+    let kind = uint8(fields.next()?)?;
+    let data = fields.next()?.into_bytes()?;
+
+    let action = match kind {
+        ACTION_CREATE_ACCOUNT => PromiseAction::CreateAccount,
+        ACTION_TRANSFER => {
+            let mut params = decode(&[ParamType::Uint(128)], &data)?;
+            PromiseAction::Transfer {
+                amount: yocto(params.next()?)?,
+            }
+        }
+        ACTION_DEPLOY_CONTRACT => PromiseAction::DeployContract {
+            code: decode_single(ParamType::Bytes, &data)?.into_bytes()?,
+        },
+        ACTION_FUNCTION_CALL => {
+            let mut params = decode(
+                &[
+                    ParamType::String,
+                    ParamType::Bytes,
+                    ParamType::Uint(128),
+                    ParamType::Uint(64),
+                ],
+                &data,
+            )?;
+            PromiseAction::FunctionCall {
+                name: params.next()?.into_string()?,
+                args: params.next()?.into_bytes()?,
+                attached_yocto: yocto(params.next()?)?,
+                gas: gas(params.next()?)?,
+            }
+        }
+        ACTION_STAKE => {
+            let mut params = decode(&[ParamType::Uint(128), ParamType::Bytes], &data)?;
+            PromiseAction::Stake {
+                amount: yocto(params.next()?)?,
+                public_key: public_key(params.next()?)?,
+            }
+        }
+        ACTION_ADD_FULL_ACCESS_KEY => {
+            let mut params = decode(&[ParamType::Bytes, ParamType::Uint(64)], &data)?;
+            PromiseAction::AddFullAccessKey {
+                public_key: public_key(params.next()?)?,
+                nonce: uint(params.next()?, 64)?.low_u64(),
+            }
+        }
+        ACTION_ADD_FUNCTION_CALL_KEY => {
+            let mut params = decode(
+                &[
+                    ParamType::Bytes,
+                    ParamType::Uint(64),
+                    ParamType::Uint(128),
+                    ParamType::String,
+                    ParamType::String,
+                ],
+                &data,
+            )?;
+            PromiseAction::AddFunctionCallKey {
+                public_key: public_key(params.next()?)?,
+                nonce: uint(params.next()?, 64)?.low_u64(),
+                allowance: yocto(params.next()?)?,
+                receiver_id: account_id(params.next()?)?,
+                function_names: params.next()?.into_string()?,
+            }
+        }
+        ACTION_DELETE_KEY => PromiseAction::DeleteKey {
+            public_key: public_key(decode_single(ParamType::Bytes, &data)?)?,
+        },
+        ACTION_DELETE_ACCOUNT => PromiseAction::DeleteAccount {
+            beneficiary_id: account_id(decode_single(ParamType::String, &data)?)?,
+        },
+        _ => return None,
+    };
+
+    Some(action)
+}
+
+fn decode(types: &[ParamType], data: &[u8]) -> Option<impl Iterator<Item = Token>> {
+    ethabi::decode(types, data).ok().map(Vec::into_iter)
+}
+
+fn decode_single(kind: ParamType, data: &[u8]) -> Option<Token> {
+    decode(&[kind], data)?.next()
+}
+
+/// Returns the value of the unsigned integer token if it fits into the provided number of bits.
+fn uint(token: Token, bits: usize) -> Option<U256> {
+    token.into_uint().filter(|value| value.bits() <= bits)
+}
+
+fn uint8(token: Token) -> Option<u8> {
+    uint(token, 8).map(|value| value.byte(0))
+}
+
+fn yocto(token: Token) -> Option<Yocto> {
+    uint(token, 128).map(|value| Yocto::new(value.low_u128()))
+}
+
+fn gas(token: Token) -> Option<NearGas> {
+    uint(token, 64).map(|value| NearGas::new(value.low_u64()))
+}
+
+fn account_id(token: Token) -> Option<AccountId> {
+    AccountId::new(&token.into_string()?).ok()
+}
+
+/// Public keys are passed in the borsh encoding: the key type followed by the key bytes.
+fn public_key(token: Token) -> Option<PublicKey> {
+    PublicKey::try_from_slice(&token.into_bytes()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_types::borsh;
+
+    fn call(target: &str, method: &str) -> PromiseCreateArgs {
+        PromiseCreateArgs {
+            target_account_id: target.parse().unwrap(),
+            method: method.into(),
+            args: b"{\"key\":\"value\"}".to_vec(),
+            attached_balance: Yocto::new(1),
+            attached_gas: NearGas::new(5_000_000_000_000),
+        }
+    }
+
+    fn call_token(call: &PromiseCreateArgs) -> Token {
+        Token::Tuple(vec![
+            Token::String(call.target_account_id.to_string()),
+            Token::String(call.method.clone()),
+            Token::Bytes(call.args.clone()),
+            Token::Uint(call.attached_balance.as_u128().into()),
+            Token::Uint(call.attached_gas.as_u64().into()),
+        ])
+    }
+
+    const fn public_key() -> PublicKey {
+        PublicKey::Ed25519([7; 32])
+    }
+
+    fn batch() -> PromiseBatchAction {
+        PromiseBatchAction {
+            target_account_id: "new.aurora".parse().unwrap(),
+            actions: vec![
+                PromiseAction::CreateAccount,
+                PromiseAction::Transfer {
+                    amount: Yocto::new(1_000_000),
+                },
+                PromiseAction::DeployContract {
+                    code: vec![0x00, 0x61, 0x73, 0x6d],
+                },
+                PromiseAction::FunctionCall {
+                    name: "new".into(),
+                    args: b"{}".to_vec(),
+                    attached_yocto: Yocto::new(0),
+                    gas: NearGas::new(10_000_000_000_000),
+                },
+                PromiseAction::Stake {
+                    amount: Yocto::new(u128::MAX),
+                    public_key: public_key(),
+                },
+                PromiseAction::AddFullAccessKey {
+                    public_key: public_key(),
+                    nonce: 1,
+                },
+                PromiseAction::AddFunctionCallKey {
+                    public_key: public_key(),
+                    nonce: u64::MAX,
+                    allowance: Yocto::new(5),
+                    receiver_id: "receiver.near".parse().unwrap(),
+                    function_names: "a,b".into(),
+                },
+                PromiseAction::DeleteKey {
+                    public_key: public_key(),
+                },
+                PromiseAction::DeleteAccount {
+                    beneficiary_id: "beneficiary.near".parse().unwrap(),
+                },
+            ],
+        }
+    }
+
+    fn action_token(kind: u8, params: &[Token]) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(kind.into()),
+            Token::Bytes(ethabi::encode(params)),
+        ])
+    }
+
+    fn batch_token() -> Token {
+        let key = Token::Bytes(borsh::to_vec(&public_key()).unwrap());
+        Token::Tuple(vec![
+            Token::String("new.aurora".into()),
+            Token::Array(vec![
+                action_token(ACTION_CREATE_ACCOUNT, &[]),
+                action_token(ACTION_TRANSFER, &[Token::Uint(1_000_000.into())]),
+                action_token(
+                    ACTION_DEPLOY_CONTRACT,
+                    &[Token::Bytes(vec![0x00, 0x61, 0x73, 0x6d])],
+                ),
+                action_token(
+                    ACTION_FUNCTION_CALL,
+                    &[
+                        Token::String("new".into()),
+                        Token::Bytes(b"{}".to_vec()),
+                        Token::Uint(0.into()),
+                        Token::Uint(10_000_000_000_000_u64.into()),
+                    ],
+                ),
+                action_token(ACTION_STAKE, &[Token::Uint(u128::MAX.into()), key.clone()]),
+                action_token(
+                    ACTION_ADD_FULL_ACCESS_KEY,
+                    &[key.clone(), Token::Uint(1.into())],
+                ),
+                action_token(
+                    ACTION_ADD_FUNCTION_CALL_KEY,
+                    &[
+                        key.clone(),
+                        Token::Uint(u64::MAX.into()),
+                        Token::Uint(5.into()),
+                        Token::String("receiver.near".into()),
+                        Token::String("a,b".into()),
+                    ],
+                ),
+                action_token(ACTION_DELETE_KEY, &[key]),
+                action_token(
+                    ACTION_DELETE_ACCOUNT,
+                    &[Token::String("beneficiary.near".into())],
+                ),
+            ]),
+        ])
+    }
+
+    fn node_token(kind: u8, params: &[Token]) -> Token {
+        action_token(kind, params)
+    }
+
+    fn input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+        [selector.as_slice(), &ethabi::encode(params)].concat()
+    }
+
+    /// Checks that the ABI-encoded input is decoded into the expected arguments
+    /// and that the borsh-encoded expected arguments are decoded into the same value.
+    fn assert_decoded(abi_input: &[u8], expected: &CrossContractCallArgs) {
+        let expected_bytes = borsh::to_vec(expected).unwrap();
+        let from_abi = decode_args(abi_input).unwrap();
+        let from_borsh = decode_args(&expected_bytes).unwrap();
+        assert_eq!(borsh::to_vec(&from_abi).unwrap(), expected_bytes);
+        assert_eq!(borsh::to_vec(&from_borsh).unwrap(), expected_bytes);
+    }
+
+    #[test]
+    fn test_selectors_differ_from_borsh_variants() {
+        // The borsh encoding starts with the index of the `CrossContractCallArgs` variant.
+        for selector in [
+            FUNCTION_CALL_SELECTOR,
+            BATCH_SELECTOR,
+            PROMISE_TREE_SELECTOR,
+            SCHEDULE_WITH_DEADLINE_SELECTOR,
+            CANCEL_SCHEDULED_SELECTOR,
+            CLOSE_ACCOUNT_SELECTOR,
+        ] {
+            assert!(selector[0] > 5);
+        }
+    }
+
+    #[test]
+    fn test_decode_schedule_with_deadline() {
+        let call = call("target.near", "method");
+        let nodes = Token::Array(vec![node_token(NODE_FUNCTION_CALL, &[call_token(&call)])]);
+        let abi_input = input(
+            SCHEDULE_WITH_DEADLINE_SELECTOR,
+            &[nodes, Token::Uint(1_000.into())],
+        );
+        let expected = CrossContractCallArgs::DelayedWithDeadline(
+            PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Create(call))),
+            1_000,
+        );
+        assert_decoded(&abi_input, &expected);
+    }
+
+    #[test]
+    fn test_decode_cancel_scheduled() {
+        let abi_input = input(CANCEL_SCHEDULED_SELECTOR, &[Token::Uint(7.into())]);
+        assert_decoded(&abi_input, &CrossContractCallArgs::CancelScheduled(7));
+    }
+
+    #[test]
+    fn test_decode_close_account() {
+        let abi_input = input(
+            CLOSE_ACCOUNT_SELECTOR,
+            &[Token::String("beneficiary.near".into())],
+        );
+        let expected = CrossContractCallArgs::CloseAccount("beneficiary.near".parse().unwrap());
+        assert_decoded(&abi_input, &expected);
+    }
+
+    #[test]
+    fn test_decode_function_call() {
+        let call = call("target.near", "method");
+        for delayed in [false, true] {
+            let abi_input = input(
+                FUNCTION_CALL_SELECTOR,
+                &[call_token(&call), Token::Bool(delayed)],
+            );
+            let promise = PromiseArgs::Create(call.clone());
+            let expected = if delayed {
+                CrossContractCallArgs::Delayed(promise)
+            } else {
+                CrossContractCallArgs::Eager(promise)
+            };
+            assert_decoded(&abi_input, &expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_batch() {
+        let abi_input = input(BATCH_SELECTOR, &[batch_token(), Token::Bool(false)]);
+        let expected = CrossContractCallArgs::Eager(PromiseArgs::Recursive(NearPromise::Simple(
+            SimpleNearPromise::Batch(batch()),
+        )));
+        assert_decoded(&abi_input, &expected);
+    }
+
+    #[test]
+    fn test_decode_promise_tree() {
+        // (a & (b -> batch)) -> c
+        let (a, b, c) = (
+            call("a.near", "a"),
+            call("b.near", "b"),
+            call("c.near", "c"),
+        );
+        let nodes = Token::Array(vec![
+            node_token(NODE_FUNCTION_CALL, &[call_token(&a)]),
+            node_token(NODE_FUNCTION_CALL, &[call_token(&b)]),
+            node_token(NODE_BATCH, &[batch_token()]),
+            node_token(NODE_THEN, &[Token::Uint(1.into()), Token::Uint(2.into())]),
+            node_token(
+                NODE_AND,
+                &[Token::Array(vec![
+                    Token::Uint(0.into()),
+                    Token::Uint(3.into()),
+                ])],
+            ),
+            node_token(NODE_FUNCTION_CALL, &[call_token(&c)]),
+            node_token(NODE_THEN, &[Token::Uint(4.into()), Token::Uint(5.into())]),
+        ]);
+        let abi_input = input(PROMISE_TREE_SELECTOR, &[nodes, Token::Bool(true)]);
+
+        let expected = CrossContractCallArgs::Delayed(PromiseArgs::Recursive(NearPromise::Then {
+            base: Box::new(NearPromise::And(vec![
+                NearPromise::Simple(SimpleNearPromise::Create(a)),
+                NearPromise::Then {
+                    base: Box::new(NearPromise::Simple(SimpleNearPromise::Create(b))),
+                    callback: SimpleNearPromise::Batch(batch()),
+                },
+            ])),
+            callback: SimpleNearPromise::Create(c),
+        }));
+        assert_decoded(&abi_input, &expected);
+    }
+
+    #[test]
+    fn test_invalid_promise_trees() {
+        let a = node_token(NODE_FUNCTION_CALL, &[call_token(&call("a.near", "a"))]);
+        let and = |indices: &[u64]| {
+            node_token(
+                NODE_AND,
+                &[Token::Array(
+                    indices.iter().map(|i| Token::Uint((*i).into())).collect(),
+                )],
+            )
+        };
+        let then = |base: u64, callback: u64| {
+            node_token(
+                NODE_THEN,
+                &[Token::Uint(base.into()), Token::Uint(callback.into())],
+            )
+        };
+
+        let invalid = [
+            // Empty promise
+            vec![],
+            // Node referenced twice
+            vec![a.clone(), and(&[0, 0])],
+            // Forward reference
+            vec![and(&[1]), a.clone()],
+            // Unused node
+            vec![a.clone(), a.clone()],
+            // Callback isn't a simple promise
+            vec![a.clone(), a, and(&[1]), then(0, 2)],
+        ];
+
+        for nodes in invalid {
+            let abi_input = input(
+                PROMISE_TREE_SELECTOR,
+                &[Token::Array(nodes), Token::Bool(false)],
+            );
+            assert!(decode_args(&abi_input).is_none());
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_values() {
+        let mut call = call_token(&call("target.near", "method"));
+        if let Token::Tuple(fields) = &mut call {
+            fields[4] = Token::Uint(U256::from(u64::MAX) + 1);
+        }
+        let abi_input = input(FUNCTION_CALL_SELECTOR, &[call, Token::Bool(false)]);
+        assert!(decode_args(&abi_input).is_none());
+    }
+}
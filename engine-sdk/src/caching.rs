@@ -15,3 +15,133 @@ impl<K: Ord, V> FullCache<K, V> {
         self.inner.contains_key(k)
     }
 }
+
+/// Number of the lookups served by a cache and of the ones which missed it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheMetrics {
+    /// Returns the share of the lookups served by the cache, or zero if there were none.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A cache which outlives a single execution of the engine. Since the cached values can be
+/// changed by other executions, its owner is responsible for invalidating them.
+pub trait SharedCache<K, V> {
+    /// Returns the cached value of the key and records the lookup in the metrics.
+    fn get(&mut self, key: &K) -> Option<V>;
+
+    /// Returns the cached value of the key without recording the lookup.
+    fn peek(&self, key: &K) -> Option<V>;
+
+    fn insert(&mut self, key: K, value: V);
+
+    fn invalidate(&mut self, key: &K);
+
+    fn clear(&mut self);
+
+    fn metrics(&self) -> CacheMetrics;
+}
+
+/// A cache holding at most `capacity` entries, evicting the least recently used one first.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: BTreeMap<K, (V, u64)>,
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+    metrics: CacheMetrics,
+}
+
+impl<K, V> LruCache<K, V> {
+    #[must_use]
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            metrics: CacheMetrics { hits: 0, misses: 0 },
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: Ord + Clone, V> LruCache<K, V> {
+    fn touch(&mut self, key: &K) {
+        if let Some((_, last_used)) = self.entries.get_mut(key) {
+            self.recency.remove(last_used);
+            self.tick += 1;
+            *last_used = self.tick;
+            self.recency.insert(self.tick, key.clone());
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> SharedCache<K, V> for LruCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.touch(key);
+        let value = self.entries.get(key).map(|(value, _)| value.clone());
+        if value.is_some() {
+            self.metrics.hits += 1;
+        } else {
+            self.metrics.misses += 1;
+        }
+        value
+    }
+
+    fn peek(&self, key: &K) -> Option<V> {
+        self.entries.get(key).map(|(value, _)| value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.invalidate(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    fn invalidate(&mut self, key: &K) {
+        if let Some((_, last_used)) = self.entries.remove(key) {
+            self.recency.remove(&last_used);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+}
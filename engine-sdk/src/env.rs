@@ -51,6 +51,11 @@ pub trait Env {
     fn random_seed(&self) -> H256;
     /// Prepaid NEAR Gas
     fn prepaid_gas(&self) -> NearGas;
+    /// NEAR Gas used so far by the current call. Environments which don't meter gas
+    /// (e.g. the standalone engine) report zero.
+    fn used_gas(&self) -> NearGas {
+        NearGas::new(0)
+    }
 
     fn assert_private_call(&self) -> Result<(), PrivateCallError> {
         if self.predecessor_account_id() == self.current_account_id() {
@@ -191,10 +191,33 @@ pub fn log(data: &str) {
     log_utf8(data.as_bytes());
 }
 
-#[cfg(not(feature = "contract"))]
+#[cfg(all(not(feature = "contract"), feature = "std"))]
+pub fn log(data: &str) {
+    CAPTURED_LOGS.with_borrow_mut(|logs| {
+        if let Some(logs) = logs {
+            logs.push(data.to_string());
+        }
+    });
+}
+
+#[cfg(all(not(feature = "contract"), not(feature = "std")))]
 #[allow(clippy::missing_const_for_fn)]
-pub fn log(_data: &str) {
-    // TODO: standalone logging
+pub fn log(_data: &str) {}
+
+#[cfg(all(not(feature = "contract"), feature = "std"))]
+std::thread_local! {
+    static CAPTURED_LOGS: core::cell::RefCell<Option<Vec<String>>> =
+        const { core::cell::RefCell::new(None) };
+}
+
+/// Runs the closure and returns the logs it emitted. Outside the contract the logs are
+/// discarded unless they are emitted within this function.
+#[cfg(all(not(feature = "contract"), feature = "std"))]
+pub fn capture_logs<R, F: FnOnce() -> R>(f: F) -> (R, Vec<String>) {
+    let outer_logs = CAPTURED_LOGS.replace(Some(Vec::new()));
+    let result = f();
+    let logs = CAPTURED_LOGS.replace(outer_logs).unwrap_or_default();
+    (result, logs)
 }
 
 #[macro_export]
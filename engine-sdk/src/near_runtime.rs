@@ -379,6 +379,10 @@ impl crate::env::Env for Runtime {
     fn prepaid_gas(&self) -> NearGas {
         NearGas::new(unsafe { exports::prepaid_gas() })
     }
+
+    fn used_gas(&self) -> NearGas {
+        NearGas::new(unsafe { exports::used_gas() })
+    }
 }
 
 impl crate::promise::PromiseHandler for Runtime {
@@ -623,7 +627,7 @@ pub(crate) mod exports {
         fn account_balance(balance_ptr: u64);
         pub(crate) fn attached_deposit(balance_ptr: u64);
         pub(crate) fn prepaid_gas() -> u64;
-        fn used_gas() -> u64;
+        pub(crate) fn used_gas() -> u64;
         // ############
         // # Math API #
         // ############
@@ -1,9 +1,22 @@
+use aurora_engine_sdk::caching::SharedCache;
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use rocksdb::DB;
 use std::cell::{Cell, RefCell};
+use std::sync::{Mutex, PoisonError};
 
 use crate::diff::{Diff, DiffValue};
-use crate::StoragePrefix;
+use crate::{StoragePrefix, ENGINE_KEY_SUFFIX_LEN};
+
+/// The latest value of an Engine key together with the block height and transaction position
+/// where it was written. `written_at` is `None` if the key has never been written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedEngineValue {
+    pub value: Option<Vec<u8>>,
+    pub written_at: Option<(u64, u16)>,
+}
+
+/// Cache of the latest Engine values shared by all the transactions executed over a `Storage`.
+pub type EngineCache = dyn SharedCache<Vec<u8>, CachedEngineValue> + Send;
 
 #[derive(Debug)]
 pub enum EngineStorageValue<'a> {
@@ -42,6 +55,7 @@ pub struct EngineStateAccess<'db, 'input, 'output> {
     transaction_diff: &'output RefCell<Diff>,
     output: &'output Cell<Vec<u8>>,
     db: &'db DB,
+    cache: &'db Mutex<Box<EngineCache>>,
 }
 
 impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
@@ -52,6 +66,7 @@ impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
         transaction_diff: &'output RefCell<Diff>,
         output: &'output Cell<Vec<u8>>,
         db: &'db DB,
+        cache: &'db Mutex<Box<EngineCache>>,
     ) -> Self {
         Self {
             input,
@@ -60,6 +75,7 @@ impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
             transaction_diff,
             output,
             db,
+            cache,
         }
     }
 
@@ -77,6 +93,46 @@ impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
         opt.set_iterate_lower_bound(lower_bound);
         opt
     }
+
+    /// Reads the latest value of the key, ignoring the bound of this access. Returns `None` if
+    /// the value can't be determined from a single read.
+    fn read_latest(&self, key: &[u8]) -> Option<CachedEngineValue> {
+        let lower_bound = super::construct_storage_key(StoragePrefix::Engine, key);
+        let upper_bound = super::construct_engine_key(key, u64::MAX, u16::MAX);
+        let key_len = lower_bound.len();
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_upper_bound(upper_bound);
+        opt.set_iterate_lower_bound(lower_bound);
+
+        let mut iter = self.db.iterator_opt(rocksdb::IteratorMode::End, opt);
+        let Some(maybe_elem) = iter.next() else {
+            return Some(CachedEngineValue {
+                value: None,
+                written_at: None,
+            });
+        };
+        let (db_key, value) = maybe_elem.ok()?;
+        // Keys which extend the given key fall into the same range.
+        if db_key.len() != key_len + ENGINE_KEY_SUFFIX_LEN {
+            return None;
+        }
+        let block_height = {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&db_key[key_len..(key_len + 8)]);
+            u64::from_be_bytes(buf)
+        };
+        let transaction_position = {
+            let mut buf = [0u8; 2];
+            buf.copy_from_slice(&db_key[(key_len + 8)..]);
+            u16::from_be_bytes(buf)
+        };
+        let value = DiffValue::try_from_bytes(&value).expect("diff value is invalid");
+
+        Some(CachedEngineValue {
+            value: value.take_value(),
+            written_at: Some((block_height, transaction_position)),
+        })
+    }
 }
 
 impl<'db, 'input: 'db, 'output: 'db> IO for EngineStateAccess<'db, 'input, 'output> {
@@ -97,6 +153,19 @@ impl<'db, 'input: 'db, 'output: 'db> IO for EngineStateAccess<'db, 'input, 'outp
                 .map(|bytes| EngineStorageValue::Vec(bytes.to_vec()));
         }
 
+        // The cache only holds the latest values, so it can serve the reads bound after them.
+        let bound = Some((self.bound_block_height, self.bound_tx_position));
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        let cached = cache.get(&key.to_vec()).or_else(|| {
+            let latest = self.read_latest(key)?;
+            cache.insert(key.to_vec(), latest.clone());
+            Some(latest)
+        });
+        drop(cache);
+        if let Some(cached) = cached.filter(|cached| cached.written_at < bound) {
+            return cached.value.map(EngineStorageValue::Vec);
+        }
+
         let opt = self.construct_engine_read(key);
         let mut iter = self.db.iterator_opt(rocksdb::IteratorMode::End, opt);
         let value = iter.next().and_then(|maybe_elem| {
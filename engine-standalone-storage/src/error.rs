@@ -12,6 +12,8 @@ pub enum Error {
     Rocksdb(rocksdb::Error),
     EngineAccountIdNotSet,
     EngineAccountIdCorrupted,
+    Hashchain(String),
+    NoHashchainAtHeight(u64),
 }
 
 impl From<rocksdb::Error> for Error {
@@ -20,6 +22,12 @@ impl From<rocksdb::Error> for Error {
     }
 }
 
+impl From<aurora_engine_hashchain::error::BlockchainHashchainError> for Error {
+    fn from(e: aurora_engine_hashchain::error::BlockchainHashchainError) -> Self {
+        Self::Hashchain(format!("{e:?}"))
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Self::Borsh(e.to_string())
@@ -0,0 +1,242 @@
+//! Block hashchain computed from the transactions processed by the standalone engine.
+//!
+//! The engine commits to the inputs and outputs of its transactions with the block hashchain.
+//! The storage recomputes it from the outcomes of the replayed transactions, so indexers can
+//! compare the values with the ones of the on-chain engine to check they executed the same
+//! transactions with the same results.
+
+use crate::sync::{
+    types::{TransactionKindTag, TransactionMessage},
+    TransactionExecutionResult, TransactionIncludedOutcome,
+};
+use crate::{construct_storage_key, Diff, Error, Storage, StoragePrefix};
+use aurora_engine::hashchain::{advance_to_block, HASHCHAIN_STATE, LOGS_HASHCHAIN_METHODS};
+use aurora_engine_hashchain::{
+    bloom::{self, Bloom},
+    hashchain::{compute_tx_hash, Hashchain},
+};
+use aurora_engine_types::{
+    storage::{bytes_to_key, KeyPrefix},
+    types::RawH256,
+};
+
+const TX_KEY: &[u8] = b"tx";
+const TX_HASH_KEY: &[u8] = b"leaf";
+const BLOCK_KEY: &[u8] = b"block";
+
+/// Result of the comparison of the hashchain of a block with the expected value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashchainVerification {
+    Verified,
+    Diverged {
+        computed: RawH256,
+        /// The first transaction of the block whose hash doesn't match the expected one. `None`
+        /// if they all match, meaning the logs or an earlier block differ from the on-chain ones.
+        first_diverging_tx: Option<DivergingTx>,
+    },
+}
+
+/// Transaction of the block whose hash doesn't match the expected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivergingTx {
+    /// Index of the transaction among the transactions of the block added to the hashchain.
+    pub index: usize,
+    /// Position of the replayed transaction in the block and its hash. `None` if fewer
+    /// transactions than expected have been replayed.
+    pub replayed: Option<(u16, RawH256)>,
+}
+
+impl Storage {
+    /// Returns the hashchain of the block with the given height.
+    pub fn get_hashchain_at(&self, block_height: u64) -> Result<RawH256, Error> {
+        if let Some(slice) = self.db.get_pinned(block_key(block_height))? {
+            let mut hashchain = [0u8; 32];
+            hashchain.copy_from_slice(slice.as_ref());
+            return Ok(hashchain);
+        }
+
+        // The block has no transactions or no transaction was processed after it yet, so the
+        // value is computed from the state of the hashchain after the last transaction before.
+        let mut hashchain = self
+            .read_hashchain_before(&tx_key(block_height + 1, 0))?
+            .ok_or(Error::NoHashchainAtHeight(block_height))?;
+        hashchain.move_to_block(block_height + 1)?;
+        Ok(hashchain.get_previous_block_hashchain())
+    }
+
+    /// Compares the hashchain of the block with the given height with the expected value. On
+    /// divergence, the hashes of the replayed transactions are compared with the expected ones,
+    /// which are the hashes of the on-chain transactions of the block computed with
+    /// `compute_tx_hash` in the order of execution.
+    pub fn verify_hashchain(
+        &self,
+        block_height: u64,
+        expected: RawH256,
+        expected_tx_hashes: &[RawH256],
+    ) -> Result<HashchainVerification, Error> {
+        let computed = self.get_hashchain_at(block_height)?;
+        if computed == expected {
+            return Ok(HashchainVerification::Verified);
+        }
+
+        let replayed = self.get_block_tx_hashes(block_height)?;
+        let first_diverging_tx = (0..replayed.len().max(expected_tx_hashes.len()))
+            .find(|&index| {
+                replayed.get(index).map(|(_, tx_hash)| tx_hash) != expected_tx_hashes.get(index)
+            })
+            .map(|index| DivergingTx {
+                index,
+                replayed: replayed.get(index).copied(),
+            });
+
+        Ok(HashchainVerification::Diverged {
+            computed,
+            first_diverging_tx,
+        })
+    }
+
+    /// Returns the positions and hashes of the replayed transactions of the block which have
+    /// been added to the hashchain.
+    fn get_block_tx_hashes(&self, block_height: u64) -> Result<Vec<(u16, RawH256)>, Error> {
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_lower_bound(tx_hash_key(block_height, 0));
+        opt.set_iterate_upper_bound(tx_hash_key(block_height + 1, 0));
+
+        self.db
+            .iterator_opt(rocksdb::IteratorMode::Start, opt)
+            .map(|item| -> Result<(u16, RawH256), Error> {
+                let (key, value) = item?;
+                let mut position = [0u8; 2];
+                position.copy_from_slice(&key[key.len() - 2..]);
+                let mut tx_hash = [0u8; 32];
+                tx_hash.copy_from_slice(&value);
+                Ok((u16::from_be_bytes(position), tx_hash))
+            })
+            .collect()
+    }
+
+    /// Adds the transaction to the hashchain if the engine added it to its own hashchain.
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub(crate) fn add_hashchain_tx(
+        &mut self,
+        outcome: &TransactionIncludedOutcome,
+    ) -> Result<(), Error> {
+        let Some(engine_hashchain) = engine_hashchain(&outcome.diff)? else {
+            return Ok(());
+        };
+        let block_height = self.get_block_height_by_hash(outcome.info.block_hash)?;
+        let position = outcome.info.position;
+        let tag = TransactionKindTag::from(&outcome.info.transaction);
+        let mut batch = rocksdb::WriteBatch::default();
+
+        let previous_hashchain = self.read_hashchain_before(&tx_key(block_height, position))?;
+        let hashchain = match previous_hashchain {
+            // `new` and `start_hashchain` (re)start the hashchain, so it is taken from the engine.
+            Some(mut hashchain)
+                if !matches!(
+                    tag,
+                    TransactionKindTag::NewEngine | TransactionKindTag::StartHashchain
+                ) =>
+            {
+                // Same as in the engine, only the block left behind is recorded.
+                if let Some((left_height, left_hashchain)) =
+                    advance_to_block(&mut hashchain, block_height)?
+                {
+                    batch.put(block_key(left_height), left_hashchain);
+                }
+                hashchain.add_block_tx(
+                    block_height,
+                    tag.as_ref(),
+                    &outcome.info.raw_input,
+                    &outcome.output,
+                    &logs_bloom(tag, outcome),
+                )?;
+                hashchain
+            }
+            _ => engine_hashchain,
+        };
+
+        batch.put(tx_key(block_height, position), hashchain.try_serialize()?);
+        batch.put(
+            tx_hash_key(block_height, position),
+            compute_tx_hash(tag.as_ref(), &outcome.info.raw_input, &outcome.output),
+        );
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes the transaction from the hashchain.
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub(crate) fn revert_hashchain_tx(&mut self, tx_msg: &TransactionMessage) -> Result<(), Error> {
+        let block_height = self.get_block_height_by_hash(tx_msg.block_hash)?;
+        let mut batch = rocksdb::WriteBatch::default();
+        batch.delete(tx_key(block_height, tx_msg.position));
+        batch.delete(tx_hash_key(block_height, tx_msg.position));
+        batch.delete_range(block_key(block_height), block_key(u64::MAX));
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Returns the state of the hashchain after the last transaction before the given key.
+    fn read_hashchain_before(&self, upper_bound: &[u8]) -> Result<Option<Hashchain>, Error> {
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_upper_bound(upper_bound);
+        opt.set_iterate_lower_bound(construct_storage_key(StoragePrefix::Hashchain, TX_KEY));
+
+        self.db
+            .iterator_opt(rocksdb::IteratorMode::End, opt)
+            .next()
+            .transpose()?
+            .map(|(_, value)| Hashchain::try_deserialize(&value))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+/// Returns the hashchain written by the engine in the transaction diff.
+fn engine_hashchain(diff: &Diff) -> Result<Option<Hashchain>, Error> {
+    let key = bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
+    diff.get(&key)
+        .and_then(|value| value.value())
+        .map(Hashchain::try_deserialize)
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// The engine only accrues the logs bloom for the methods executing EVM transactions with
+/// `with_logs_hashchain`.
+fn logs_bloom(tag: TransactionKindTag, outcome: &TransactionIncludedOutcome) -> Bloom {
+    let uses_logs = LOGS_HASHCHAIN_METHODS.contains(&tag.as_ref());
+    match &outcome.maybe_result {
+        Ok(Some(TransactionExecutionResult::Submit(Ok(result)))) if uses_logs => {
+            bloom::get_logs_bloom(&result.logs)
+        }
+        _ => Bloom::default(),
+    }
+}
+
+fn tx_key(block_height: u64, position: u16) -> Vec<u8> {
+    construct_storage_key(
+        StoragePrefix::Hashchain,
+        &[TX_KEY, &block_height.to_be_bytes(), &position.to_be_bytes()].concat(),
+    )
+}
+
+fn tx_hash_key(block_height: u64, position: u16) -> Vec<u8> {
+    construct_storage_key(
+        StoragePrefix::Hashchain,
+        &[
+            TX_HASH_KEY,
+            &block_height.to_be_bytes(),
+            &position.to_be_bytes(),
+        ]
+        .concat(),
+    )
+}
+
+fn block_key(block_height: u64) -> Vec<u8> {
+    construct_storage_key(
+        StoragePrefix::Hashchain,
+        &[BLOCK_KEY, &block_height.to_be_bytes()].concat(),
+    )
+}
@@ -21,6 +21,7 @@ pub fn initialize_engine_state(
         batch.put(storage_key, storage_value.try_to_bytes()?);
     }
     storage.db.write(batch)?;
+    storage.engine_cache().clear();
 
     Ok(())
 }
@@ -5,12 +5,15 @@
     clippy::missing_panics_doc,
     clippy::missing_errors_doc
 )]
+use aurora_engine_sdk::caching::{CacheMetrics, LruCache};
 use aurora_engine_sdk::env::Timestamp;
 use aurora_engine_types::{account_id::AccountId, H256};
+use engine_state::{CachedEngineValue, EngineCache};
 use rocksdb::DB;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use sync::types::TransactionMessage;
 
 const VERSION: u8 = 0;
@@ -18,6 +21,7 @@ const VERSION: u8 = 0;
 pub mod diff;
 pub mod engine_state;
 pub mod error;
+pub mod hashchain;
 pub mod json_snapshot;
 pub mod promise;
 pub mod relayer_db;
@@ -32,6 +36,9 @@ pub use error::Error;
 /// 16 bits for the transaction position.
 const ENGINE_KEY_SUFFIX_LEN: usize = (64 / 8) + (16 / 8);
 
+/// Number of Engine values kept by the default cache shared between transactions.
+pub const DEFAULT_ENGINE_CACHE_CAPACITY: usize = 100_000;
+
 #[repr(u8)]
 pub enum StoragePrefix {
     BlockHash = 0x00,
@@ -44,6 +51,7 @@ pub enum StoragePrefix {
     EngineAccountId = 0x07,
     /// Prefix used for storing arbitrary data from the outside of the crate.
     CustomData = 0x8,
+    Hashchain = 0x09,
 }
 
 impl From<StoragePrefix> for u8 {
@@ -58,6 +66,7 @@ impl From<StoragePrefix> for u8 {
             StoragePrefix::BlockMetadata => 0x06,
             StoragePrefix::EngineAccountId => 0x07,
             StoragePrefix::CustomData => 0x08,
+            StoragePrefix::Hashchain => 0x09,
         }
     }
 }
@@ -66,12 +75,36 @@ const ACCOUNT_ID_KEY: &[u8] = b"engine_account_id";
 
 pub struct Storage {
     db: DB,
+    engine_cache: Mutex<Box<EngineCache>>,
 }
 
 impl Storage {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, rocksdb::Error> {
         let db = DB::open_default(path)?;
-        Ok(Self { db })
+        let engine_cache: Box<EngineCache> = Box::new(LruCache::<_, CachedEngineValue>::new(
+            DEFAULT_ENGINE_CACHE_CAPACITY,
+        ));
+        let engine_cache = Mutex::new(engine_cache);
+        Ok(Self { db, engine_cache })
+    }
+
+    /// Replaces the cache of the Engine values which is shared between transactions.
+    pub fn set_engine_cache(&mut self, cache: Box<EngineCache>) {
+        *self
+            .engine_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = cache;
+    }
+
+    /// Returns the hits and misses of the Engine values cache.
+    pub fn get_engine_cache_metrics(&self) -> CacheMetrics {
+        self.engine_cache().metrics()
+    }
+
+    fn engine_cache(&self) -> MutexGuard<Box<EngineCache>> {
+        self.engine_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
 
     pub fn set_engine_account_id(&mut self, id: &AccountId) -> Result<(), rocksdb::Error> {
@@ -209,9 +242,33 @@ impl Storage {
         diff: &Diff,
     ) -> Result<(), Error> {
         let batch = rocksdb::WriteBatch::default();
-        self.process_transaction(tx_hash, tx_included, diff, batch, |batch, key, value| {
-            batch.put(key, value);
-        })
+        let block_height =
+            self.process_transaction(tx_hash, tx_included, diff, batch, |batch, key, value| {
+                batch.put(key, value);
+            })?;
+
+        // Only the cached values which are the latest ones are updated, the others stay valid.
+        let written_at = Some((block_height, tx_included.position));
+        let cache = self
+            .engine_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (key, value) in diff {
+            if cache
+                .peek(key)
+                .is_some_and(|cached| cached.written_at <= written_at)
+            {
+                cache.insert(
+                    key.clone(),
+                    CachedEngineValue {
+                        value: value.value().map(<[u8]>::to_vec),
+                        written_at,
+                    },
+                );
+            }
+        }
+
+        Ok(())
     }
 
     pub fn revert_transaction_included(
@@ -223,7 +280,17 @@ impl Storage {
         let batch = rocksdb::WriteBatch::default();
         self.process_transaction(tx_hash, tx_included, diff, batch, |batch, key, _value| {
             batch.delete(key);
-        })
+        })?;
+
+        let cache = self
+            .engine_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (key, _) in diff {
+            cache.invalidate(key);
+        }
+
+        self.revert_hashchain_tx(tx_included)
     }
 
     #[allow(clippy::needless_pass_by_ref_mut)]
@@ -234,7 +301,7 @@ impl Storage {
         diff: &Diff,
         mut batch: rocksdb::WriteBatch,
         action: F,
-    ) -> Result<(), Error> {
+    ) -> Result<u64, Error> {
         let tx_included = TransactionIncluded {
             block_hash: tx_msg.block_hash,
             position: tx_msg.position,
@@ -259,7 +326,9 @@ impl Storage {
             action(&mut batch, &storage_key, &value_bytes);
         }
 
-        self.db.write(batch).map_err(Into::into)
+        self.db.write(batch)?;
+
+        Ok(block_height)
     }
 
     /// Returns a list of transactions that modified the key, and the values _after_ each transaction.
@@ -396,15 +465,17 @@ impl Storage {
             &diff,
             &engine_output,
             &self.db,
+            &self.engine_cache,
         );
 
-        let result = f(engine_state);
+        let (result, logs) = aurora_engine_sdk::capture_logs(|| f(engine_state));
         let diff = engine_state.get_transaction_diff();
         let engine_output = engine_output.into_inner();
 
         EngineAccessResult {
             result,
             engine_output,
+            logs,
             diff,
         }
     }
@@ -428,6 +499,8 @@ impl Storage {
 pub struct EngineAccessResult<R> {
     pub result: R,
     pub engine_output: Vec<u8>,
+    /// The logs emitted by the engine.
+    pub logs: Vec<String>,
     pub diff: Diff,
 }
 
@@ -1,6 +1,12 @@
-use aurora_engine_sdk::promise::{PromiseHandler, PromiseId};
+use crate::engine_state::EngineStateAccess;
+use crate::sync::TransactionIncludedOutcome;
+use crate::sync::{self, types::TransactionMessage, TransactionExecutionResult};
+use crate::{Error, Storage};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_sdk::promise::{PromiseHandler, PromiseId, ReadOnlyPromiseHandler};
 use aurora_engine_types::parameters::{PromiseBatchAction, PromiseCreateArgs};
 use aurora_engine_types::types::PromiseResult;
+use aurora_engine_types::H256;
 
 /// Implements `PromiseHandler` so that it can be used in the standalone engine implementation of
 /// methods like `call`, however since the standalone engine cannot schedule promises in a
@@ -21,16 +27,7 @@ impl<'a> PromiseHandler for NoScheduler<'a> {
     }
 
     fn promise_result(&self, index: u64) -> Option<PromiseResult> {
-        let i = usize::try_from(index).ok()?;
-        let result = self
-            .promise_data
-            .get(i)?
-            .as_ref()
-            .map_or(PromiseResult::Failed, |bytes| {
-                PromiseResult::Successful(bytes.clone())
-            });
-
-        Some(result)
+        promise_result(self.promise_data, index)
     }
 
     unsafe fn promise_create_call(&mut self, _args: &PromiseCreateArgs) -> PromiseId {
@@ -67,3 +64,405 @@ impl<'a> PromiseHandler for NoScheduler<'a> {
         *self
     }
 }
+
+/// Resolves the promises which are not targeting the Engine account when they are executed
+/// by the `LocalScheduler`. Typically, it is a mock of the contracts the Engine interacts with
+/// (bridge prover, NEP-141 tokens, XCC router, etc).
+pub trait ExternalReceiptHandler {
+    /// Executes a function call on another account. `promise_data` contains the results of
+    /// the promises the call is a callback of.
+    fn function_call(
+        &mut self,
+        args: &PromiseCreateArgs,
+        promise_data: &[Option<Vec<u8>>],
+    ) -> PromiseResult;
+
+    /// Executes a batch of actions on another account. By default, the batch succeeds without
+    /// returning a value.
+    fn batch(
+        &mut self,
+        _args: &PromiseBatchAction,
+        _promise_data: &[Option<Vec<u8>>],
+    ) -> PromiseResult {
+        PromiseResult::Successful(Vec::new())
+    }
+}
+
+impl<F> ExternalReceiptHandler for F
+where
+    F: FnMut(&PromiseCreateArgs, &[Option<Vec<u8>>]) -> PromiseResult,
+{
+    fn function_call(
+        &mut self,
+        args: &PromiseCreateArgs,
+        promise_data: &[Option<Vec<u8>>],
+    ) -> PromiseResult {
+        self(args, promise_data)
+    }
+}
+
+/// A promise recorded by the `LocalScheduler`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledPromise {
+    FunctionCall {
+        args: PromiseCreateArgs,
+        /// The promise this one is a callback of.
+        base: Option<PromiseId>,
+    },
+    Batch {
+        args: PromiseBatchAction,
+        /// The promise this one is a callback of.
+        base: Option<PromiseId>,
+    },
+    /// Promises combined with `promise_create_and_combine`.
+    And(Vec<PromiseId>),
+}
+
+impl ScheduledPromise {
+    const fn base(&self) -> Option<PromiseId> {
+        match self {
+            Self::FunctionCall { base, .. } | Self::Batch { base, .. } => *base,
+            Self::And(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PromiseState {
+    Pending,
+    /// The receipt returned another promise (`promise_return`), its result is the result of
+    /// that promise.
+    Forwarded(PromiseId),
+    Done(Option<Vec<u8>>),
+}
+
+/// Implements `PromiseHandler` by queueing the promises instead of discarding them. The queued
+/// promises are executed in the standalone engine by `LocalScheduler::execute`: the promises
+/// targeting the Engine account are parsed into `TransactionKind`s (e.g. callbacks) and executed
+/// as follow-up transactions, the other promises are resolved by an `ExternalReceiptHandler`.
+/// The results of the promises are passed to their callbacks as promise data. This makes it
+/// possible to test flows like `exit_to_near`, cross-contract calls or `ft_transfer_call` refunds
+/// end to end without a NEAR node.
+///
+/// Batches are always resolved by the `ExternalReceiptHandler` because the Engine does not
+/// send batches to itself.
+#[derive(Debug, Default, Clone)]
+pub struct LocalScheduler {
+    /// Results of the promises available to the receipt being executed.
+    promise_data: Vec<Option<Vec<u8>>>,
+    promises: Vec<ScheduledPromise>,
+    states: Vec<PromiseState>,
+    returned: Option<PromiseId>,
+}
+
+impl LocalScheduler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All the promises created so far, indexed by their `PromiseId`.
+    #[must_use]
+    pub fn promises(&self) -> &[ScheduledPromise] {
+        &self.promises
+    }
+
+    /// The result of the promise, `PromiseResult::NotReady` if the promise has not been
+    /// executed yet.
+    #[must_use]
+    pub fn result_of(&self, id: PromiseId) -> PromiseResult {
+        let Some(index) = usize::try_from(id.raw()).ok() else {
+            return PromiseResult::NotReady;
+        };
+        match (self.promises.get(index), self.states.get(index)) {
+            // The result of a joint promise is not observable, only its readiness matters.
+            (Some(ScheduledPromise::And(ids)), _) => {
+                if ids
+                    .iter()
+                    .any(|id| self.result_of(*id) == PromiseResult::NotReady)
+                {
+                    PromiseResult::NotReady
+                } else {
+                    PromiseResult::Failed
+                }
+            }
+            (_, Some(PromiseState::Forwarded(id))) => self.result_of(*id),
+            (_, Some(PromiseState::Done(Some(bytes)))) => PromiseResult::Successful(bytes.clone()),
+            (_, Some(PromiseState::Done(None))) => PromiseResult::Failed,
+            (_, Some(PromiseState::Pending) | None) => PromiseResult::NotReady,
+        }
+    }
+
+    /// Executes the transaction and then all the promises it created (including the promises
+    /// created by the follow-up transactions) until the queue is empty. Every executed
+    /// transaction is committed to the storage, the follow-up transactions are included in the
+    /// same block as `message` at the subsequent positions. Returns the outcomes of the
+    /// transactions executed by the Engine, in execution order.
+    pub fn execute<M: ModExpAlgorithm + 'static, X: ExternalReceiptHandler>(
+        &mut self,
+        storage: &mut Storage,
+        message: TransactionMessage,
+        external: &mut X,
+    ) -> Result<Vec<TransactionIncludedOutcome>, Error> {
+        let engine_account_id = storage.get_engine_account_id()?;
+        let mut position = message.position;
+        let template = message.clone();
+        let mut outcomes = Vec::new();
+
+        let (outcome, _) = self.execute_receipt::<M>(storage, message)?;
+        outcomes.push(outcome);
+
+        while let Some((index, promise_data)) = self.next_ready() {
+            let promise = self.promises[index].clone();
+            let state = match &promise {
+                ScheduledPromise::FunctionCall { args, .. }
+                    if args.target_account_id == engine_account_id =>
+                {
+                    let Ok(transaction) = sync::parse_transaction_kind(
+                        &args.method,
+                        args.args.clone(),
+                        &promise_data,
+                    ) else {
+                        // The Engine doesn't have such a method, so the receipt fails.
+                        self.states[index] = PromiseState::Done(None);
+                        continue;
+                    };
+                    position = position.saturating_add(1);
+                    let receipt_id = follow_up_receipt_id(template.near_receipt_id, index);
+                    let message = TransactionMessage {
+                        block_hash: template.block_hash,
+                        near_receipt_id: receipt_id,
+                        position,
+                        succeeded: true,
+                        signer: template.signer.clone(),
+                        // The promises are created by the Engine.
+                        caller: engine_account_id.clone(),
+                        attached_near: args.attached_balance.as_u128(),
+                        transaction,
+                        promise_data,
+                        raw_input: args.args.clone(),
+                        action_hash: receipt_id,
+                    };
+                    let (outcome, state) = self.execute_receipt::<M>(storage, message)?;
+                    outcomes.push(outcome);
+                    state
+                }
+                ScheduledPromise::FunctionCall { args, .. } => {
+                    to_state(external.function_call(args, &promise_data))
+                }
+                ScheduledPromise::Batch { args, .. } => {
+                    to_state(external.batch(args, &promise_data))
+                }
+                ScheduledPromise::And(_) => unreachable!("Joint promises are never executed"),
+            };
+            self.states[index] = state;
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Executes a transaction with the promises it creates recorded by the scheduler and commits
+    /// it to the storage. The promises created by a failed transaction are discarded,
+    /// like on NEAR.
+    fn execute_receipt<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        storage: &mut Storage,
+        message: TransactionMessage,
+    ) -> Result<(TransactionIncludedOutcome, PromiseState), Error> {
+        let block_height = storage.get_block_height_by_hash(message.block_hash)?;
+        let block_metadata = storage.get_block_metadata(message.block_hash)?;
+        let engine_account_id = storage.get_engine_account_id()?;
+
+        self.promise_data.clone_from(&message.promise_data);
+        self.returned = None;
+        let first_new_promise = self.promises.len();
+
+        let access =
+            storage.with_engine_access(block_height, message.position, &message.raw_input, |io| {
+                sync::execute_transaction_with_handler::<_, M, _, _>(
+                    &message,
+                    block_height,
+                    &block_metadata,
+                    engine_account_id,
+                    io,
+                    self,
+                    EngineStateAccess::get_transaction_diff,
+                )
+            });
+        let (hash, diff, maybe_result) = access.result;
+        let outcome = TransactionIncludedOutcome {
+            hash,
+            info: message,
+            diff,
+            output: access.engine_output,
+            logs: access.logs,
+            maybe_result,
+        };
+        outcome.commit(storage)?;
+
+        let state = match (&outcome.maybe_result, self.returned.take()) {
+            (Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_)))), _) => {
+                self.promises.truncate(first_new_promise);
+                self.states.truncate(first_new_promise);
+                PromiseState::Done(None)
+            }
+            (Ok(_), Some(id)) => PromiseState::Forwarded(id),
+            (Ok(_), None) => PromiseState::Done(Some(access.engine_output)),
+        };
+
+        Ok((outcome, state))
+    }
+
+    /// Returns the first pending promise whose base promise (if any) is resolved, together
+    /// with the promise data passed to it.
+    fn next_ready(&self) -> Option<(usize, Vec<Option<Vec<u8>>>)> {
+        self.states
+            .iter()
+            .zip(self.promises.iter())
+            .enumerate()
+            .filter(|(_, (state, promise))| {
+                **state == PromiseState::Pending && !matches!(promise, ScheduledPromise::And(_))
+            })
+            .find_map(|(index, (_, promise))| {
+                let promise_data = match promise.base() {
+                    None => Vec::new(),
+                    Some(base) => self.promise_data_of(base)?,
+                };
+                Some((index, promise_data))
+            })
+    }
+
+    /// The promise data of a callback attached to the given promise. `None` if the promise
+    /// is not resolved yet.
+    fn promise_data_of(&self, base: PromiseId) -> Option<Vec<Option<Vec<u8>>>> {
+        let index = usize::try_from(base.raw()).ok()?;
+        let ids = match self.promises.get(index)? {
+            ScheduledPromise::And(ids) => ids.clone(),
+            _ => vec![base],
+        };
+        ids.into_iter()
+            .map(|id| match self.result_of(id) {
+                PromiseResult::NotReady => None,
+                PromiseResult::Successful(bytes) => Some(Some(bytes)),
+                PromiseResult::Failed => Some(None),
+            })
+            .collect()
+    }
+
+    fn push(&mut self, promise: ScheduledPromise) -> PromiseId {
+        let id = PromiseId::new(self.promises.len() as u64);
+        self.promises.push(promise);
+        self.states.push(PromiseState::Pending);
+        id
+    }
+}
+
+impl PromiseHandler for LocalScheduler {
+    type ReadOnly = LocalPromiseResults;
+
+    fn promise_results_count(&self) -> u64 {
+        u64::try_from(self.promise_data.len()).unwrap_or_default()
+    }
+
+    fn promise_result(&self, index: u64) -> Option<PromiseResult> {
+        promise_result(&self.promise_data, index)
+    }
+
+    unsafe fn promise_create_call(&mut self, args: &PromiseCreateArgs) -> PromiseId {
+        self.push(ScheduledPromise::FunctionCall {
+            args: args.clone(),
+            base: None,
+        })
+    }
+
+    unsafe fn promise_create_and_combine(&mut self, args: &[PromiseCreateArgs]) -> PromiseId {
+        let ids = args
+            .iter()
+            .map(|args| self.promise_create_call(args))
+            .collect();
+        self.push(ScheduledPromise::And(ids))
+    }
+
+    unsafe fn promise_attach_callback(
+        &mut self,
+        base: PromiseId,
+        callback: &PromiseCreateArgs,
+    ) -> PromiseId {
+        self.push(ScheduledPromise::FunctionCall {
+            args: callback.clone(),
+            base: Some(base),
+        })
+    }
+
+    unsafe fn promise_create_batch(&mut self, args: &PromiseBatchAction) -> PromiseId {
+        self.push(ScheduledPromise::Batch {
+            args: args.clone(),
+            base: None,
+        })
+    }
+
+    unsafe fn promise_attach_batch_callback(
+        &mut self,
+        base: PromiseId,
+        args: &PromiseBatchAction,
+    ) -> PromiseId {
+        self.push(ScheduledPromise::Batch {
+            args: args.clone(),
+            base: Some(base),
+        })
+    }
+
+    fn promise_return(&mut self, promise: PromiseId) {
+        self.returned = Some(promise);
+    }
+
+    fn read_only(&self) -> Self::ReadOnly {
+        LocalPromiseResults {
+            promise_data: self.promise_data.clone(),
+        }
+    }
+}
+
+/// Read-only view of the promise results available to the receipt executed by
+/// the `LocalScheduler`.
+#[derive(Debug, Clone)]
+pub struct LocalPromiseResults {
+    promise_data: Vec<Option<Vec<u8>>>,
+}
+
+impl ReadOnlyPromiseHandler for LocalPromiseResults {
+    fn ro_promise_results_count(&self) -> u64 {
+        u64::try_from(self.promise_data.len()).unwrap_or_default()
+    }
+
+    fn ro_promise_result(&self, index: u64) -> Option<PromiseResult> {
+        promise_result(&self.promise_data, index)
+    }
+}
+
+fn promise_result(promise_data: &[Option<Vec<u8>>], index: u64) -> Option<PromiseResult> {
+    let i = usize::try_from(index).ok()?;
+    let result = promise_data
+        .get(i)?
+        .as_ref()
+        .map_or(PromiseResult::Failed, |bytes| {
+            PromiseResult::Successful(bytes.clone())
+        });
+
+    Some(result)
+}
+
+fn to_state(result: PromiseResult) -> PromiseState {
+    match result {
+        PromiseResult::Successful(bytes) => PromiseState::Done(Some(bytes)),
+        PromiseResult::Failed | PromiseResult::NotReady => PromiseState::Done(None),
+    }
+}
+
+/// Receipt ids of the follow-up transactions are derived from the receipt id of the
+/// original transaction and the index of the promise.
+fn follow_up_receipt_id(receipt_id: H256, index: usize) -> H256 {
+    let mut bytes = receipt_id.as_bytes().to_vec();
+    bytes.extend_from_slice(&index.to_le_bytes());
+    aurora_engine_sdk::sha256(&bytes)
+}
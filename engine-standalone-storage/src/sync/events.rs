@@ -0,0 +1,14 @@
+//! Typed counterparts of the NEP-297 events emitted by the engine on admin and configuration
+//! changes. The events are parsed from the `EVENT_JSON:` logs the engine emitted while the
+//! standalone engine executed the transaction.
+
+use aurora_engine_types::events::{AdminEvent, EventLog};
+
+/// Returns the events contained in the logs of the transaction in the order of emission.
+#[must_use]
+pub fn admin_events(logs: &[String]) -> Vec<AdminEvent> {
+    logs.iter()
+        .filter_map(|log| EventLog::parse(log))
+        .map(|log| log.event)
+        .collect()
+}
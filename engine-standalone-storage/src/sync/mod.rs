@@ -8,17 +8,23 @@ use aurora_engine_modexp::ModExpAlgorithm;
 use aurora_engine_sdk::{
     env::{self, DEFAULT_PREPAID_GAS},
     io::IO,
+    promise::PromiseHandler,
 };
 use aurora_engine_transactions::EthTransactionKind;
 use aurora_engine_types::{
     account_id::AccountId,
     borsh::BorshDeserialize,
-    parameters::{silo as silo_params, xcc, PromiseWithCallbackArgs},
+    events::AdminEvent,
+    parameters::{
+        governance, mt, nft, roles, silo as silo_params, withdrawal_limits, xcc,
+        PromiseWithCallbackArgs,
+    },
     types::Address,
     H256,
 };
 use std::{io, str::FromStr};
 
+pub mod events;
 pub mod types;
 
 use crate::{error::ParseTransactionKindError, BlockMetadata, Diff, Storage};
@@ -268,6 +274,105 @@ pub fn parse_transaction_kind(
             let args = parameters::MirrorErc20TokenArgs::try_from_slice(&bytes).map_err(f)?;
             TransactionKind::MirrorErc20TokenCallback(args)
         }
+        TransactionKindTag::GrantRole => {
+            let args = roles::RoleArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::GrantRole(args)
+        }
+        TransactionKindTag::RevokeRole => {
+            let args = roles::RoleArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::RevokeRole(args)
+        }
+        TransactionKindTag::SetGovernanceConfig => {
+            let args = governance::GovernanceConfig::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SetGovernanceConfig(args)
+        }
+        TransactionKindTag::CreateProposal => {
+            let args = governance::ProposalAction::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::CreateProposal(args)
+        }
+        TransactionKindTag::ApproveProposal => {
+            let args = governance::ProposalIdArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::ApproveProposal(args)
+        }
+        TransactionKindTag::ExecuteProposal => {
+            let args = governance::ProposalIdArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::ExecuteProposal(args)
+        }
+        TransactionKindTag::CancelProposal => {
+            let args = governance::ProposalIdArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::CancelProposal(args)
+        }
+        TransactionKindTag::XccEvmCallback => {
+            let args = xcc::XccEvmCallbackArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::XccEvmCallback(args)
+        }
+        TransactionKindTag::FactoryRemoveAddressVersion => {
+            let address = Address::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::FactoryRemoveAddressVersion(address)
+        }
+        TransactionKindTag::NftOnTransfer => {
+            let args: nft::NEP171NftOnTransferArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::NftOnTransfer(args)
+        }
+        TransactionKindTag::SetErc721Template => TransactionKind::SetErc721Template(bytes),
+        TransactionKindTag::SyncErc721Metadata => {
+            let args = nft::SyncErc721MetadataArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SyncErc721Metadata(args)
+        }
+        TransactionKindTag::Erc721MetadataCallback => {
+            let args = nft::SyncErc721MetadataArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::Erc721MetadataCallback(args)
+        }
+        TransactionKindTag::MtOnTransfer => {
+            let args: mt::NEP245MtOnTransferArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::MtOnTransfer(args)
+        }
+        TransactionKindTag::SetErc1155Template => TransactionKind::SetErc1155Template(bytes),
+        TransactionKindTag::MtExitCallback => {
+            let args = mt::MtExitCallbackArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::MtExitCallback(args)
+        }
+        TransactionKindTag::SetWithdrawalLimit => {
+            let args =
+                withdrawal_limits::SetWithdrawalLimitArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SetWithdrawalLimit(args)
+        }
+        TransactionKindTag::ApproveQueuedWithdrawal => {
+            let id = u64::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::ApproveQueuedWithdrawal(id)
+        }
+        TransactionKindTag::RejectQueuedWithdrawal => {
+            let id = u64::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::RejectQueuedWithdrawal(id)
+        }
+        TransactionKindTag::DeployErc20Tokens => {
+            let args = parameters::DeployErc20TokensArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::DeployErc20Tokens(args)
+        }
+        TransactionKindTag::DeployErc20TokensCallback => {
+            let args =
+                Vec::<parameters::DeployErc20TokenResult>::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::DeployErc20TokensCallback(args)
+        }
+        TransactionKindTag::SetCoinbaseMode => {
+            let args = parameters::SetCoinbaseModeArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SetCoinbaseMode(args)
+        }
+        TransactionKindTag::SetBlockGasLimit => {
+            let args = parameters::SetBlockGasLimitArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SetBlockGasLimit(args)
+        }
+        TransactionKindTag::CancelStagedUpgrade => TransactionKind::CancelStagedUpgrade,
+        TransactionKindTag::CommitUpgradeCodeHash => {
+            let args = parameters::CommitUpgradeCodeHashArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::CommitUpgradeCodeHash(args)
+        }
         TransactionKindTag::Unknown => {
             return Err(ParseTransactionKindError::UnknownMethodName {
                 name: method_name.into(),
@@ -307,27 +412,28 @@ pub fn consume_message<M: ModExpAlgorithm + 'static>(
             let block_metadata = storage.get_block_metadata(block_hash)?;
             let engine_account_id = storage.get_engine_account_id()?;
 
-            let (tx_hash, diff, result) = storage
-                .with_engine_access(
-                    block_height,
-                    transaction_position,
-                    &transaction_message.raw_input,
-                    |io| {
-                        execute_transaction::<_, M, _>(
-                            transaction_message.as_ref(),
-                            block_height,
-                            &block_metadata,
-                            engine_account_id,
-                            io,
-                            EngineStateAccess::get_transaction_diff,
-                        )
-                    },
-                )
-                .result;
+            let access = storage.with_engine_access(
+                block_height,
+                transaction_position,
+                &transaction_message.raw_input,
+                |io| {
+                    execute_transaction::<_, M, _>(
+                        transaction_message.as_ref(),
+                        block_height,
+                        &block_metadata,
+                        engine_account_id,
+                        io,
+                        EngineStateAccess::get_transaction_diff,
+                    )
+                },
+            );
+            let (tx_hash, diff, result) = access.result;
             let outcome = TransactionIncludedOutcome {
                 hash: tx_hash,
                 info: *transaction_message,
                 diff,
+                output: access.engine_output,
+                logs: access.logs,
                 maybe_result: result,
             };
             Ok(ConsumeMessageOutcome::TransactionIncluded(Box::new(
@@ -366,6 +472,8 @@ pub fn execute_transaction_message<M: ModExpAlgorithm + 'static>(
         hash: tx_hash,
         info: transaction_message,
         diff,
+        output: result.engine_output,
+        logs: result.logs,
         maybe_result,
     };
     Ok(outcome)
@@ -387,6 +495,44 @@ where
     I: IO + Copy,
     M: ModExpAlgorithm + 'static,
     F: FnOnce(&I) -> Diff,
+{
+    // We can ignore promises in the standalone engine because it processes each receipt separately
+    // and it is fed a stream of receipts (it does not schedule them)
+    let mut handler = crate::promise::NoScheduler {
+        promise_data: &transaction_message.promise_data,
+    };
+    execute_transaction_with_handler::<_, M, _, _>(
+        transaction_message,
+        block_height,
+        block_metadata,
+        engine_account_id,
+        io,
+        &mut handler,
+        get_diff,
+    )
+}
+
+/// Same as `execute_transaction`, but the promises created by the transaction are passed
+/// to the given handler (see `crate::promise::LocalScheduler`). The handler also provides
+/// the promise results available to the transaction.
+pub fn execute_transaction_with_handler<I, M, F, H>(
+    transaction_message: &TransactionMessage,
+    block_height: u64,
+    block_metadata: &BlockMetadata,
+    engine_account_id: AccountId,
+    io: I,
+    handler: &mut H,
+    get_diff: F,
+) -> (
+    H256,
+    Diff,
+    Result<Option<TransactionExecutionResult>, error::Error>,
+)
+where
+    I: IO + Copy,
+    M: ModExpAlgorithm + 'static,
+    F: FnOnce(&I) -> Diff,
+    H: PromiseHandler,
 {
     let signer_account_id = transaction_message.signer.clone();
     let predecessor_account_id = transaction_message.caller.clone();
@@ -409,35 +555,24 @@ where
 
     let (tx_hash, result) = match &transaction_message.transaction {
         TransactionKind::Submit(tx) => {
-            // We can ignore promises in the standalone engine because it processes each receipt separately
-            // and it is fed a stream of receipts (it does not schedule them)
-            let mut handler = crate::promise::NoScheduler {
-                promise_data: &transaction_message.promise_data,
-            };
             let tx_data: Vec<u8> = tx.into();
             let tx_hash = aurora_engine_sdk::keccak(&tx_data);
-            let result = contract_methods::evm_transactions::submit(io, &env, &mut handler)
+            let result = contract_methods::evm_transactions::submit(io, &env, handler)
                 .map(|submit_result| Some(TransactionExecutionResult::Submit(Ok(submit_result))))
                 .map_err(Into::into);
 
             (tx_hash, result)
         }
         TransactionKind::SubmitWithArgs(args) => {
-            let mut handler = crate::promise::NoScheduler {
-                promise_data: &transaction_message.promise_data,
-            };
             let tx_hash = aurora_engine_sdk::keccak(&args.tx_data);
-            let result =
-                contract_methods::evm_transactions::submit_with_args(io, &env, &mut handler)
-                    .map(|submit_result| {
-                        Some(TransactionExecutionResult::Submit(Ok(submit_result)))
-                    })
-                    .map_err(Into::into);
+            let result = contract_methods::evm_transactions::submit_with_args(io, &env, handler)
+                .map(|submit_result| Some(TransactionExecutionResult::Submit(Ok(submit_result))))
+                .map_err(Into::into);
 
             (tx_hash, result)
         }
         other => {
-            let result = non_submit_execute(other, io, &env, &transaction_message.promise_data);
+            let result = non_submit_execute(other, io, &env, handler);
             (near_receipt_id, result)
         }
     };
@@ -465,39 +600,33 @@ fn compute_random_seed(action_hash: &H256, block_random_value: &H256) -> H256 {
     clippy::match_same_arms,
     clippy::cognitive_complexity
 )]
-fn non_submit_execute<I: IO + Copy>(
+fn non_submit_execute<I: IO + Copy, H: PromiseHandler>(
     transaction: &TransactionKind,
     mut io: I,
     env: &env::Fixed,
-    promise_data: &[Option<Vec<u8>>],
+    handler: &mut H,
 ) -> Result<Option<TransactionExecutionResult>, error::Error> {
     let result = match transaction {
         TransactionKind::Call(_) => {
-            // We can ignore promises in the standalone engine (see above)
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::evm_transactions::call(io, env, &mut handler)?;
+            let result = contract_methods::evm_transactions::call(io, env, handler)?;
 
             Some(TransactionExecutionResult::Submit(Ok(result)))
         }
 
         TransactionKind::Deploy(_) => {
-            // We can ignore promises in the standalone engine (see above)
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::evm_transactions::deploy_code(io, env, &mut handler)?;
+            let result = contract_methods::evm_transactions::deploy_code(io, env, handler)?;
 
             Some(TransactionExecutionResult::Submit(Ok(result)))
         }
         TransactionKind::DeployErc20(_) => {
             // No promises can be created by `deploy_erc20_token`
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::connector::deploy_erc20_token(io, env, &mut handler)?;
+            let result = contract_methods::connector::deploy_erc20_token(io, env, handler)?;
 
             Some(TransactionExecutionResult::DeployErc20(result))
         }
         TransactionKind::FtOnTransfer(_) => {
             // No promises can be created by `ft_on_transfer`
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let maybe_output = contract_methods::connector::ft_on_transfer(io, env, &mut handler)?;
+            let maybe_output = contract_methods::connector::ft_on_transfer(io, env, handler)?;
 
             maybe_output.map(|result| TransactionExecutionResult::Submit(Ok(result)))
         }
@@ -507,9 +636,8 @@ fn non_submit_execute<I: IO + Copy>(
 
             #[cfg(not(feature = "ext-connector"))]
             {
-                let mut handler = crate::promise::NoScheduler { promise_data };
                 let maybe_promise_args =
-                    contract_methods::connector::ft_transfer_call(io, env, &mut handler)?;
+                    contract_methods::connector::ft_transfer_call(io, env, handler)?;
 
                 maybe_promise_args.map(TransactionExecutionResult::Promise)
             }
@@ -517,8 +645,7 @@ fn non_submit_execute<I: IO + Copy>(
         TransactionKind::ResolveTransfer(_, _) => {
             #[cfg(not(feature = "ext-connector"))]
             {
-                let handler = crate::promise::NoScheduler { promise_data };
-                contract_methods::connector::ft_resolve_transfer(io, env, &handler)?;
+                contract_methods::connector::ft_resolve_transfer(io, env, &*handler)?;
             }
 
             None
@@ -541,9 +668,7 @@ fn non_submit_execute<I: IO + Copy>(
 
             #[cfg(not(feature = "ext-connector"))]
             {
-                let mut handler = crate::promise::NoScheduler { promise_data };
-                let maybe_promise_args =
-                    contract_methods::connector::deposit(io, env, &mut handler)?;
+                let maybe_promise_args = contract_methods::connector::deposit(io, env, handler)?;
                 maybe_promise_args.map(TransactionExecutionResult::Promise)
             }
         }
@@ -554,9 +679,8 @@ fn non_submit_execute<I: IO + Copy>(
 
             #[cfg(not(feature = "ext-connector"))]
             {
-                let mut handler = crate::promise::NoScheduler { promise_data };
                 let maybe_promise_args =
-                    contract_methods::connector::finish_deposit(io, env, &mut handler)?;
+                    contract_methods::connector::finish_deposit(io, env, handler)?;
 
                 maybe_promise_args.map(TransactionExecutionResult::Promise)
             }
@@ -565,8 +689,7 @@ fn non_submit_execute<I: IO + Copy>(
         TransactionKind::StorageDeposit(_) => {
             #[cfg(not(feature = "ext-connector"))]
             {
-                let mut handler = crate::promise::NoScheduler { promise_data };
-                contract_methods::connector::storage_deposit(io, env, &mut handler)?;
+                contract_methods::connector::storage_deposit(io, env, handler)?;
             }
 
             None
@@ -574,8 +697,7 @@ fn non_submit_execute<I: IO + Copy>(
         TransactionKind::StorageUnregister(_) => {
             #[cfg(not(feature = "ext-connector"))]
             {
-                let mut handler = crate::promise::NoScheduler { promise_data };
-                contract_methods::connector::storage_unregister(io, env, &mut handler)?;
+                contract_methods::connector::storage_unregister(io, env, handler)?;
             }
 
             None
@@ -598,12 +720,8 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::ExitToNear(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let maybe_result = contract_methods::connector::exit_to_near_precompile_callback(
-                io,
-                env,
-                &mut handler,
-            )?;
+            let maybe_result =
+                contract_methods::connector::exit_to_near_precompile_callback(io, env, handler)?;
 
             maybe_result.map(|submit_result| TransactionExecutionResult::Submit(Ok(submit_result)))
         }
@@ -636,8 +754,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::FactoryUpdateAddressVersion(_) => {
-            let handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::xcc::factory_update_address_version(io, env, &handler)?;
+            contract_methods::xcc::factory_update_address_version(io, env, &*handler)?;
 
             None
         }
@@ -647,17 +764,141 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::FundXccSubAccount(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::xcc::fund_xcc_sub_account(io, env, &mut handler)?;
+            contract_methods::xcc::fund_xcc_sub_account(io, env, handler)?;
 
             None
         }
         TransactionKind::WithdrawWnearToRouter(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::xcc::withdraw_wnear_to_router(io, env, &mut handler)?;
+            let result = contract_methods::xcc::withdraw_wnear_to_router(io, env, handler)?;
+
+            Some(TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::GrantRole(_) => {
+            contract_methods::roles::grant_role(io, env)?;
+
+            None
+        }
+        TransactionKind::RevokeRole(_) => {
+            contract_methods::roles::revoke_role(io, env)?;
+
+            None
+        }
+        TransactionKind::SetGovernanceConfig(_) => {
+            contract_methods::governance::set_governance_config(io, env)?;
+
+            None
+        }
+        TransactionKind::CreateProposal(_) => {
+            contract_methods::governance::create_proposal(io, env)?;
+
+            None
+        }
+        TransactionKind::ApproveProposal(_) => {
+            contract_methods::governance::approve_proposal(io, env)?;
+
+            None
+        }
+        TransactionKind::ExecuteProposal(_) => {
+            contract_methods::governance::execute_proposal(io, env)?;
+
+            None
+        }
+        TransactionKind::CancelProposal(_) => {
+            contract_methods::governance::cancel_proposal(io, env)?;
+
+            None
+        }
+        TransactionKind::XccEvmCallback(_) => {
+            let result = contract_methods::xcc::xcc_evm_callback(io, env, handler)?;
 
             Some(TransactionExecutionResult::Submit(Ok(result)))
         }
+        TransactionKind::FactoryRemoveAddressVersion(_) => {
+            contract_methods::xcc::factory_remove_address_version(io, env, &*handler)?;
+
+            None
+        }
+        TransactionKind::NftOnTransfer(_) => {
+            let maybe_output = contract_methods::nft::nft_on_transfer(io, env, handler)?;
+
+            maybe_output.map(|result| TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::SetErc721Template(_) => {
+            contract_methods::nft::set_erc721_template(io, env)?;
+
+            None
+        }
+        TransactionKind::SyncErc721Metadata(_) => {
+            contract_methods::nft::sync_erc721_metadata(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::Erc721MetadataCallback(_) => {
+            let result = contract_methods::nft::erc721_metadata_callback(io, env, handler)?;
+
+            Some(TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::MtOnTransfer(_) => {
+            let maybe_output = contract_methods::mt::mt_on_transfer(io, env, handler)?;
+
+            maybe_output.map(|result| TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::SetErc1155Template(_) => {
+            contract_methods::mt::set_erc1155_template(io, env)?;
+
+            None
+        }
+        TransactionKind::MtExitCallback(_) => {
+            let maybe_output = contract_methods::mt::mt_exit_callback(io, env, handler)?;
+
+            maybe_output.map(|result| TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::SetWithdrawalLimit(_) => {
+            contract_methods::withdrawal_limits::set_withdrawal_limit(io, env)?;
+
+            None
+        }
+        TransactionKind::ApproveQueuedWithdrawal(_) => {
+            contract_methods::withdrawal_limits::approve_queued_withdrawal(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::RejectQueuedWithdrawal(_) => {
+            let result =
+                contract_methods::withdrawal_limits::reject_queued_withdrawal(io, env, handler)?;
+
+            Some(TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::DeployErc20Tokens(_) => {
+            contract_methods::connector::deploy_erc20_tokens(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::DeployErc20TokensCallback(_) => {
+            contract_methods::connector::deploy_erc20_tokens_callback(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::SetCoinbaseMode(_) => {
+            contract_methods::admin::set_coinbase_mode(io, env)?;
+
+            None
+        }
+        TransactionKind::SetBlockGasLimit(_) => {
+            contract_methods::admin::set_block_gas_limit(io, env)?;
+
+            None
+        }
+        TransactionKind::CancelStagedUpgrade => {
+            contract_methods::admin::cancel_staged_upgrade(io, env)?;
+
+            None
+        }
+        TransactionKind::CommitUpgradeCodeHash(_) => {
+            contract_methods::admin::commit_upgrade_code_hash(io, env)?;
+
+            None
+        }
         TransactionKind::Unknown => None,
         // Not handled in this function; is handled by the general `execute_transaction` function
         TransactionKind::Submit(_) | TransactionKind::SubmitWithArgs(_) => unreachable!(),
@@ -697,8 +938,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::AddRelayerKey(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::admin::add_relayer_key(io, env, &mut handler)?;
+            contract_methods::admin::add_relayer_key(io, env, handler)?;
 
             None
         }
@@ -708,8 +948,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::RemoveRelayerKey(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::admin::remove_relayer_key(io, env, &mut handler)?;
+            contract_methods::admin::remove_relayer_key(io, env, handler)?;
 
             None
         }
@@ -719,8 +958,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::SetErc20Metadata(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::set_erc20_metadata(io, env, &mut handler)?;
+            contract_methods::connector::set_erc20_metadata(io, env, handler)?;
 
             None
         }
@@ -749,8 +987,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::MirrorErc20TokenCallback(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::mirror_erc20_token_callback(io, env, &mut handler)?;
+            contract_methods::connector::mirror_erc20_token_callback(io, env, handler)?;
 
             None
         }
@@ -780,6 +1017,10 @@ pub struct TransactionIncludedOutcome {
     pub hash: H256,
     pub info: TransactionMessage,
     pub diff: Diff,
+    /// The output returned by the engine.
+    pub output: Vec<u8>,
+    /// The logs emitted by the engine, including the NEP-297 events.
+    pub logs: Vec<String>,
     pub maybe_result: Result<Option<TransactionExecutionResult>, error::Error>,
 }
 
@@ -787,10 +1028,23 @@ impl TransactionIncludedOutcome {
     pub fn commit(&self, storage: &mut Storage) -> Result<(), crate::error::Error> {
         match self.maybe_result.as_ref() {
             Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_)))) => (), // do not persist if Engine encounters an error
-            _ => storage.set_transaction_included(self.hash, &self.info, &self.diff)?,
+            _ => {
+                storage.set_transaction_included(self.hash, &self.info, &self.diff)?;
+                storage.add_hashchain_tx(self)?;
+            }
         };
         Ok(())
     }
+
+    /// Returns the typed NEP-297 events which were emitted by the transaction on admin and
+    /// configuration changes.
+    #[must_use]
+    pub fn admin_events(&self) -> Vec<AdminEvent> {
+        match self.maybe_result.as_ref() {
+            Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_)))) => Vec::new(),
+            _ => events::admin_events(&self.logs),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -2,10 +2,15 @@ use crate::Storage;
 use aurora_engine::contract_methods::connector::deposit_event;
 use aurora_engine::parameters;
 use aurora_engine::xcc::{AddressVersionUpdateArgs, FundXccArgs};
+use aurora_engine_precompiles::xcc::cross_contract_call;
 use aurora_engine_transactions::{EthTransactionKind, NormalizedEthTransaction};
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::parameters::mt::{MtExitCallbackArgs, NEP245MtOnTransferArgs};
+use aurora_engine_types::parameters::nft::{NEP171NftOnTransferArgs, SyncErc721MetadataArgs};
 use aurora_engine_types::parameters::silo;
-use aurora_engine_types::parameters::xcc::WithdrawWnearToRouterArgs;
+use aurora_engine_types::parameters::withdrawal_limits::SetWithdrawalLimitArgs;
+use aurora_engine_types::parameters::xcc::{WithdrawWnearToRouterArgs, XccEvmCallbackArgs};
+use aurora_engine_types::parameters::{governance, roles};
 use aurora_engine_types::types::Address;
 use aurora_engine_types::{
     borsh::{self, BorshDeserialize, BorshSerialize},
@@ -14,7 +19,7 @@ use aurora_engine_types::{
 };
 use serde::Serialize;
 use std::borrow::Cow;
-use strum::EnumString;
+use strum::{AsRefStr, EnumString};
 
 /// Type describing the format of messages sent to the storage layer for keeping
 /// it in sync with the blockchain.
@@ -174,6 +179,56 @@ pub enum TransactionKind {
     SetWhitelistStatus(silo::WhitelistStatusArgs),
     /// Callback which mirrors existed ERC-20 contract deployed on the main contract.
     MirrorErc20TokenCallback(parameters::MirrorErc20TokenArgs),
+    /// Grant a role to an account
+    GrantRole(roles::RoleArgs),
+    /// Revoke a role from an account
+    RevokeRole(roles::RoleArgs),
+    /// Set the initial governance config
+    SetGovernanceConfig(governance::GovernanceConfig),
+    /// Create a governance proposal
+    CreateProposal(governance::ProposalAction),
+    /// Approve a governance proposal
+    ApproveProposal(governance::ProposalIdArgs),
+    /// Execute an approved governance proposal
+    ExecuteProposal(governance::ProposalIdArgs),
+    /// Cancel a governance proposal
+    CancelProposal(governance::ProposalIdArgs),
+    /// Self-call used during XCC flow to deliver the promise results to the EVM contract
+    XccEvmCallback(XccEvmCallbackArgs),
+    /// Self-call used when the XCC router account of the address is closed
+    FactoryRemoveAddressVersion(Address),
+    /// Receive a NEP-171 token and mint it on the corresponding ERC-721
+    NftOnTransfer(NEP171NftOnTransferArgs),
+    /// Update the bytecode of the ERC-721 contract deployed for bridged NEP-171 contracts
+    SetErc721Template(Vec<u8>),
+    /// Request the NEP-171 metadata to update the metadata of the corresponding ERC-721
+    SyncErc721Metadata(SyncErc721MetadataArgs),
+    /// Self-call used to update the metadata of the ERC-721 with the result of `nft_metadata`
+    Erc721MetadataCallback(SyncErc721MetadataArgs),
+    /// Receive NEP-245 tokens and mint them on the corresponding ERC-1155
+    MtOnTransfer(NEP245MtOnTransferArgs),
+    /// Update the bytecode of the ERC-1155 contract deployed for bridged NEP-245 contracts
+    SetErc1155Template(Vec<u8>),
+    /// Self-call used to mint back the ERC-1155 tokens if the withdrawal to NEAR failed
+    MtExitCallback(MtExitCallbackArgs),
+    /// Set or remove a withdrawal limit of the exit precompiles
+    SetWithdrawalLimit(SetWithdrawalLimitArgs),
+    /// Create the transfer of a queued withdrawal
+    ApproveQueuedWithdrawal(u64),
+    /// Drop a queued withdrawal and refund the withdrawn tokens
+    RejectQueuedWithdrawal(u64),
+    /// Deploy the ERC-20 tokens of many NEP-141 tokens
+    DeployErc20Tokens(parameters::DeployErc20TokensArgs),
+    /// Callback setting the metadata of the tokens deployed by `deploy_erc20_tokens`
+    DeployErc20TokensCallback(Vec<parameters::DeployErc20TokenResult>),
+    /// Admin only method; used to select the address returned by `COINBASE`
+    SetCoinbaseMode(parameters::SetCoinbaseModeArgs),
+    /// Admin only method; used to set the limit of the EVM gas used in one block
+    SetBlockGasLimit(parameters::SetBlockGasLimitArgs),
+    /// Admin only method; used to cancel the staged upgrade
+    CancelStagedUpgrade,
+    /// Admin only method; used to commit to the hash of the code of the next staged upgrade
+    CommitUpgradeCodeHash(parameters::CommitUpgradeCodeHashArgs),
     /// Sentinel kind for cases where a NEAR receipt caused a
     /// change in Aurora state, but we failed to parse the Action.
     Unknown,
@@ -405,6 +460,30 @@ impl TransactionKind {
                     storage,
                 )
             }
+            Self::XccEvmCallback(args) => {
+                let from = cross_contract_call::ADDRESS;
+                let nonce =
+                    Self::get_implicit_nonce(&from, block_height, transaction_position, storage);
+                // The promise results aren't a part of the transaction, so the input only
+                // includes the sender of the cross contract call.
+                let data = aurora_engine::xcc::evm_callback_input(
+                    args.callback.selector,
+                    args.sender,
+                    &[],
+                );
+                NormalizedEthTransaction {
+                    address: from,
+                    chain_id: None,
+                    nonce,
+                    gas_limit: U256::from(u64::MAX),
+                    max_priority_fee_per_gas: U256::zero(),
+                    max_fee_per_gas: U256::zero(),
+                    to: Some(args.callback.address),
+                    value: Wei::zero(),
+                    data,
+                    access_list: Vec::new(),
+                }
+            }
             Self::Deposit(_) => Self::no_evm_execution("deposit"),
             Self::FtTransferCall(_) => Self::no_evm_execution("ft_transfer_call"),
             Self::FinishDeposit(_) => Self::no_evm_execution("finish_deposit"),
@@ -427,6 +506,34 @@ impl TransactionKind {
                 Self::no_evm_execution("factory_update_address_version")
             }
             Self::FactorySetWNearAddress(_) => Self::no_evm_execution("factory_set_wnear_address"),
+            Self::GrantRole(_) => Self::no_evm_execution("grant_role"),
+            Self::RevokeRole(_) => Self::no_evm_execution("revoke_role"),
+            Self::SetGovernanceConfig(_) => Self::no_evm_execution("set_governance_config"),
+            Self::CreateProposal(_) => Self::no_evm_execution("create_proposal"),
+            Self::ApproveProposal(_) => Self::no_evm_execution("approve_proposal"),
+            Self::ExecuteProposal(_) => Self::no_evm_execution("execute_proposal"),
+            Self::CancelProposal(_) => Self::no_evm_execution("cancel_proposal"),
+            Self::FactoryRemoveAddressVersion(_) => {
+                Self::no_evm_execution("factory_remove_address_version")
+            }
+            Self::NftOnTransfer(_) => Self::no_evm_execution("nft_on_transfer"),
+            Self::SetErc721Template(_) => Self::no_evm_execution("set_erc721_template"),
+            Self::SyncErc721Metadata(_) => Self::no_evm_execution("sync_erc721_metadata"),
+            Self::Erc721MetadataCallback(_) => Self::no_evm_execution("erc721_metadata_callback"),
+            Self::MtOnTransfer(_) => Self::no_evm_execution("mt_on_transfer"),
+            Self::SetErc1155Template(_) => Self::no_evm_execution("set_erc1155_template"),
+            Self::MtExitCallback(_) => Self::no_evm_execution("mt_exit_callback"),
+            Self::SetWithdrawalLimit(_) => Self::no_evm_execution("set_withdrawal_limit"),
+            Self::ApproveQueuedWithdrawal(_) => Self::no_evm_execution("approve_queued_withdrawal"),
+            Self::RejectQueuedWithdrawal(_) => Self::no_evm_execution("reject_queued_withdrawal"),
+            Self::DeployErc20Tokens(_) => Self::no_evm_execution("deploy_erc20_tokens"),
+            Self::DeployErc20TokensCallback(_) => {
+                Self::no_evm_execution("deploy_erc20_tokens_callback")
+            }
+            Self::SetCoinbaseMode(_) => Self::no_evm_execution("set_coinbase_mode"),
+            Self::SetBlockGasLimit(_) => Self::no_evm_execution("set_block_gas_limit"),
+            Self::CancelStagedUpgrade => Self::no_evm_execution("cancel_staged_upgrade"),
+            Self::CommitUpgradeCodeHash(_) => Self::no_evm_execution("commit_upgrade_code_hash"),
             Self::Unknown => Self::no_evm_execution("unknown"),
             Self::PausePrecompiles(_) => Self::no_evm_execution("pause_precompiles"),
             Self::ResumePrecompiles(_) => Self::no_evm_execution("resume_precompiles"),
@@ -495,7 +602,7 @@ impl TransactionKind {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
 pub enum TransactionKindTag {
     #[strum(serialize = "submit")]
     Submit,
@@ -589,6 +696,56 @@ pub enum TransactionKindTag {
     MirrorErc20TokenCallback,
     #[strum(serialize = "withdraw_wnear_to_router")]
     WithdrawWnearToRouter,
+    #[strum(serialize = "grant_role")]
+    GrantRole,
+    #[strum(serialize = "revoke_role")]
+    RevokeRole,
+    #[strum(serialize = "set_governance_config")]
+    SetGovernanceConfig,
+    #[strum(serialize = "create_proposal")]
+    CreateProposal,
+    #[strum(serialize = "approve_proposal")]
+    ApproveProposal,
+    #[strum(serialize = "execute_proposal")]
+    ExecuteProposal,
+    #[strum(serialize = "cancel_proposal")]
+    CancelProposal,
+    #[strum(serialize = "xcc_evm_callback")]
+    XccEvmCallback,
+    #[strum(serialize = "factory_remove_address_version")]
+    FactoryRemoveAddressVersion,
+    #[strum(serialize = "nft_on_transfer")]
+    NftOnTransfer,
+    #[strum(serialize = "set_erc721_template")]
+    SetErc721Template,
+    #[strum(serialize = "sync_erc721_metadata")]
+    SyncErc721Metadata,
+    #[strum(serialize = "erc721_metadata_callback")]
+    Erc721MetadataCallback,
+    #[strum(serialize = "mt_on_transfer")]
+    MtOnTransfer,
+    #[strum(serialize = "set_erc1155_template")]
+    SetErc1155Template,
+    #[strum(serialize = "mt_exit_callback")]
+    MtExitCallback,
+    #[strum(serialize = "set_withdrawal_limit")]
+    SetWithdrawalLimit,
+    #[strum(serialize = "approve_queued_withdrawal")]
+    ApproveQueuedWithdrawal,
+    #[strum(serialize = "reject_queued_withdrawal")]
+    RejectQueuedWithdrawal,
+    #[strum(serialize = "deploy_erc20_tokens")]
+    DeployErc20Tokens,
+    #[strum(serialize = "deploy_erc20_tokens_callback")]
+    DeployErc20TokensCallback,
+    #[strum(serialize = "set_coinbase_mode")]
+    SetCoinbaseMode,
+    #[strum(serialize = "set_block_gas_limit")]
+    SetBlockGasLimit,
+    #[strum(serialize = "cancel_staged_upgrade")]
+    CancelStagedUpgrade,
+    #[strum(serialize = "commit_upgrade_code_hash")]
+    CommitUpgradeCodeHash,
     Unknown,
 }
 
@@ -616,9 +773,9 @@ impl TransactionKind {
             Self::SetOwner(args) => to_borsh(args),
             Self::SetUpgradeDelayBlocks(args) => to_borsh(args),
             Self::SetPausedFlags(args) => to_borsh(args),
-            Self::RegisterRelayer(address) | Self::FactorySetWNearAddress(address) => {
-                address.as_bytes().to_vec()
-            }
+            Self::RegisterRelayer(address)
+            | Self::FactorySetWNearAddress(address)
+            | Self::FactoryRemoveAddressVersion(address) => address.as_bytes().to_vec(),
             Self::ExitToNear(maybe_args) => maybe_args
                 .as_ref()
                 .and_then(|args| borsh::to_vec(&args).ok())
@@ -628,7 +785,32 @@ impl TransactionKind {
             Self::FactoryUpdateAddressVersion(args) => to_borsh(args),
             Self::FundXccSubAccount(args) => to_borsh(args),
             Self::WithdrawWnearToRouter(args) => to_borsh(args),
-            Self::PauseContract | Self::ResumeContract | Self::Unknown => Vec::new(),
+            Self::GrantRole(args) | Self::RevokeRole(args) => to_borsh(args),
+            Self::SetGovernanceConfig(args) => to_borsh(args),
+            Self::CreateProposal(args) => to_borsh(args),
+            Self::ApproveProposal(args)
+            | Self::ExecuteProposal(args)
+            | Self::CancelProposal(args) => to_borsh(args),
+            Self::XccEvmCallback(args) => to_borsh(args),
+            Self::NftOnTransfer(args) => to_json(args),
+            Self::SetErc721Template(args) => args.clone(),
+            Self::SyncErc721Metadata(args) => to_borsh(args),
+            Self::Erc721MetadataCallback(args) => to_borsh(args),
+            Self::MtOnTransfer(args) => to_json(args),
+            Self::SetErc1155Template(args) => args.clone(),
+            Self::MtExitCallback(args) => to_borsh(args),
+            Self::SetWithdrawalLimit(args) => to_borsh(args),
+            Self::ApproveQueuedWithdrawal(args) => to_borsh(args),
+            Self::RejectQueuedWithdrawal(args) => to_borsh(args),
+            Self::DeployErc20Tokens(args) => to_borsh(args),
+            Self::DeployErc20TokensCallback(args) => to_borsh(args),
+            Self::SetCoinbaseMode(args) => to_borsh(args),
+            Self::SetBlockGasLimit(args) => to_borsh(args),
+            Self::CommitUpgradeCodeHash(args) => to_borsh(args),
+            Self::PauseContract
+            | Self::ResumeContract
+            | Self::CancelStagedUpgrade
+            | Self::Unknown => Vec::new(),
             Self::SetKeyManager(args) => to_borsh(args),
             Self::AddRelayerKey(args)
             | Self::RemoveRelayerKey(args)
@@ -707,6 +889,31 @@ impl From<&TransactionKind> for TransactionKindTag {
             TransactionKind::AddEntryToWhitelistBatch(_) => Self::AddEntryToWhitelistBatch,
             TransactionKind::RemoveEntryFromWhitelist(_) => Self::RemoveEntryFromWhitelist,
             TransactionKind::SetWhitelistStatus(_) => Self::SetWhitelistStatus,
+            TransactionKind::GrantRole(_) => Self::GrantRole,
+            TransactionKind::RevokeRole(_) => Self::RevokeRole,
+            TransactionKind::SetGovernanceConfig(_) => Self::SetGovernanceConfig,
+            TransactionKind::CreateProposal(_) => Self::CreateProposal,
+            TransactionKind::ApproveProposal(_) => Self::ApproveProposal,
+            TransactionKind::ExecuteProposal(_) => Self::ExecuteProposal,
+            TransactionKind::CancelProposal(_) => Self::CancelProposal,
+            TransactionKind::XccEvmCallback(_) => Self::XccEvmCallback,
+            TransactionKind::FactoryRemoveAddressVersion(_) => Self::FactoryRemoveAddressVersion,
+            TransactionKind::NftOnTransfer(_) => Self::NftOnTransfer,
+            TransactionKind::SetErc721Template(_) => Self::SetErc721Template,
+            TransactionKind::SyncErc721Metadata(_) => Self::SyncErc721Metadata,
+            TransactionKind::Erc721MetadataCallback(_) => Self::Erc721MetadataCallback,
+            TransactionKind::MtOnTransfer(_) => Self::MtOnTransfer,
+            TransactionKind::SetErc1155Template(_) => Self::SetErc1155Template,
+            TransactionKind::MtExitCallback(_) => Self::MtExitCallback,
+            TransactionKind::SetWithdrawalLimit(_) => Self::SetWithdrawalLimit,
+            TransactionKind::ApproveQueuedWithdrawal(_) => Self::ApproveQueuedWithdrawal,
+            TransactionKind::RejectQueuedWithdrawal(_) => Self::RejectQueuedWithdrawal,
+            TransactionKind::DeployErc20Tokens(_) => Self::DeployErc20Tokens,
+            TransactionKind::DeployErc20TokensCallback(_) => Self::DeployErc20TokensCallback,
+            TransactionKind::SetCoinbaseMode(_) => Self::SetCoinbaseMode,
+            TransactionKind::SetBlockGasLimit(_) => Self::SetBlockGasLimit,
+            TransactionKind::CancelStagedUpgrade => Self::CancelStagedUpgrade,
+            TransactionKind::CommitUpgradeCodeHash(_) => Self::CommitUpgradeCodeHash,
             TransactionKind::Unknown => Self::Unknown,
             TransactionKind::MirrorErc20TokenCallback(_) => Self::MirrorErc20TokenCallback,
         }
@@ -940,6 +1147,31 @@ enum BorshableTransactionKind<'a> {
     MirrorErc20TokenCallback(Cow<'a, parameters::MirrorErc20TokenArgs>),
     WithdrawWnearToRouter(Cow<'a, WithdrawWnearToRouterArgs>),
     StoreRelayerKeyCallback(Cow<'a, parameters::RelayerKeyArgs>),
+    GrantRole(Cow<'a, roles::RoleArgs>),
+    RevokeRole(Cow<'a, roles::RoleArgs>),
+    SetGovernanceConfig(Cow<'a, governance::GovernanceConfig>),
+    CreateProposal(Cow<'a, governance::ProposalAction>),
+    ApproveProposal(Cow<'a, governance::ProposalIdArgs>),
+    ExecuteProposal(Cow<'a, governance::ProposalIdArgs>),
+    CancelProposal(Cow<'a, governance::ProposalIdArgs>),
+    XccEvmCallback(Cow<'a, XccEvmCallbackArgs>),
+    FactoryRemoveAddressVersion(Address),
+    NftOnTransfer(Cow<'a, NEP171NftOnTransferArgs>),
+    SetErc721Template(Cow<'a, Vec<u8>>),
+    SyncErc721Metadata(Cow<'a, SyncErc721MetadataArgs>),
+    Erc721MetadataCallback(Cow<'a, SyncErc721MetadataArgs>),
+    MtOnTransfer(Cow<'a, NEP245MtOnTransferArgs>),
+    SetErc1155Template(Cow<'a, Vec<u8>>),
+    MtExitCallback(Cow<'a, MtExitCallbackArgs>),
+    SetWithdrawalLimit(Cow<'a, SetWithdrawalLimitArgs>),
+    ApproveQueuedWithdrawal(Cow<'a, u64>),
+    RejectQueuedWithdrawal(Cow<'a, u64>),
+    DeployErc20Tokens(Cow<'a, parameters::DeployErc20TokensArgs>),
+    DeployErc20TokensCallback(Cow<'a, Vec<parameters::DeployErc20TokenResult>>),
+    SetCoinbaseMode(Cow<'a, parameters::SetCoinbaseModeArgs>),
+    SetBlockGasLimit(Cow<'a, parameters::SetBlockGasLimitArgs>),
+    CancelStagedUpgrade,
+    CommitUpgradeCodeHash(Cow<'a, parameters::CommitUpgradeCodeHashArgs>),
 }
 
 impl<'a> From<&'a TransactionKind> for BorshableTransactionKind<'a> {
@@ -981,6 +1213,43 @@ impl<'a> From<&'a TransactionKind> for BorshableTransactionKind<'a> {
             TransactionKind::WithdrawWnearToRouter(x) => {
                 Self::WithdrawWnearToRouter(Cow::Borrowed(x))
             }
+            TransactionKind::GrantRole(x) => Self::GrantRole(Cow::Borrowed(x)),
+            TransactionKind::RevokeRole(x) => Self::RevokeRole(Cow::Borrowed(x)),
+            TransactionKind::SetGovernanceConfig(x) => Self::SetGovernanceConfig(Cow::Borrowed(x)),
+            TransactionKind::CreateProposal(x) => Self::CreateProposal(Cow::Borrowed(x)),
+            TransactionKind::ApproveProposal(x) => Self::ApproveProposal(Cow::Borrowed(x)),
+            TransactionKind::ExecuteProposal(x) => Self::ExecuteProposal(Cow::Borrowed(x)),
+            TransactionKind::CancelProposal(x) => Self::CancelProposal(Cow::Borrowed(x)),
+            TransactionKind::XccEvmCallback(x) => Self::XccEvmCallback(Cow::Borrowed(x)),
+            TransactionKind::FactoryRemoveAddressVersion(address) => {
+                Self::FactoryRemoveAddressVersion(*address)
+            }
+            TransactionKind::NftOnTransfer(x) => Self::NftOnTransfer(Cow::Borrowed(x)),
+            TransactionKind::SetErc721Template(x) => Self::SetErc721Template(Cow::Borrowed(x)),
+            TransactionKind::SyncErc721Metadata(x) => Self::SyncErc721Metadata(Cow::Borrowed(x)),
+            TransactionKind::Erc721MetadataCallback(x) => {
+                Self::Erc721MetadataCallback(Cow::Borrowed(x))
+            }
+            TransactionKind::MtOnTransfer(x) => Self::MtOnTransfer(Cow::Borrowed(x)),
+            TransactionKind::SetErc1155Template(x) => Self::SetErc1155Template(Cow::Borrowed(x)),
+            TransactionKind::MtExitCallback(x) => Self::MtExitCallback(Cow::Borrowed(x)),
+            TransactionKind::SetWithdrawalLimit(x) => Self::SetWithdrawalLimit(Cow::Borrowed(x)),
+            TransactionKind::ApproveQueuedWithdrawal(x) => {
+                Self::ApproveQueuedWithdrawal(Cow::Borrowed(x))
+            }
+            TransactionKind::RejectQueuedWithdrawal(x) => {
+                Self::RejectQueuedWithdrawal(Cow::Borrowed(x))
+            }
+            TransactionKind::DeployErc20Tokens(x) => Self::DeployErc20Tokens(Cow::Borrowed(x)),
+            TransactionKind::DeployErc20TokensCallback(x) => {
+                Self::DeployErc20TokensCallback(Cow::Borrowed(x))
+            }
+            TransactionKind::SetCoinbaseMode(x) => Self::SetCoinbaseMode(Cow::Borrowed(x)),
+            TransactionKind::SetBlockGasLimit(x) => Self::SetBlockGasLimit(Cow::Borrowed(x)),
+            TransactionKind::CancelStagedUpgrade => Self::CancelStagedUpgrade,
+            TransactionKind::CommitUpgradeCodeHash(x) => {
+                Self::CommitUpgradeCodeHash(Cow::Borrowed(x))
+            }
             TransactionKind::Unknown => Self::Unknown,
             TransactionKind::PausePrecompiles(x) => Self::PausePrecompiles(Cow::Borrowed(x)),
             TransactionKind::ResumePrecompiles(x) => Self::ResumePrecompiles(Cow::Borrowed(x)),
@@ -1066,6 +1335,63 @@ impl<'a> TryFrom<BorshableTransactionKind<'a>> for TransactionKind {
             BorshableTransactionKind::FactorySetWNearAddress(address) => {
                 Ok(Self::FactorySetWNearAddress(address))
             }
+            BorshableTransactionKind::GrantRole(x) => Ok(Self::GrantRole(x.into_owned())),
+            BorshableTransactionKind::RevokeRole(x) => Ok(Self::RevokeRole(x.into_owned())),
+            BorshableTransactionKind::SetGovernanceConfig(x) => {
+                Ok(Self::SetGovernanceConfig(x.into_owned()))
+            }
+            BorshableTransactionKind::CreateProposal(x) => Ok(Self::CreateProposal(x.into_owned())),
+            BorshableTransactionKind::ApproveProposal(x) => {
+                Ok(Self::ApproveProposal(x.into_owned()))
+            }
+            BorshableTransactionKind::ExecuteProposal(x) => {
+                Ok(Self::ExecuteProposal(x.into_owned()))
+            }
+            BorshableTransactionKind::CancelProposal(x) => Ok(Self::CancelProposal(x.into_owned())),
+            BorshableTransactionKind::XccEvmCallback(x) => Ok(Self::XccEvmCallback(x.into_owned())),
+            BorshableTransactionKind::FactoryRemoveAddressVersion(address) => {
+                Ok(Self::FactoryRemoveAddressVersion(address))
+            }
+            BorshableTransactionKind::NftOnTransfer(x) => Ok(Self::NftOnTransfer(x.into_owned())),
+            BorshableTransactionKind::SetErc721Template(x) => {
+                Ok(Self::SetErc721Template(x.into_owned()))
+            }
+            BorshableTransactionKind::SyncErc721Metadata(x) => {
+                Ok(Self::SyncErc721Metadata(x.into_owned()))
+            }
+            BorshableTransactionKind::Erc721MetadataCallback(x) => {
+                Ok(Self::Erc721MetadataCallback(x.into_owned()))
+            }
+            BorshableTransactionKind::MtOnTransfer(x) => Ok(Self::MtOnTransfer(x.into_owned())),
+            BorshableTransactionKind::SetErc1155Template(x) => {
+                Ok(Self::SetErc1155Template(x.into_owned()))
+            }
+            BorshableTransactionKind::MtExitCallback(x) => Ok(Self::MtExitCallback(x.into_owned())),
+            BorshableTransactionKind::SetWithdrawalLimit(x) => {
+                Ok(Self::SetWithdrawalLimit(x.into_owned()))
+            }
+            BorshableTransactionKind::ApproveQueuedWithdrawal(x) => {
+                Ok(Self::ApproveQueuedWithdrawal(x.into_owned()))
+            }
+            BorshableTransactionKind::RejectQueuedWithdrawal(x) => {
+                Ok(Self::RejectQueuedWithdrawal(x.into_owned()))
+            }
+            BorshableTransactionKind::DeployErc20Tokens(x) => {
+                Ok(Self::DeployErc20Tokens(x.into_owned()))
+            }
+            BorshableTransactionKind::DeployErc20TokensCallback(x) => {
+                Ok(Self::DeployErc20TokensCallback(x.into_owned()))
+            }
+            BorshableTransactionKind::SetCoinbaseMode(x) => {
+                Ok(Self::SetCoinbaseMode(x.into_owned()))
+            }
+            BorshableTransactionKind::SetBlockGasLimit(x) => {
+                Ok(Self::SetBlockGasLimit(x.into_owned()))
+            }
+            BorshableTransactionKind::CancelStagedUpgrade => Ok(Self::CancelStagedUpgrade),
+            BorshableTransactionKind::CommitUpgradeCodeHash(x) => {
+                Ok(Self::CommitUpgradeCodeHash(x.into_owned()))
+            }
             BorshableTransactionKind::Unknown => Ok(Self::Unknown),
             BorshableTransactionKind::PausePrecompiles(x) => {
                 Ok(Self::PausePrecompiles(x.into_owned()))
@@ -4,7 +4,9 @@ use aurora_engine::engine::EngineError;
 use aurora_engine::parameters::{CallArgs, FunctionCallArgsV2};
 use aurora_engine_transactions::legacy::LegacyEthSignedTransaction;
 use aurora_engine_types::borsh::BorshDeserialize;
-use aurora_engine_types::parameters::engine::{SubmitResult, TransactionStatus};
+use aurora_engine_types::parameters::engine::{
+    DeployErc20TokensArgs, SubmitResult, TransactionStatus,
+};
 use ethabi::Token;
 use libsecp256k1::SecretKey;
 use near_vm_runner::logic::VMOutcome;
@@ -226,6 +228,29 @@ fn test_deploy_erc20_token() {
     runner.deploy_erc20_token("tt.testnet");
 }
 
+#[test]
+fn test_deploy_erc20_tokens_batch_size() {
+    let mut runner = AuroraRunner::new();
+    let deploy = |runner: &mut AuroraRunner, count: usize| {
+        let args = DeployErc20TokensArgs {
+            nep141s: (0..count)
+                .map(|i| format!("tt{i}.testnet").parse().unwrap())
+                .collect(),
+        };
+        runner.make_call(
+            "deploy_erc20_tokens",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            borsh::to_vec(&args).unwrap(),
+        )
+    };
+
+    let error = deploy(&mut runner, 0).unwrap_err();
+    assert!(error.to_string().contains("ERR_EMPTY_TOKENS_LIST"));
+    let error = deploy(&mut runner, 9).unwrap_err();
+    assert!(error.to_string().contains("ERR_TOO_MANY_TOKENS"));
+    assert!(deploy(&mut runner, 8).is_ok());
+}
+
 #[test]
 fn test_mint() {
     let mut runner = AuroraRunner::new();
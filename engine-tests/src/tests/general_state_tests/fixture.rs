@@ -0,0 +1,57 @@
+//! The JSON format of the filled `GeneralStateTests` fixtures of `ethereum/tests`.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A fixture file maps the test names to the tests.
+pub type Fixture = BTreeMap<String, StateTest>;
+
+#[derive(Debug, Deserialize)]
+pub struct StateTest {
+    pub env: TestEnv,
+    pub pre: BTreeMap<String, TestAccount>,
+    /// The expected outcomes of the transactions by fork name.
+    pub post: BTreeMap<String, Vec<PostState>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestEnv {
+    pub current_coinbase: String,
+    pub current_gas_limit: String,
+    pub current_number: String,
+    pub current_timestamp: String,
+    pub current_base_fee: Option<String>,
+    pub current_random: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestAccount {
+    pub balance: String,
+    pub code: String,
+    pub nonce: String,
+    pub storage: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostState {
+    /// The state root after the transaction.
+    pub hash: String,
+    /// The keccak hash of the RLP encoded logs of the transaction.
+    pub logs: String,
+    pub indexes: Indexes,
+    /// The signed transaction.
+    pub txbytes: String,
+    /// Set if the transaction is invalid.
+    pub expect_exception: Option<String>,
+    /// The full post state, only present in some of the fixtures.
+    pub state: Option<BTreeMap<String, TestAccount>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Indexes {
+    pub data: usize,
+    pub gas: usize,
+    pub value: usize,
+}
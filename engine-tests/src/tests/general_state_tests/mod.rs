@@ -0,0 +1,472 @@
+//! Runs the `GeneralStateTests` of [`ethereum/tests`](https://github.com/ethereum/tests) against
+//! the engine.
+//!
+//! The fixtures are not part of the repository. To run the whole suite, point
+//! `GENERAL_STATE_TESTS_DIR` to the `GeneralStateTests` directory of a checkout of the filled
+//! tests. Every post state of the `Cancun` fork is executed on a fresh engine state seeded with
+//! the pre state, and the resulting state root and logs are compared with the expected ones.
+//! Failures of the tests listed in `KNOWN_DEVIATIONS` are reported but don't fail the run.
+
+mod fixture;
+mod trie;
+
+use crate::prelude::transactions::{EthTransactionKind, NormalizedEthTransaction};
+use crate::prelude::{
+    parameters::SubmitArgs,
+    storage::{KeyPrefix, VersionPrefix},
+    u256_to_arr, Address, EthGas, Wei, H256, U256,
+};
+use aurora_engine::{engine, state};
+use aurora_engine_sdk::{
+    env::{Fixed, Timestamp, DEFAULT_PREPAID_GAS},
+    io::IO,
+    keccak,
+};
+use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+use aurora_engine_test_doubles::promise::PromiseTracker;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::parameters::engine::{CoinbaseMode, ResultLog};
+use fixture::{Fixture, PostState, StateTest, TestAccount, TestEnv};
+use rlp::RlpStream;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const FORK: &str = "Cancun";
+const FIXTURES_DIR_VAR: &str = "GENERAL_STATE_TESTS_DIR";
+
+/// Tests which are expected to fail because the engine intentionally behaves differently from
+/// Ethereum. A test is matched if its path or name contains the pattern.
+const KNOWN_DEVIATIONS: &[(&str, &str)] = &[
+    (
+        "stEIP4844-blobtransactions",
+        "blob transactions are not supported",
+    ),
+    (
+        "pointEvaluation",
+        "the KZG point evaluation precompile is not supported",
+    ),
+    (
+        "stBlockHashTest",
+        "block hashes are derived from the NEAR block height and the engine account",
+    ),
+    (
+        "stEIP1559/baseFeeDiffPlaces",
+        "there is no base fee, so BASEFEE returns zero",
+    ),
+    (
+        "stEIP1559/gasPriceDiffPlaces",
+        "there is no base fee, so GASPRICE returns the price without it",
+    ),
+];
+
+#[derive(Debug, Default)]
+struct Report {
+    passed: usize,
+    failures: Vec<String>,
+    known_deviations: Vec<String>,
+}
+
+/// The state of an account in a form which is comparable with the fixtures.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct AccountState {
+    nonce: U256,
+    balance: U256,
+    code: Vec<u8>,
+    storage: BTreeMap<H256, U256>,
+}
+
+impl AccountState {
+    fn is_empty(&self) -> bool {
+        self.nonce.is_zero()
+            && self.balance.is_zero()
+            && self.code.is_empty()
+            && self.storage.is_empty()
+    }
+
+    fn rlp(&self) -> Vec<u8> {
+        let storage_root = trie::sec_trie_root(
+            self.storage
+                .iter()
+                .map(|(key, value)| (key.as_bytes().to_vec(), rlp_u256(value))),
+        );
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&trim_u256(&self.nonce));
+        stream.append(&trim_u256(&self.balance));
+        stream.append(&storage_root.as_bytes());
+        stream.append(&keccak(&self.code).as_bytes());
+        stream.out().to_vec()
+    }
+}
+
+#[test]
+fn test_general_state_tests() {
+    let Some(dir) = std::env::var_os(FIXTURES_DIR_VAR) else {
+        println!("{FIXTURES_DIR_VAR} is not set, skipping the GeneralStateTests");
+        return;
+    };
+
+    let mut paths = Vec::new();
+    collect_fixtures(Path::new(&dir), &mut paths);
+    paths.sort();
+
+    let mut report = Report::default();
+    for path in &paths {
+        run_fixture_file(path, &mut report);
+    }
+
+    println!(
+        "GeneralStateTests: {} passed, {} failed, {} known deviations",
+        report.passed,
+        report.failures.len(),
+        report.known_deviations.len()
+    );
+    for deviation in &report.known_deviations {
+        println!("known deviation: {deviation}");
+    }
+    assert!(
+        report.failures.is_empty(),
+        "unexpected failures:\n{}",
+        report.failures.join("\n")
+    );
+}
+
+#[test]
+fn test_value_transfer_fixture() {
+    let mut report = Report::default();
+    run_fixture_file(
+        Path::new("src/tests/res/general_state_value_transfer.json"),
+        &mut report,
+    );
+
+    assert_eq!(report.passed, 1);
+    assert!(report.failures.is_empty(), "{:?}", report.failures);
+}
+
+fn collect_fixtures(dir: &Path, paths: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_fixtures(&path, paths);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+}
+
+fn run_fixture_file(path: &Path, report: &mut Report) {
+    let fixture: Fixture = match std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+    {
+        Ok(fixture) => fixture,
+        Err(e) => {
+            report.failures.push(format!("{}: {e}", path.display()));
+            return;
+        }
+    };
+
+    for (name, test) in &fixture {
+        let id = format!("{}::{name}", path.display());
+        let deviation = KNOWN_DEVIATIONS
+            .iter()
+            .find(|(pattern, _)| id.contains(pattern))
+            .map(|(_, reason)| reason);
+
+        for post in test.post.get(FORK).into_iter().flatten() {
+            let indexes = &post.indexes;
+            let id = format!("{id}[d{}g{}v{}]", indexes.data, indexes.gas, indexes.value);
+            match (run_post_state(test, post), deviation) {
+                (Ok(()), _) => report.passed += 1,
+                (Err(e), None) => report.failures.push(format!("{id}: {e}")),
+                (Err(_), Some(reason)) => {
+                    report.known_deviations.push(format!("{id}: {reason}"));
+                }
+            }
+        }
+    }
+}
+
+fn run_post_state(test: &StateTest, post: &PostState) -> Result<(), String> {
+    let storage = RefCell::new(Storage::default());
+    let mut io = StoragePointer(&storage);
+    let env = test_env(&test.env);
+    let engine_state = state::EngineState {
+        chain_id: u256_to_arr(&U256::one()),
+        owner_id: env.current_account_id.clone(),
+        upgrade_delay_blocks: 0,
+        is_paused: false,
+        key_manager: None,
+    };
+    state::set_state(&mut io, &engine_state).map_err(|e| format!("{e:?}"))?;
+
+    let coinbase = parse_address(&test.env.current_coinbase);
+    engine::set_coinbase_mode(&mut io, CoinbaseMode::Treasury(coinbase));
+    engine::set_block_gas_limit(
+        &mut io,
+        Some(EthGas::new(
+            parse_u256(&test.env.current_gas_limit).low_u64(),
+        )),
+    );
+    for (address, account) in &test.pre {
+        seed_account(&mut io, &parse_address(address), &parse_account(account));
+    }
+
+    let tx_data = parse_bytes(&post.txbytes);
+    let args = SubmitArgs {
+        tx_data: tx_data.clone(),
+        max_gas_price: None,
+        gas_token_address: None,
+    };
+    let result = engine::submit(
+        io,
+        &env,
+        &args,
+        engine_state,
+        env.current_account_id.clone(),
+        coinbase,
+        &mut PromiseTracker::default(),
+    );
+    let logs = match (result, &post.expect_exception) {
+        (Ok(result), None) => {
+            let base_fee = test
+                .env
+                .current_base_fee
+                .as_deref()
+                .map_or_else(U256::zero, parse_u256);
+            charge_base_fee(&mut io, &tx_data, base_fee, &coinbase, result.gas_used)?;
+            result.logs
+        }
+        (Err(e), None) => return Err(format!("the transaction failed with {:?}", e.kind)),
+        (Ok(_), Some(exception)) => {
+            return Err(format!("the transaction succeeded, expected {exception}"))
+        }
+        (Err(_), Some(_)) => Vec::new(),
+    };
+
+    let logs_hash = logs_hash(&logs);
+    if logs_hash != parse_h256(&post.logs) {
+        return Err(format!("logs hash mismatch: got {logs_hash:?}"));
+    }
+
+    let accounts = dump_state(&storage);
+    if let Some(expected) = &post.state {
+        let expected = parse_state(expected);
+        if let Some(address) = expected
+            .keys()
+            .chain(accounts.keys())
+            .find(|address| expected.get(*address) != accounts.get(*address))
+        {
+            return Err(format!(
+                "state mismatch at {}: expected {:?}, got {:?}",
+                address.encode(),
+                expected.get(address),
+                accounts.get(address)
+            ));
+        }
+    }
+
+    let state_root = state_root(&accounts);
+    if state_root != parse_h256(&post.hash) {
+        return Err(format!("state root mismatch: got {state_root:?}"));
+    }
+
+    Ok(())
+}
+
+fn test_env(env: &TestEnv) -> Fixed {
+    let aurora_id: AccountId = crate::utils::DEFAULT_AURORA_ACCOUNT_ID.parse().unwrap();
+    let timestamp = parse_u256(&env.current_timestamp).low_u64();
+    Fixed {
+        signer_account_id: aurora_id.clone(),
+        current_account_id: aurora_id.clone(),
+        predecessor_account_id: aurora_id,
+        block_height: parse_u256(&env.current_number).low_u64(),
+        block_timestamp: Timestamp::new(timestamp * 1_000_000_000),
+        attached_deposit: 0,
+        random_seed: env
+            .current_random
+            .as_deref()
+            .map_or_else(H256::zero, parse_h256),
+        prepaid_gas: DEFAULT_PREPAID_GAS,
+    }
+}
+
+fn seed_account<I: IO>(io: &mut I, address: &Address, account: &AccountState) {
+    engine::set_nonce(io, address, &account.nonce);
+    engine::set_balance(io, address, &Wei::new(account.balance));
+    if !account.code.is_empty() {
+        engine::set_code(io, address, &account.code);
+    }
+    for (key, value) in &account.storage {
+        engine::set_storage(io, address, key, &H256(u256_to_arr(value)), 0);
+    }
+}
+
+/// The engine has no base fee, so it pays the whole gas price to the coinbase. Burns the base fee
+/// the same way as Ethereum would.
+fn charge_base_fee<I: IO>(
+    io: &mut I,
+    tx_data: &[u8],
+    base_fee: U256,
+    coinbase: &Address,
+    gas_used: u64,
+) -> Result<(), String> {
+    let tx = EthTransactionKind::try_from(tx_data)
+        .and_then(NormalizedEthTransaction::try_from)
+        .map_err(|e| format!("{e:?}"))?;
+    let engine_price = tx.max_priority_fee_per_gas.min(tx.max_fee_per_gas);
+    let price = tx
+        .max_fee_per_gas
+        .min(base_fee.saturating_add(tx.max_priority_fee_per_gas));
+    let tip = price.saturating_sub(base_fee);
+    let gas_used = U256::from(gas_used);
+
+    let sender_charge = (price - engine_price) * gas_used;
+    let coinbase_refund = (engine_price - tip) * gas_used;
+    let sender_balance = engine::get_balance(io, &tx.address).raw();
+    let coinbase_balance = engine::get_balance(io, coinbase).raw();
+    engine::set_balance(
+        io,
+        &tx.address,
+        &Wei::new(
+            sender_balance
+                .checked_sub(sender_charge)
+                .ok_or("the sender can't pay the base fee")?,
+        ),
+    );
+    engine::set_balance(
+        io,
+        coinbase,
+        &Wei::new(
+            coinbase_balance
+                .checked_sub(coinbase_refund)
+                .ok_or("the coinbase didn't receive the base fee")?,
+        ),
+    );
+    Ok(())
+}
+
+/// Reads all non-empty accounts of the engine with the storage of their current generation.
+fn dump_state(storage: &RefCell<Storage>) -> BTreeMap<Address, AccountState> {
+    let io = StoragePointer(storage);
+    let account_prefix = [u8::from(VersionPrefix::V1), u8::from(KeyPrefix::Account)];
+    let storage_prefix = [u8::from(VersionPrefix::V1), u8::from(KeyPrefix::Storage)];
+    let store = storage.borrow();
+    let kv_store = &store.kv_store;
+
+    let mut accounts: BTreeMap<Address, AccountState> = kv_store
+        .keys()
+        .filter(|key| key.len() == 22 && key.starts_with(&account_prefix))
+        .map(|key| {
+            let address = Address::try_from_slice(&key[2..]).unwrap();
+            let account = AccountState {
+                nonce: engine::get_nonce(&io, &address),
+                balance: engine::get_balance(&io, &address).raw(),
+                code: engine::get_code(&io, &address),
+                storage: BTreeMap::new(),
+            };
+            (address, account)
+        })
+        .collect();
+
+    for (key, value) in kv_store {
+        if !key.starts_with(&storage_prefix) {
+            continue;
+        }
+        let (generation, slot) = match key.len() {
+            54 => (0, &key[22..]),
+            58 => (
+                u32::from_le_bytes(key[22..26].try_into().unwrap()),
+                &key[26..],
+            ),
+            _ => continue,
+        };
+        let address = Address::try_from_slice(&key[2..22]).unwrap();
+        let value = U256::from_big_endian(value);
+        if generation == engine::get_generation(&io, &address) && !value.is_zero() {
+            accounts
+                .entry(address)
+                .or_default()
+                .storage
+                .insert(H256::from_slice(slot), value);
+        }
+    }
+
+    accounts.retain(|_, account| !account.is_empty());
+    accounts
+}
+
+fn state_root(accounts: &BTreeMap<Address, AccountState>) -> H256 {
+    trie::sec_trie_root(
+        accounts
+            .iter()
+            .map(|(address, account)| (address.as_bytes().to_vec(), account.rlp())),
+    )
+}
+
+fn logs_hash(logs: &[ResultLog]) -> H256 {
+    let mut stream = RlpStream::new_list(logs.len());
+    for log in logs {
+        stream.begin_list(3);
+        stream.append(&log.address.as_bytes());
+        stream.begin_list(log.topics.len());
+        for topic in &log.topics {
+            stream.append(&topic.as_slice());
+        }
+        stream.append(&log.data);
+    }
+    keccak(&stream.out())
+}
+
+fn parse_state(state: &BTreeMap<String, TestAccount>) -> BTreeMap<Address, AccountState> {
+    state
+        .iter()
+        .map(|(address, account)| (parse_address(address), parse_account(account)))
+        .filter(|(_, account)| !account.is_empty())
+        .collect()
+}
+
+fn parse_account(account: &TestAccount) -> AccountState {
+    AccountState {
+        nonce: parse_u256(&account.nonce),
+        balance: parse_u256(&account.balance),
+        code: parse_bytes(&account.code),
+        storage: account
+            .storage
+            .iter()
+            .map(|(key, value)| (parse_h256(key), parse_u256(value)))
+            .filter(|(_, value)| !value.is_zero())
+            .collect(),
+    }
+}
+
+fn parse_u256(value: &str) -> U256 {
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() {
+        return U256::zero();
+    }
+    U256::from_str_radix(digits, 16).unwrap()
+}
+
+fn parse_h256(value: &str) -> H256 {
+    H256(u256_to_arr(&parse_u256(value)))
+}
+
+fn parse_bytes(value: &str) -> Vec<u8> {
+    hex::decode(value.trim_start_matches("0x")).unwrap()
+}
+
+fn parse_address(value: &str) -> Address {
+    Address::decode(value.trim_start_matches("0x")).unwrap()
+}
+
+fn trim_u256(value: &U256) -> Vec<u8> {
+    let bytes = u256_to_arr(value);
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+fn rlp_u256(value: &U256) -> Vec<u8> {
+    rlp::encode(&trim_u256(value)).to_vec()
+}
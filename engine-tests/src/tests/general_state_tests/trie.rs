@@ -0,0 +1,151 @@
+//! Root hashes of the Merkle Patricia tries used by Ethereum for the state and storage roots.
+
+use crate::prelude::H256;
+use aurora_engine_sdk::keccak;
+use rlp::RlpStream;
+
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// Returns the root of the trie holding the values under the keccak hashes of their keys.
+pub fn sec_trie_root<I: IntoIterator<Item = Entry>>(entries: I) -> H256 {
+    trie_root(
+        entries
+            .into_iter()
+            .map(|(key, value)| (keccak(&key).as_bytes().to_vec(), value)),
+    )
+}
+
+/// Returns the root of the trie holding the values under their keys.
+pub fn trie_root<I: IntoIterator<Item = Entry>>(entries: I) -> H256 {
+    let mut entries: Vec<Entry> = entries
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(&key), value))
+        .collect();
+    entries.sort();
+
+    let mut stream = RlpStream::new();
+    append_node(&mut stream, &entries, 0);
+    keccak(&stream.out())
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+/// Compact encoding of a path, see Appendix C of the Yellow Paper.
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 0x20 } else { 0x00 };
+    let mut result = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        result.push(flag | 0x10 | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        result.push(flag);
+        nibbles
+    };
+    result.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    result
+}
+
+/// Appends the node holding the sorted entries, whose keys are equal up to `depth` nibbles.
+fn append_node(stream: &mut RlpStream, entries: &[Entry], depth: usize) {
+    match entries {
+        [] => {
+            stream.append_empty_data();
+        }
+        [(key, value)] => {
+            stream.begin_list(2);
+            stream.append(&hex_prefix(&key[depth..], true));
+            stream.append(value);
+        }
+        [(first, _), rest @ ..] => {
+            let shared = rest
+                .iter()
+                .map(|(key, _)| common_prefix_len(&first[depth..], &key[depth..]))
+                .min()
+                .unwrap_or_default();
+            if shared > 0 {
+                stream.begin_list(2);
+                stream.append(&hex_prefix(&first[depth..(depth + shared)], false));
+                append_child(stream, entries, depth + shared);
+                return;
+            }
+
+            // The key ending at this depth, if any, is sorted first and becomes the branch value.
+            let (value, mut children) = if first.len() == depth {
+                (Some(&entries[0].1), &entries[1..])
+            } else {
+                (None, entries)
+            };
+            stream.begin_list(17);
+            for nibble in 0..16 {
+                let len = children
+                    .iter()
+                    .take_while(|(key, _)| key[depth] == nibble)
+                    .count();
+                append_child(stream, &children[..len], depth + 1);
+                children = &children[len..];
+            }
+            match value {
+                Some(value) => stream.append(value),
+                None => stream.append_empty_data(),
+            };
+        }
+    }
+}
+
+/// Appends the node inline if its encoding is shorter than 32 bytes, otherwise its hash.
+fn append_child(stream: &mut RlpStream, entries: &[Entry], depth: usize) {
+    let mut child = RlpStream::new();
+    append_node(&mut child, entries, depth);
+    let encoded = child.out();
+    if encoded.len() < 32 {
+        stream.append_raw(&encoded, 1);
+    } else {
+        stream.append(&keccak(&encoded));
+    }
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+#[test]
+fn test_trie_root() {
+    let entries = |pairs: &[(&str, &str)]| -> Vec<Entry> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+            .collect()
+    };
+    let pairs = [
+        ("doe", "reindeer"),
+        ("dog", "puppy"),
+        ("dogglesworth", "cat"),
+    ];
+
+    assert_eq!(
+        trie_root(Vec::new()),
+        H256::from_slice(
+            &hex::decode("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        trie_root(entries(&pairs)),
+        H256::from_slice(
+            &hex::decode("8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        sec_trie_root(entries(&pairs)),
+        H256::from_slice(
+            &hex::decode("d4cd937e4a4368d7931a9cf51686b7e10abb3dce38a39000fd7902a092b64585")
+                .unwrap()
+        )
+    );
+}
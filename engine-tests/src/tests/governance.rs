@@ -0,0 +1,192 @@
+use crate::utils::{self, AuroraRunner};
+use aurora_engine_types::parameters::engine::SetOwnerArgs;
+use aurora_engine_types::parameters::governance::{
+    GovernanceConfig, Proposal, ProposalAction, ProposalIdArgs, ProposalStatus,
+};
+
+const ALICE: &str = "alice.near";
+const BOB: &str = "bob.near";
+const EVE: &str = "eve.near";
+const TIMELOCK_BLOCKS: u64 = 10;
+
+#[test]
+fn test_set_governance_config_only_once() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call("set_governance_config", EVE, governance_config());
+    assert!(result.is_err());
+
+    let result = runner.call(
+        "set_governance_config",
+        &aurora_account_id,
+        governance_config(),
+    );
+    assert!(result.is_ok());
+
+    // The config could be changed only via a proposal after it has been set.
+    let result = runner.call(
+        "set_governance_config",
+        &aurora_account_id,
+        governance_config(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_execute_proposal_after_approval_and_timelock() {
+    let mut runner = setup_governance();
+    let action = ProposalAction::SetOwner(SetOwnerArgs {
+        new_owner: "new_owner.near".parse().unwrap(),
+    });
+
+    // Accounts which are neither approvers nor the owner can't create proposals.
+    let result = runner.call("create_proposal", EVE, borsh::to_vec(&action).unwrap());
+    assert!(result.is_err());
+
+    let id = create_proposal(&mut runner, ALICE, &action);
+    assert_eq!(get_proposal(&runner, id).approvals.len(), 1);
+
+    // The threshold of approvals hasn't been reached yet.
+    let result = runner.call("execute_proposal", EVE, id_args(id));
+    assert!(result.is_err());
+
+    let result = runner.call("approve_proposal", EVE, id_args(id));
+    assert!(result.is_err());
+    let result = runner.call("approve_proposal", ALICE, id_args(id));
+    assert!(result.is_err());
+    let result = runner.call("approve_proposal", BOB, id_args(id));
+    assert!(result.is_ok());
+
+    // The timelock hasn't passed yet.
+    let result = runner.call("execute_proposal", EVE, id_args(id));
+    assert!(result.is_err());
+
+    runner.context.block_height += TIMELOCK_BLOCKS;
+    let result = runner.call("execute_proposal", EVE, id_args(id));
+    assert!(result.is_ok());
+    assert_eq!(get_proposal(&runner, id).status, ProposalStatus::Executed);
+
+    let outcome = runner.one_shot().call("get_owner", ALICE, vec![]).unwrap();
+    assert_eq!(
+        b"new_owner.near",
+        outcome.return_data.as_value().unwrap().as_slice()
+    );
+
+    // The proposal can't be executed twice.
+    let result = runner.call("execute_proposal", EVE, id_args(id));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cancel_proposal() {
+    let mut runner = setup_governance();
+    let action = ProposalAction::FactoryUpdate(vec![0; 4]);
+    let id = create_proposal(&mut runner, ALICE, &action);
+
+    let result = runner.call("cancel_proposal", BOB, id_args(id));
+    assert!(result.is_err());
+    let result = runner.call("cancel_proposal", ALICE, id_args(id));
+    assert!(result.is_ok());
+    assert_eq!(get_proposal(&runner, id).status, ProposalStatus::Cancelled);
+
+    // The cancelled proposal can't be approved anymore.
+    let result = runner.call("approve_proposal", BOB, id_args(id));
+    assert!(result.is_err());
+
+    // Ids of the proposals aren't reused.
+    assert_eq!(create_proposal(&mut runner, BOB, &action), id + 1);
+}
+
+#[test]
+fn test_direct_changes_are_rejected_once_governance_is_configured() {
+    let mut runner = setup_governance();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let args = borsh::to_vec(&SetOwnerArgs {
+        new_owner: "new_owner.near".parse().unwrap(),
+    })
+    .unwrap();
+    let result = runner.call("set_owner", &aurora_account_id, args);
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("ERR_GOVERNANCE_PROPOSAL_REQUIRED"));
+
+    let result = runner.call("factory_update", &aurora_account_id, vec![0; 4]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_set_owner_proposal_rejects_same_owner() {
+    let mut runner = setup_governance();
+    let action = ProposalAction::SetOwner(SetOwnerArgs {
+        new_owner: runner.aurora_account_id.parse().unwrap(),
+    });
+    let id = create_proposal(&mut runner, ALICE, &action);
+    let result = runner.call("approve_proposal", BOB, id_args(id));
+    assert!(result.is_ok());
+
+    runner.context.block_height += TIMELOCK_BLOCKS;
+    let result = runner.call("execute_proposal", EVE, id_args(id));
+    assert!(result.is_err());
+    assert_eq!(get_proposal(&runner, id).status, ProposalStatus::Pending);
+}
+
+#[cfg(not(feature = "ext-connector"))]
+#[test]
+fn test_eth_connector_account_proposal_requires_ext_connector() {
+    use aurora_engine_types::parameters::connector::{
+        SetEthConnectorContractAccountArgs, WithdrawSerializeType,
+    };
+
+    let mut runner = setup_governance();
+    let action =
+        ProposalAction::SetEthConnectorContractAccount(SetEthConnectorContractAccountArgs {
+            account: "eth_connector.near".parse().unwrap(),
+            withdraw_serialize_type: WithdrawSerializeType::Borsh,
+        });
+    let result = runner.call("create_proposal", ALICE, borsh::to_vec(&action).unwrap());
+    assert!(result.is_err());
+}
+
+fn setup_governance() -> AuroraRunner {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "set_governance_config",
+        &aurora_account_id,
+        governance_config(),
+    );
+    assert!(result.is_ok());
+    runner
+}
+
+fn create_proposal(runner: &mut AuroraRunner, proposer: &str, action: &ProposalAction) -> u64 {
+    let outcome = runner
+        .call("create_proposal", proposer, borsh::to_vec(action).unwrap())
+        .unwrap();
+    borsh::from_slice(&outcome.return_data.as_value().unwrap()).unwrap()
+}
+
+fn get_proposal(runner: &AuroraRunner, id: u64) -> Proposal {
+    let outcome = runner
+        .one_shot()
+        .call("get_proposal", ALICE, id_args(id))
+        .unwrap();
+    borsh::from_slice(&outcome.return_data.as_value().unwrap()).unwrap()
+}
+
+fn governance_config() -> Vec<u8> {
+    borsh::to_vec(&GovernanceConfig {
+        approvers: vec![ALICE.parse().unwrap(), BOB.parse().unwrap()],
+        threshold: 2,
+        default_timelock_blocks: TIMELOCK_BLOCKS,
+        action_timelocks: Vec::new(),
+    })
+    .unwrap()
+}
+
+fn id_args(id: u64) -> Vec<u8> {
+    borsh::to_vec(&ProposalIdArgs { id }).unwrap()
+}
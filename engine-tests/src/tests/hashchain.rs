@@ -1,6 +1,7 @@
 use crate::utils;
 use aurora_engine::parameters::{StartHashchainArgs, SubmitResult, TransactionStatus};
 use aurora_engine_hashchain::bloom::Bloom;
+use aurora_engine_precompiles::hashchain_history;
 use aurora_engine_transactions::legacy::TransactionLegacy;
 use aurora_engine_types::{
     types::{Address, Wei},
@@ -89,6 +90,26 @@ fn test_hashchain() {
     let hc = get_latest_hashchain(&runner);
     assert_eq!(hc.block_height, runner.context.block_height - 1);
     assert_eq!(hc.hashchain, hex::encode(expected_hc));
+
+    // The hashchain of the block stays available in the history
+    assert_eq!(get_hashchain_at(&runner, hc.block_height), Some(hc.clone()));
+    assert_eq!(get_hashchain_at(&runner, hc.block_height + 1), None);
+
+    // and can be read by contracts
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| TransactionLegacy {
+            nonce,
+            gas_price: U256::zero(),
+            gas_limit: u64::MAX.into(),
+            to: Some(hashchain_history::ADDRESS),
+            value: Wei::zero(),
+            data: aurora_engine_types::types::u256_to_arr(&U256::from(hc.block_height)).to_vec(),
+        })
+        .unwrap();
+    assert_eq!(
+        result.status,
+        TransactionStatus::Succeed(expected_hc.to_vec())
+    );
 }
 
 fn get_latest_hashchain(runner: &utils::AuroraRunner) -> HashchainView {
@@ -101,6 +122,20 @@ fn get_latest_hashchain(runner: &utils::AuroraRunner) -> HashchainView {
     result.result.unwrap()
 }
 
+fn get_hashchain_at(runner: &utils::AuroraRunner, block_height: u64) -> Option<HashchainView> {
+    let outcome = runner
+        .one_shot()
+        .call(
+            "get_hashchain_at",
+            "any.near",
+            borsh::to_vec(&block_height).unwrap(),
+        )
+        .unwrap();
+    let return_data = outcome.return_data.as_value().unwrap();
+    let result: HashchainViewResult = serde_json::from_slice(&return_data).unwrap();
+    result.result
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 struct HashchainViewResult {
     result: Option<HashchainView>,
@@ -5,10 +5,14 @@ mod ecrecover;
 mod erc20;
 mod erc20_connector;
 mod erc20_mirror;
+mod general_state_tests;
 mod ghsa_3p69_m8gg_fwmf;
+mod governance;
 mod hashchain;
 pub mod modexp;
+mod mt;
 mod multisender;
+mod nft;
 mod one_inch;
 mod pausable_precompiles;
 mod pause_contract;
@@ -16,6 +20,7 @@ mod prepaid_gas_precompile;
 mod promise_results_precompile;
 mod random;
 mod repro;
+mod roles;
 pub mod sanity;
 mod self_destruct_state;
 mod serde;
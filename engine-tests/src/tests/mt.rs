@@ -0,0 +1,209 @@
+use crate::utils::solidity::{ContractConstructor, DeployedContract};
+use crate::utils::{self, AuroraRunner, Signer};
+use aurora_engine_precompiles::native::events::EXIT_MULTI_TOKEN_TO_NEAR_SIGNATURE;
+use aurora_engine_transactions::legacy::TransactionLegacy;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::parameters::engine::TransactionStatus;
+use aurora_engine_types::parameters::mt::{NEP245MtBatchTransferArgs, NEP245MtOnTransferArgs};
+use aurora_engine_types::parameters::withdrawal_limits::{
+    ExceedAction, SetWithdrawalLimitArgs, WithdrawalLimit, WithdrawalLimitTarget,
+};
+use aurora_engine_types::types::{Address, Balance, NearGas, Wei};
+use aurora_engine_types::U256;
+use near_vm_runner::logic::mocks::mock_external::MockAction;
+
+const NEP245: &str = "mt.near";
+const NEAR_TOKEN_IDS: [&str; 2] = ["sword", "shield"];
+const AMOUNTS: [u128; 2] = [1, 10];
+const NEAR_RECIPIENT: &str = "bob.near";
+/// Gas attached by the exit precompile to `mt_batch_transfer`: 20 Tgas and 2 Tgas per token.
+const MT_BATCH_TRANSFER_GAS: NearGas = NearGas::new(24_000_000_000_000);
+
+#[test]
+fn test_mt_on_transfer_and_batch_exit_to_near() {
+    let mut runner = utils::deploy_runner();
+    let constructor = ContractConstructor::compile_from_source(
+        "src/tests/res",
+        "target/solidity_build",
+        "erc1155_template.sol",
+        "Erc1155Template",
+    );
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "set_erc1155_template",
+        &aurora_account_id,
+        constructor.code.clone(),
+    );
+    assert!(result.is_ok());
+
+    let mut signer = Signer::random();
+    let owner = utils::address_from_secret_key(&signer.secret_key);
+    runner.create_address(owner, Wei::zero(), U256::zero());
+
+    // The ERC-1155 contract is deployed on the first transfer of tokens of the NEP-245 contract.
+    let args = NEP245MtOnTransferArgs {
+        sender_id: "alice.near".parse().unwrap(),
+        previous_owner_ids: vec!["alice.near".parse().unwrap(); 2],
+        token_ids: NEAR_TOKEN_IDS.iter().map(ToString::to_string).collect(),
+        amounts: AMOUNTS.into_iter().map(Balance::new).collect(),
+        msg: hex::encode(owner.as_bytes()),
+    };
+    let outcome = runner
+        .call("mt_on_transfer", NEP245, serde_json::to_vec(&args).unwrap())
+        .unwrap();
+    assert_eq!(outcome.return_data.as_value().unwrap(), br#"["0","0"]"#);
+
+    let erc1155 = get_erc1155_from_nep245(&runner, NEP245);
+    let contract = constructor.deployed_at(erc1155);
+    let token_ids =
+        NEAR_TOKEN_IDS.map(|id| U256::from_big_endian(&aurora_engine_sdk::keccak(id.as_bytes()).0));
+    for (token_id, amount) in token_ids.iter().zip(AMOUNTS) {
+        assert_eq!(
+            balance_of(&runner, &contract, owner, *token_id),
+            U256::from(amount)
+        );
+    }
+
+    // The multi-token exits are covered by the withdrawal limits, the amounts of the
+    // tokens of the batch are summed.
+    let total = AMOUNTS.iter().sum::<u128>();
+    set_withdrawal_limit(&mut runner, erc1155, total - 1);
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            withdraw_to_near(&contract, &token_ids, nonce)
+        })
+        .unwrap();
+    assert!(matches!(result.status, TransactionStatus::Revert(_)));
+
+    set_withdrawal_limit(&mut runner, erc1155, total);
+    runner.ext.underlying.action_log.clear();
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            withdraw_to_near(&contract, &token_ids, nonce)
+        })
+        .unwrap();
+    assert!(result.status.is_ok());
+    for token_id in &token_ids {
+        assert_eq!(
+            balance_of(&runner, &contract, owner, *token_id),
+            U256::zero()
+        );
+    }
+
+    // One event per token of the batch, with the token id and the amount in the data.
+    let exit_events = result
+        .logs
+        .iter()
+        .filter(|log| log.topics.first() == Some(&EXIT_MULTI_TOKEN_TO_NEAR_SIGNATURE.0))
+        .collect::<Vec<_>>();
+    assert_eq!(exit_events.len(), token_ids.len());
+    for ((event, token_id), amount) in exit_events.iter().zip(token_ids).zip(AMOUNTS) {
+        assert_eq!(event.topics.len(), 4);
+        assert_eq!(event.topics[2][12..], *erc1155.as_bytes());
+        assert_eq!(
+            event.data,
+            ethabi::encode(&[
+                ethabi::Token::Uint(token_id),
+                ethabi::Token::Uint(U256::from(amount))
+            ])
+        );
+    }
+
+    // The NEAR tokens are transferred to the recipient with a single batch transfer.
+    let transfer = runner
+        .ext
+        .underlying
+        .action_log
+        .iter()
+        .find_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name,
+                args,
+                prepaid_gas,
+                ..
+            } if method_name == b"mt_batch_transfer" => Some((args, *prepaid_gas)),
+            _ => None,
+        });
+    let (args, prepaid_gas) = transfer.unwrap();
+    let args: NEP245MtBatchTransferArgs = serde_json::from_slice(args).unwrap();
+    assert_eq!(args.receiver_id, NEAR_RECIPIENT.parse().unwrap());
+    assert_eq!(args.token_ids, NEAR_TOKEN_IDS);
+    assert_eq!(args.amounts, AMOUNTS.map(Balance::new));
+    assert_eq!(prepaid_gas, MT_BATCH_TRANSFER_GAS.as_u64());
+}
+
+fn withdraw_to_near(
+    contract: &DeployedContract,
+    token_ids: &[U256],
+    nonce: U256,
+) -> TransactionLegacy {
+    contract.call_method_with_args(
+        "withdrawToNear",
+        &[
+            ethabi::Token::Bytes(NEAR_RECIPIENT.as_bytes().to_vec()),
+            ethabi::Token::Array(token_ids.iter().copied().map(ethabi::Token::Uint).collect()),
+            ethabi::Token::Array(
+                AMOUNTS
+                    .iter()
+                    .map(|amount| ethabi::Token::Uint((*amount).into()))
+                    .collect(),
+            ),
+        ],
+        nonce,
+    )
+}
+
+fn set_withdrawal_limit(runner: &mut AuroraRunner, erc1155: Address, max_amount: u128) {
+    let args = SetWithdrawalLimitArgs {
+        target: WithdrawalLimitTarget::Erc1155(erc1155),
+        limit: Some(WithdrawalLimit {
+            max_amount,
+            window_secs: 3_600,
+            on_exceed: ExceedAction::Reject,
+            pause_threshold: None,
+        }),
+    };
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "set_withdrawal_limit",
+        &aurora_account_id,
+        borsh::to_vec(&args).unwrap(),
+    );
+    assert!(result.is_ok());
+}
+
+fn get_erc1155_from_nep245(runner: &AuroraRunner, nep245: &str) -> Address {
+    let nep245: AccountId = nep245.parse().unwrap();
+    let outcome = runner
+        .one_shot()
+        .call(
+            "get_erc1155_from_nep245",
+            "alice.near",
+            borsh::to_vec(&nep245).unwrap(),
+        )
+        .unwrap();
+    Address::try_from_slice(&outcome.return_data.as_value().unwrap()).unwrap()
+}
+
+fn balance_of(
+    runner: &AuroraRunner,
+    contract: &DeployedContract,
+    account: Address,
+    token_id: U256,
+) -> U256 {
+    let tx = contract.call_method_with_args(
+        "balanceOf",
+        &[
+            ethabi::Token::Address(account.raw()),
+            ethabi::Token::Uint(token_id),
+        ],
+        0.into(),
+    );
+    let result = runner
+        .view_call(&utils::as_view_call(tx, Address::zero()))
+        .unwrap();
+    let TransactionStatus::Succeed(output) = result else {
+        panic!("Unexpected status {result:?}");
+    };
+    U256::from_big_endian(&output)
+}
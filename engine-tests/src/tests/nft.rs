@@ -0,0 +1,129 @@
+use crate::utils::solidity::{ContractConstructor, DeployedContract};
+use crate::utils::{self, AuroraRunner, Signer};
+use aurora_engine_precompiles::native::events::EXIT_NFT_TO_NEAR_SIGNATURE;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::parameters::engine::TransactionStatus;
+use aurora_engine_types::parameters::nft::{NEP171NftOnTransferArgs, NEP171NftTransferArgs};
+use aurora_engine_types::types::{Address, NearGas, Wei};
+use aurora_engine_types::U256;
+use near_vm_runner::logic::mocks::mock_external::MockAction;
+
+const NEP171: &str = "nft.near";
+const NEAR_TOKEN_ID: &str = "token-1";
+const NEAR_RECIPIENT: &str = "bob.near";
+/// Gas attached by the exit precompile to `nft_transfer`.
+const NFT_TRANSFER_GAS: NearGas = NearGas::new(15_000_000_000_000);
+
+#[test]
+fn test_nft_on_transfer_and_exit_to_near() {
+    let mut runner = utils::deploy_runner();
+    let constructor = ContractConstructor::compile_from_source(
+        "src/tests/res",
+        "target/solidity_build",
+        "erc721_template.sol",
+        "Erc721Template",
+    );
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "set_erc721_template",
+        &aurora_account_id,
+        constructor.code.clone(),
+    );
+    assert!(result.is_ok());
+
+    let mut signer = Signer::random();
+    let owner = utils::address_from_secret_key(&signer.secret_key);
+    runner.create_address(owner, Wei::zero(), U256::zero());
+
+    // The ERC-721 contract is deployed on the first transfer of a token of the NEP-171 contract.
+    let args = NEP171NftOnTransferArgs {
+        sender_id: "alice.near".parse().unwrap(),
+        previous_owner_id: "alice.near".parse().unwrap(),
+        token_id: NEAR_TOKEN_ID.into(),
+        msg: hex::encode(owner.as_bytes()),
+    };
+    let outcome = runner
+        .call(
+            "nft_on_transfer",
+            NEP171,
+            serde_json::to_vec(&args).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(outcome.return_data.as_value().unwrap(), b"false");
+
+    let erc721 = get_erc721_from_nep171(&runner, NEP171);
+    let contract = constructor.deployed_at(erc721);
+    let token_id = U256::from_big_endian(&aurora_engine_sdk::keccak(NEAR_TOKEN_ID.as_bytes()).0);
+    assert_eq!(owner_of(&runner, &contract, token_id), owner);
+
+    runner.ext.underlying.action_log.clear();
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            contract.call_method_with_args(
+                "withdrawToNear",
+                &[
+                    ethabi::Token::Bytes(NEAR_RECIPIENT.as_bytes().to_vec()),
+                    ethabi::Token::Uint(token_id),
+                ],
+                nonce,
+            )
+        })
+        .unwrap();
+    assert!(result.status.is_ok());
+    assert_eq!(owner_of(&runner, &contract, token_id), Address::zero());
+
+    let exit_event = result
+        .logs
+        .iter()
+        .find(|log| log.topics.first() == Some(&EXIT_NFT_TO_NEAR_SIGNATURE.0))
+        .unwrap();
+    assert_eq!(exit_event.topics[2][12..], *erc721.as_bytes());
+    assert_eq!(U256::from_big_endian(&exit_event.data), token_id);
+
+    // The NEAR token is transferred to the recipient.
+    let transfer = runner
+        .ext
+        .underlying
+        .action_log
+        .iter()
+        .find_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name,
+                args,
+                attached_deposit,
+                prepaid_gas,
+                ..
+            } if method_name == b"nft_transfer" => Some((args, *attached_deposit, *prepaid_gas)),
+            _ => None,
+        });
+    let (args, attached_deposit, prepaid_gas) = transfer.unwrap();
+    let args: NEP171NftTransferArgs = serde_json::from_slice(args).unwrap();
+    assert_eq!(args.receiver_id, NEAR_RECIPIENT.parse().unwrap());
+    assert_eq!(args.token_id, NEAR_TOKEN_ID);
+    assert_eq!(attached_deposit, 1);
+    assert_eq!(prepaid_gas, NFT_TRANSFER_GAS.as_u64());
+}
+
+fn get_erc721_from_nep171(runner: &AuroraRunner, nep171: &str) -> Address {
+    let nep171: AccountId = nep171.parse().unwrap();
+    let outcome = runner
+        .one_shot()
+        .call(
+            "get_erc721_from_nep171",
+            "alice.near",
+            borsh::to_vec(&nep171).unwrap(),
+        )
+        .unwrap();
+    Address::try_from_slice(&outcome.return_data.as_value().unwrap()).unwrap()
+}
+
+fn owner_of(runner: &AuroraRunner, contract: &DeployedContract, token_id: U256) -> Address {
+    let tx = contract.call_method_with_args("ownerOf", &[ethabi::Token::Uint(token_id)], 0.into());
+    let result = runner
+        .view_call(&utils::as_view_call(tx, Address::zero()))
+        .unwrap();
+    let TransactionStatus::Succeed(output) = result else {
+        panic!("Unexpected status {result:?}");
+    };
+    Address::try_from_slice(&output[12..]).unwrap()
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity ^0.8.0;
+
+// Minimal version of `etc/eth-contracts/contracts/EvmErc1155.sol` (which depends on
+// OpenZeppelin) exposing the functions used by the engine.
+contract Erc1155Template {
+    // `keccak("exitToNear")[12..]`
+    address constant EXIT_TO_NEAR = address(uint160(uint256(keccak256("exitToNear"))));
+
+    address admin;
+    string public name;
+    mapping(uint256 => mapping(address => uint256)) balances;
+    mapping(uint256 => string) nearTokenIds;
+
+    constructor(string memory nep245_account_id, address admin_address) {
+        name = nep245_account_id;
+        admin = admin_address;
+    }
+
+    function balanceOf(address account, uint256 id) public view returns (uint256) {
+        return balances[id][account];
+    }
+
+    function nearTokenId(uint256 id) public view returns (string memory) {
+        return nearTokenIds[id];
+    }
+
+    function mintBatch(address account, uint256[] memory ids, uint256[] memory amounts, string[] memory near_token_ids) public {
+        require(msg.sender == admin, "ERR_NOT_ADMIN");
+        require(ids.length == amounts.length && ids.length == near_token_ids.length, "ERR_LENGTH_MISMATCH");
+        for (uint256 i = 0; i < ids.length; ++i) {
+            nearTokenIds[ids[i]] = near_token_ids[i];
+            balances[ids[i]][account] += amounts[i];
+        }
+    }
+
+    function withdrawToNear(bytes memory recipient, uint256[] memory ids, uint256[] memory amounts) external {
+        require(ids.length == amounts.length, "ERR_LENGTH_MISMATCH");
+        for (uint256 i = 0; i < ids.length; ++i) {
+            balances[ids[i]][msg.sender] -= amounts[i];
+        }
+
+        bytes memory input = abi.encodePacked("\x03", msg.sender, abi.encode(recipient, ids, amounts));
+        (bool success, ) = EXIT_TO_NEAR.call(input);
+        require(success);
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity ^0.8.0;
+
+// Minimal version of `etc/eth-contracts/contracts/EvmErc721.sol` (which depends on
+// OpenZeppelin) exposing the functions used by the engine.
+contract Erc721Template {
+    // `keccak("exitToNear")[12..]`
+    address constant EXIT_TO_NEAR = address(uint160(uint256(keccak256("exitToNear"))));
+
+    address admin;
+    string public name;
+    string public symbol;
+    mapping(uint256 => address) owners;
+    mapping(uint256 => string) nearTokenIds;
+
+    constructor(string memory metadata_name, string memory metadata_symbol, address admin_address) {
+        name = metadata_name;
+        symbol = metadata_symbol;
+        admin = admin_address;
+    }
+
+    modifier onlyAdmin() {
+        require(msg.sender == admin, "ERR_NOT_ADMIN");
+        _;
+    }
+
+    function ownerOf(uint256 tokenId) public view returns (address) {
+        return owners[tokenId];
+    }
+
+    function nearTokenId(uint256 tokenId) public view returns (string memory) {
+        return nearTokenIds[tokenId];
+    }
+
+    function setMetadata(string memory metadata_name, string memory metadata_symbol, string memory) external onlyAdmin {
+        name = metadata_name;
+        symbol = metadata_symbol;
+    }
+
+    function mint(address account, uint256 tokenId, string memory near_token_id) public onlyAdmin {
+        nearTokenIds[tokenId] = near_token_id;
+        mint(account, tokenId);
+    }
+
+    function mint(address account, uint256 tokenId) public onlyAdmin {
+        require(owners[tokenId] == address(0), "ERR_TOKEN_EXISTS");
+        owners[tokenId] = account;
+    }
+
+    function withdrawToNear(bytes memory recipient, uint256 tokenId) external {
+        require(owners[tokenId] == msg.sender, "ERR_NOT_OWNER");
+        delete owners[tokenId];
+
+        bytes memory input = abi.encodePacked("\x02", msg.sender, bytes32(tokenId), recipient);
+        (bool success, ) = EXIT_TO_NEAR.call(input);
+        require(success);
+    }
+}
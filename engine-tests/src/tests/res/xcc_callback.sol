@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity ^0.8.0;
+
+contract XccCallbackReceiver {
+    // `keccak("nearCrossContractCall")[12..]`
+    address constant XCC_PRECOMPILE = address(uint160(uint256(keccak256("nearCrossContractCall"))));
+
+    struct PromiseResult {
+        bool success;
+        bytes output;
+    }
+
+    address lastSender;
+    uint256 resultsCount;
+    bool lastSuccess;
+    bytes lastOutput;
+
+    function onXccResult(address sender, PromiseResult[] calldata results) external {
+        require(msg.sender == XCC_PRECOMPILE, "ERR_NOT_XCC_PRECOMPILE");
+        lastSender = sender;
+        resultsCount = results.length;
+        if (results.length > 0) {
+            lastSuccess = results[0].success;
+            lastOutput = results[0].output;
+        }
+    }
+
+    function getLastResult() external view returns (address, uint256, bool, bytes memory) {
+        return (lastSender, resultsCount, lastSuccess, lastOutput);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::utils::{self, AuroraRunner, PAUSE_PRECOMPILES};
+use aurora_engine::parameters::PausePrecompilesCallArgs;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::events::{AdminEvent, EventLog};
+use aurora_engine_types::parameters::roles::{Role, RoleArgs, RoleKindArgs};
+
+const PAUSER: &str = "pauser.near";
+const UPGRADER: &str = "upgrader.near";
+
+#[test]
+fn test_grant_role_require_owner() {
+    let mut runner = utils::deploy_runner();
+
+    let result = runner.call("grant_role", PAUSER, role_args(Role::Pauser, PAUSER));
+    assert!(result.is_err());
+    assert!(get_role_members(&runner, Role::Pauser).is_empty());
+
+    grant_role(&mut runner, Role::Pauser, PAUSER);
+    assert_eq!(
+        get_role_members(&runner, Role::Pauser),
+        vec![PAUSER.parse::<AccountId>().unwrap()]
+    );
+
+    // The role can't be granted twice.
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "grant_role",
+        &aurora_account_id,
+        role_args(Role::Pauser, PAUSER),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_pauser_role_allows_pausing() {
+    let mut runner = utils::deploy_runner();
+
+    let result = runner.call("pause_contract", PAUSER, vec![]);
+    assert!(result.is_err());
+
+    grant_role(&mut runner, Role::Pauser, PAUSER);
+
+    let result = runner.call("pause_contract", PAUSER, vec![]);
+    assert!(result.is_ok());
+    let result = runner.call("resume_contract", PAUSER, vec![]);
+    assert!(result.is_ok());
+
+    let input = borsh::to_vec(&PausePrecompilesCallArgs { paused_mask: 0b01 }).unwrap();
+    let result = runner.call(PAUSE_PRECOMPILES, PAUSER, input.clone());
+    assert!(result.is_ok());
+
+    // Other roles don't allow pausing.
+    grant_role(&mut runner, Role::Upgrader, UPGRADER);
+    let result = runner.call(PAUSE_PRECOMPILES, UPGRADER, input);
+    assert!(result.is_err());
+    let result = runner.call("pause_contract", UPGRADER, vec![]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_revoke_role() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    grant_role(&mut runner, Role::Pauser, PAUSER);
+    assert_eq!(get_account_roles(&runner, PAUSER), vec![Role::Pauser]);
+
+    let outcome = runner
+        .call(
+            "revoke_role",
+            &aurora_account_id,
+            role_args(Role::Pauser, PAUSER),
+        )
+        .unwrap();
+    assert!(get_account_roles(&runner, PAUSER).is_empty());
+
+    let events: Vec<_> = outcome
+        .logs
+        .iter()
+        .filter_map(|log| EventLog::parse(log))
+        .map(|log| log.event)
+        .collect();
+    assert_eq!(
+        events,
+        vec![AdminEvent::RoleRevoked {
+            role: Role::Pauser,
+            account_id: PAUSER.parse().unwrap(),
+        }]
+    );
+
+    let result = runner.call("pause_contract", PAUSER, vec![]);
+    assert!(result.is_err());
+
+    // The role which isn't granted can't be revoked.
+    let result = runner.call(
+        "revoke_role",
+        &aurora_account_id,
+        role_args(Role::Pauser, PAUSER),
+    );
+    assert!(result.is_err());
+}
+
+fn grant_role(runner: &mut AuroraRunner, role: Role, account_id: &str) {
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call(
+        "grant_role",
+        &aurora_account_id,
+        role_args(role, account_id),
+    );
+    assert!(result.is_ok());
+}
+
+fn get_role_members(runner: &AuroraRunner, role: Role) -> Vec<AccountId> {
+    let input = borsh::to_vec(&RoleKindArgs { role }).unwrap();
+    let result = runner
+        .one_shot()
+        .call("get_role_members", &runner.aurora_account_id, input)
+        .unwrap();
+    borsh::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+fn get_account_roles(runner: &AuroraRunner, account_id: &str) -> Vec<Role> {
+    let input = borsh::to_vec(&account_id.parse::<AccountId>().unwrap()).unwrap();
+    let result = runner
+        .one_shot()
+        .call("get_account_roles", &runner.aurora_account_id, input)
+        .unwrap();
+    borsh::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+fn role_args(role: Role, account_id: &str) -> Vec<u8> {
+    borsh::to_vec(&RoleArgs {
+        role,
+        account_id: account_id.parse().unwrap(),
+    })
+    .unwrap()
+}
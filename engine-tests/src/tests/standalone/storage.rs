@@ -358,15 +358,15 @@ fn test_track_key() {
         .unwrap();
     assert!(result.status.is_ok());
 
-    // The balance key for the signer will have changed 3 times:
+    // The account key for the signer will have changed 3 times:
     // 1. Account minted
     // 2. Transfer to dest1
     // 3. Transfer to dest2
-    let balance_key = aurora_engine_types::storage::address_to_key(
-        aurora_engine_types::storage::KeyPrefix::Balance,
+    let account_key = aurora_engine_types::storage::address_to_key(
+        aurora_engine_types::storage::KeyPrefix::Account,
         &signer_address,
     );
-    let trace = runner.storage.track_engine_key(&balance_key).unwrap();
+    let trace = runner.storage.track_engine_key(&account_key).unwrap();
     let mut expected_balance = initial_balance;
     for (i, (block_height, tx_hash, value)) in trace.into_iter().enumerate() {
         assert_eq!(
@@ -387,10 +387,45 @@ fn test_track_key() {
                 .get_transaction_by_position(transaction_included)
                 .unwrap()
         );
-        let balance = Wei::new(U256::from_big_endian(value.value().unwrap()));
+        let account: aurora_engine::engine::AccountRecord =
+            aurora_engine_types::borsh::from_slice(value.value().unwrap()).unwrap();
+        let balance = account.balance();
         assert_eq!(balance, expected_balance);
         expected_balance = expected_balance - transfer_amount;
     }
 
     runner.close();
 }
+
+#[test]
+fn test_engine_cache_follows_committed_diffs() {
+    let mut signer = Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let initial_balance = Wei::new_u64(1000);
+    let transfer_amount = Wei::new_u64(37);
+    let dest = Address::from_array([0x11; 20]);
+    let mut runner = utils::standalone::StandaloneRunner::default();
+
+    runner.init_evm();
+    runner.mint_account(signer_address, initial_balance, signer.nonce.into(), None);
+
+    // The second read of the same account is served by the cache.
+    assert_eq!(runner.get_balance(&signer_address), initial_balance);
+    let metrics = runner.storage.get_engine_cache_metrics();
+    assert_eq!(runner.get_balance(&signer_address), initial_balance);
+    assert!(runner.storage.get_engine_cache_metrics().hits > metrics.hits);
+
+    // Values written by the committed transactions replace the cached ones.
+    let result = runner
+        .transfer_with_signer(&mut signer, transfer_amount, dest)
+        .unwrap();
+    assert!(result.status.is_ok());
+    assert_eq!(
+        runner.get_balance(&signer_address),
+        initial_balance - transfer_amount
+    );
+    assert_eq!(runner.get_balance(&dest), transfer_amount);
+    assert_eq!(runner.get_nonce(&signer_address), U256::one());
+
+    runner.close();
+}
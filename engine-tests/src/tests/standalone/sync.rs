@@ -153,6 +153,73 @@ fn test_consume_deposit_message() {
     runner.close();
 }
 
+#[cfg(not(feature = "ext-connector"))]
+#[test]
+fn test_local_scheduler_deposit() {
+    use aurora_engine_types::types::PromiseResult;
+    use engine_standalone_storage::promise::LocalScheduler;
+
+    let (mut runner, block_message) = initialize();
+
+    let recipient_address = Address::new(H160([22u8; 20]));
+    let deposit_amount = Wei::new_u64(123_456_789);
+    let proof = mock_proof(recipient_address, deposit_amount);
+    let tx_kind = sync::types::TransactionKind::Deposit(borsh::to_vec(&proof).unwrap());
+    let raw_input = tx_kind.raw_bytes();
+
+    let transaction_message = sync::types::TransactionMessage {
+        block_hash: block_message.hash,
+        near_receipt_id: H256([0x11; 32]),
+        position: 0,
+        succeeded: true,
+        signer: runner.env.signer_account_id(),
+        caller: runner.env.predecessor_account_id(),
+        attached_near: 0,
+        transaction: tx_kind,
+        promise_data: Vec::new(),
+        raw_input,
+        action_hash: H256::default(),
+    };
+
+    // The only external receipt is the call to the proof verifier.
+    let mut verifier_calls = 0;
+    let mut prover = |args: &aurora_engine_types::parameters::PromiseCreateArgs,
+                      _: &[Option<Vec<u8>>]| {
+        assert_eq!(args.target_account_id.as_ref(), "prover.near");
+        verifier_calls += 1;
+        PromiseResult::Successful(borsh::to_vec(&true).unwrap())
+    };
+    let mut scheduler = LocalScheduler::new();
+    let outcomes = scheduler
+        .execute::<AuroraModExp, _>(&mut runner.storage, transaction_message, &mut prover)
+        .unwrap();
+
+    assert_eq!(verifier_calls, 1);
+    let executed: Vec<_> = outcomes
+        .iter()
+        .map(|outcome| {
+            assert!(outcome.maybe_result.is_ok());
+            sync::types::TransactionKindTag::from(&outcome.info.transaction)
+        })
+        .collect();
+    assert_eq!(
+        executed,
+        vec![
+            sync::types::TransactionKindTag::Deposit,
+            sync::types::TransactionKindTag::FinishDeposit,
+            sync::types::TransactionKindTag::FtOnTransfer,
+            sync::types::TransactionKindTag::ResolveTransfer,
+        ]
+    );
+    // The callbacks are executed by the Engine itself.
+    assert!(outcomes[1..]
+        .iter()
+        .all(|outcome| outcome.info.caller == runner.env.current_account_id));
+    assert_eq!(runner.get_balance(&recipient_address), deposit_amount);
+
+    runner.close();
+}
+
 #[test]
 fn test_consume_deploy_message() {
     let (mut runner, block_message) = initialize();
@@ -462,6 +529,339 @@ fn test_consume_submit_message() {
     assert_eq!(runner.get_nonce(&signer_address), U256::one());
 }
 
+#[test]
+fn test_hashchain_verification() {
+    use aurora_engine::hashchain::{read_current_hashchain, save_hashchain};
+    use aurora_engine_hashchain::hashchain::compute_tx_hash;
+    use aurora_engine_hashchain::hashchain::HashchainBuilder;
+    use engine_standalone_storage::hashchain::{DivergingTx, HashchainVerification};
+
+    let (mut runner, _) = initialize();
+
+    let mut signer = utils::Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    runner.mint_account(signer_address, Wei::new_u64(800_000), U256::zero(), None);
+
+    // Start the hashchain of the engine.
+    runner.env.block_height += 1;
+    let start_height = runner.env.block_height;
+    utils::standalone::mocks::insert_block(&mut runner.storage, start_height);
+    let hashchain = HashchainBuilder::new()
+        .with_u64_chain_id(runner.chain_id)
+        .with_account_id(runner.env.current_account_id())
+        .with_current_block_height(start_height)
+        .build();
+    let result = runner
+        .storage
+        .with_engine_access(start_height, 0, &[], |mut io| {
+            save_hashchain(&mut io, &hashchain).unwrap();
+        });
+    let outcome = sync::TransactionIncludedOutcome {
+        hash: H256::zero(),
+        info: sync::types::TransactionMessage {
+            block_hash: utils::standalone::mocks::compute_block_hash(start_height),
+            near_receipt_id: H256::zero(),
+            position: 0,
+            succeeded: true,
+            signer: runner.env.signer_account_id(),
+            caller: runner.env.predecessor_account_id(),
+            attached_near: 0,
+            transaction: sync::types::TransactionKind::Unknown,
+            promise_data: Vec::new(),
+            raw_input: Vec::new(),
+            action_hash: H256::default(),
+        },
+        diff: result.diff,
+        output: result.engine_output,
+        logs: result.logs,
+        maybe_result: Ok(None),
+    };
+    outcome.commit(&mut runner.storage).unwrap();
+
+    // Submit transactions in two later blocks with an empty block in between.
+    let mut tx_hashes = Vec::new();
+    for (block_height, position) in [
+        (start_height + 1, 0),
+        (start_height + 3, 0),
+        (start_height + 3, 1),
+    ] {
+        runner.env.block_height = block_height;
+        if position == 0 {
+            utils::standalone::mocks::insert_block(&mut runner.storage, block_height);
+        }
+        let transaction = utils::transfer(
+            Address::new(H160([1u8; 20])),
+            Wei::new_u64(1_000),
+            signer.use_nonce().into(),
+        );
+        let signed_transaction =
+            utils::sign_transaction(transaction, Some(runner.chain_id), &signer.secret_key);
+        let tx_kind = sync::types::TransactionKind::Submit(
+            crate::prelude::transactions::EthTransactionKind::Legacy(signed_transaction),
+        );
+        let raw_input = tx_kind.raw_bytes();
+        let transaction_message = sync::types::TransactionMessage {
+            block_hash: utils::standalone::mocks::compute_block_hash(block_height),
+            near_receipt_id: H256([8 + position; 32]),
+            position: u16::from(position),
+            succeeded: true,
+            signer: runner.env.signer_account_id(),
+            caller: runner.env.predecessor_account_id(),
+            attached_near: 0,
+            transaction: tx_kind,
+            promise_data: Vec::new(),
+            raw_input: raw_input.clone(),
+            action_hash: H256::default(),
+        };
+        let outcome = sync::consume_message::<AuroraModExp>(
+            &mut runner.storage,
+            sync::types::Message::Transaction(Box::new(transaction_message)),
+        )
+        .unwrap();
+        if block_height == start_height + 3 {
+            tx_hashes.push(compute_tx_hash("submit", &raw_input, &outcome.output));
+        }
+        outcome.commit(&mut runner.storage).unwrap();
+    }
+
+    // The hashchain computed from the outcomes matches the one computed by the engine.
+    let mut engine_hashchain = runner
+        .storage
+        .with_engine_access(start_height + 4, 0, &[], |io| {
+            read_current_hashchain(&io).unwrap().unwrap()
+        })
+        .result;
+    assert_eq!(
+        engine_hashchain.get_current_block_height(),
+        start_height + 3
+    );
+    let expected = engine_hashchain.get_previous_block_hashchain();
+    assert_eq!(
+        runner.storage.get_hashchain_at(start_height + 2).unwrap(),
+        expected
+    );
+    assert_eq!(
+        runner
+            .storage
+            .verify_hashchain(start_height + 2, expected, &[])
+            .unwrap(),
+        HashchainVerification::Verified
+    );
+
+    // The hashchain of the last block is computed from the latest state.
+    engine_hashchain.move_to_block(start_height + 4).unwrap();
+    let expected = engine_hashchain.get_previous_block_hashchain();
+    assert_eq!(
+        runner
+            .storage
+            .verify_hashchain(start_height + 3, expected, &tx_hashes)
+            .unwrap(),
+        HashchainVerification::Verified
+    );
+
+    // The transaction whose on-chain output differs is reported.
+    let mut onchain_tx_hashes = tx_hashes.clone();
+    onchain_tx_hashes[1] = compute_tx_hash("submit", b"input", b"other output");
+    assert_eq!(
+        runner
+            .storage
+            .verify_hashchain(start_height + 3, [0u8; 32], &onchain_tx_hashes)
+            .unwrap(),
+        HashchainVerification::Diverged {
+            computed: expected,
+            first_diverging_tx: Some(DivergingTx {
+                index: 1,
+                replayed: Some((1, tx_hashes[1])),
+            }),
+        }
+    );
+    // The block has fewer transactions on-chain.
+    assert_eq!(
+        runner
+            .storage
+            .verify_hashchain(start_height + 3, [0u8; 32], &tx_hashes[..1])
+            .unwrap(),
+        HashchainVerification::Diverged {
+            computed: expected,
+            first_diverging_tx: Some(DivergingTx {
+                index: 1,
+                replayed: Some((1, tx_hashes[1])),
+            }),
+        }
+    );
+    // The transactions match, so the difference comes from the logs or an earlier block.
+    assert_eq!(
+        runner
+            .storage
+            .verify_hashchain(start_height + 3, [0u8; 32], &tx_hashes)
+            .unwrap(),
+        HashchainVerification::Diverged {
+            computed: expected,
+            first_diverging_tx: None,
+        }
+    );
+    assert_eq!(
+        runner.storage.get_hashchain_at(start_height - 1),
+        Err(engine_standalone_storage::Error::NoHashchainAtHeight(
+            start_height - 1
+        ))
+    );
+}
+
+#[test]
+fn test_consume_upgrade_code_hash_messages() {
+    use aurora_engine_types::events::AdminEvent;
+    use aurora_engine_types::parameters::engine::CommitUpgradeCodeHashArgs;
+    use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+
+    let (mut runner, _) = initialize();
+    let commitment_key = bytes_to_key(KeyPrefix::Config, b"CODE_HASH_COMMIT");
+
+    let code_hash = [7u8; 32];
+    let tx_kind = sync::types::TransactionKind::CommitUpgradeCodeHash(CommitUpgradeCodeHashArgs {
+        code_hash: Some(code_hash),
+    });
+    assert_eq!(
+        sync::parse_transaction_kind("commit_upgrade_code_hash", tx_kind.raw_bytes(), &[]).unwrap(),
+        tx_kind
+    );
+    let outcome = consume_transaction(&mut runner, tx_kind);
+    assert!(outcome.maybe_result.is_ok());
+    assert_eq!(
+        outcome.admin_events(),
+        vec![AdminEvent::UpgradeCodeHashCommitted {
+            code_hash: Some(H256(code_hash)),
+        }]
+    );
+    assert_eq!(
+        outcome
+            .diff
+            .get(&commitment_key)
+            .and_then(|value| value.value()),
+        Some(code_hash.as_slice())
+    );
+
+    // Nothing is staged, so there is nothing to cancel.
+    let tx_kind = sync::types::TransactionKind::CancelStagedUpgrade;
+    assert_eq!(
+        sync::parse_transaction_kind("cancel_staged_upgrade", tx_kind.raw_bytes(), &[]).unwrap(),
+        tx_kind
+    );
+    let outcome = consume_transaction(&mut runner, tx_kind);
+    assert!(outcome.maybe_result.is_err());
+}
+
+#[test]
+fn test_admin_events() {
+    use aurora_engine_types::events::AdminEvent;
+    use aurora_engine_types::parameters::engine::SetOwnerArgs;
+    use aurora_engine_types::parameters::governance::{
+        GovernanceConfig, ProposalAction, ProposalActionKind,
+    };
+
+    let (mut runner, _) = initialize();
+    let owner = runner.env.predecessor_account_id();
+
+    let events =
+        consume_admin_transaction(&mut runner, sync::types::TransactionKind::PauseContract);
+    assert_eq!(events, vec![AdminEvent::ContractPaused]);
+    let events =
+        consume_admin_transaction(&mut runner, sync::types::TransactionKind::ResumeContract);
+    assert_eq!(events, vec![AdminEvent::ContractResumed]);
+
+    let address = Address::from_array([1; 20]);
+    let events = consume_admin_transaction(
+        &mut runner,
+        sync::types::TransactionKind::RegisterRelayer(address),
+    );
+    assert_eq!(
+        events,
+        vec![AdminEvent::RelayerRegistered {
+            account_id: owner.clone(),
+            address,
+        }]
+    );
+
+    let config = GovernanceConfig {
+        approvers: vec![owner.clone()],
+        threshold: 1,
+        default_timelock_blocks: 0,
+        action_timelocks: Vec::new(),
+    };
+    let events = consume_admin_transaction(
+        &mut runner,
+        sync::types::TransactionKind::SetGovernanceConfig(config),
+    );
+    assert_eq!(
+        events,
+        vec![AdminEvent::GovernanceConfigChanged {
+            approvers: vec![owner.clone()],
+            threshold: 1,
+        }]
+    );
+
+    // The id of the created proposal is assigned by the engine and taken from the emitted event.
+    for id in 0..2 {
+        let action = ProposalAction::SetOwner(SetOwnerArgs {
+            new_owner: "new_owner.near".parse().unwrap(),
+        });
+        let events = consume_admin_transaction(
+            &mut runner,
+            sync::types::TransactionKind::CreateProposal(action),
+        );
+        assert_eq!(
+            events,
+            vec![AdminEvent::ProposalCreated {
+                id,
+                proposer: owner.clone(),
+                action: ProposalActionKind::SetOwner,
+            }]
+        );
+    }
+}
+
+fn consume_admin_transaction(
+    runner: &mut StandaloneRunner,
+    tx_kind: sync::types::TransactionKind,
+) -> Vec<aurora_engine_types::events::AdminEvent> {
+    consume_transaction(runner, tx_kind).admin_events()
+}
+
+fn consume_transaction(
+    runner: &mut StandaloneRunner,
+    tx_kind: sync::types::TransactionKind,
+) -> Box<sync::TransactionIncludedOutcome> {
+    runner.env.block_height += 1;
+    utils::standalone::mocks::insert_block(&mut runner.storage, runner.env.block_height);
+    let block_hash = utils::standalone::mocks::compute_block_hash(runner.env.block_height);
+    let raw_input = tx_kind.raw_bytes();
+    let transaction_message = sync::types::TransactionMessage {
+        block_hash,
+        near_receipt_id: H256([8u8; 32]),
+        position: 0,
+        succeeded: true,
+        signer: runner.env.signer_account_id(),
+        caller: runner.env.predecessor_account_id(),
+        attached_near: 0,
+        transaction: tx_kind,
+        promise_data: Vec::new(),
+        raw_input,
+        action_hash: H256::default(),
+    };
+
+    let outcome = sync::consume_message::<AuroraModExp>(
+        &mut runner.storage,
+        sync::types::Message::Transaction(Box::new(transaction_message)),
+    )
+    .unwrap();
+    outcome.commit(&mut runner.storage).unwrap();
+
+    match outcome {
+        sync::ConsumeMessageOutcome::TransactionIncluded(outcome) => outcome,
+        _ => panic!("Transaction must be included"),
+    }
+}
+
 #[cfg(not(feature = "ext-connector"))]
 fn mock_proof(recipient_address: Address, deposit_amount: Wei) -> aurora_engine::proof::Proof {
     use aurora_engine::contract_methods::connector::deposit_event::{
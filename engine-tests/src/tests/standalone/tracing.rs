@@ -78,6 +78,8 @@ fn test_evm_tracing_with_storage() {
             action_hash: H256::default(),
         },
         diff,
+        output: Vec::new(),
+        logs: Vec::new(),
         maybe_result: Ok(None),
     };
     standalone::storage::commit(&mut runner.storage, &tx);
@@ -1,6 +1,7 @@
 use std::{fs, path::Path};
 
 use crate::utils::workspace::deploy_engine;
+use aurora_engine_types::parameters::migration::{RecordVersion, StorageRecord};
 
 #[tokio::test]
 async fn test_code_upgrade() {
@@ -51,6 +52,35 @@ async fn test_code_upgrade_with_stage() {
     assert_eq!(output, [3, 1, 4, 1, 5, 9, 2]);
 }
 
+#[tokio::test]
+async fn test_state_migration_progress() {
+    let aurora = deploy_engine().await;
+    // The records of the newly initialized contract have the latest versions.
+    let progress = aurora.get_migration_progress().await.unwrap().result;
+    assert!(progress.is_completed());
+    assert_eq!(progress.versions, progress.latest_versions);
+    assert_eq!(progress.cursor, None);
+    // The records without schema changes yet are at the initial version.
+    for record in [
+        StorageRecord::Connector,
+        StorageRecord::Xcc,
+        StorageRecord::Silo,
+        StorageRecord::Relayer,
+    ] {
+        assert!(progress
+            .latest_versions
+            .contains(&RecordVersion { record, version: 1 }));
+    }
+
+    // Calling the migration again doesn't change anything.
+    let result = aurora.state_migration().max_gas().transact().await.unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        aurora.get_migration_progress().await.unwrap().result,
+        progress
+    );
+}
+
 // TODO: Should be reworked with `upgrade_delay_blocks` more then one to check that
 // we get the TOO EARLY error.
 #[tokio::test]
@@ -77,6 +107,129 @@ async fn test_repeated_calls_to_upgrade_should_fail() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_get_staged_upgrade() {
+    let aurora = deploy_engine().await;
+    assert!(aurora.get_staged_upgrade().await.is_err());
+
+    let code = contract_bytes();
+    let result = aurora
+        .stage_upgrade(code.clone())
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let info = aurora.get_staged_upgrade().await.unwrap().result;
+    let unlock_height = aurora.get_upgrade_index().await.unwrap().result;
+    assert_eq!(info.code_hash, aurora_engine_sdk::sha256(&code).0);
+    assert_eq!(info.code_size, code.len() as u64);
+    assert_eq!(info.unlock_height, unlock_height);
+    assert_eq!(info.committed_code_hash, None);
+}
+
+#[tokio::test]
+async fn test_cancel_staged_upgrade() {
+    let aurora = deploy_engine().await;
+    // Nothing to cancel
+    let result = aurora.cancel_staged_upgrade().max_gas().transact().await;
+    assert!(result.is_err());
+
+    let result = aurora
+        .stage_upgrade(contract_bytes())
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let result = aurora
+        .cancel_staged_upgrade()
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert!(aurora.get_staged_upgrade().await.is_err());
+
+    // The cancelled upgrade can't be deployed.
+    let result = aurora.deploy_upgrade().max_gas().transact().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_staged_upgrade_must_match_committed_code_hash() {
+    let aurora = deploy_engine().await;
+    let code = contract_bytes();
+    let code_hash = aurora_engine_sdk::sha256(&code).0;
+
+    // Commit to a hash of some other code.
+    let result = aurora
+        .commit_upgrade_code_hash(Some([1; 32]))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    let result = aurora
+        .stage_upgrade(code.clone())
+        .max_gas()
+        .transact()
+        .await;
+    assert!(result.is_err());
+
+    let result = aurora
+        .commit_upgrade_code_hash(Some(code_hash))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    let result = aurora
+        .stage_upgrade(code)
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let info = aurora.get_staged_upgrade().await.unwrap().result;
+    assert_eq!(info.committed_code_hash, Some(code_hash));
+
+    // The commitment can't be changed while the upgrade is staged.
+    let result = aurora
+        .commit_upgrade_code_hash(None)
+        .max_gas()
+        .transact()
+        .await;
+    assert!(result.is_err());
+
+    let result = aurora.deploy_upgrade().max_gas().transact().await.unwrap();
+    assert!(result.is_success());
+}
+
+#[tokio::test]
+async fn test_upgrade_must_match_committed_code_hash() {
+    let aurora = deploy_engine().await;
+    let result = aurora
+        .commit_upgrade_code_hash(Some([1; 32]))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    // The commitment can't be bypassed by deploying other code right away.
+    let result = aurora.upgrade(contract_bytes()).max_gas().transact().await;
+    assert!(result.is_err());
+    let result = aurora
+        .as_raw_contract()
+        .view("some_new_fancy_function")
+        .await;
+    assert!(result.is_err());
+}
+
 fn contract_bytes() -> Vec<u8> {
     let base_path = Path::new("../etc")
         .join("tests")
@@ -1,14 +1,17 @@
 use crate::utils::solidity::erc20::{ERC20Constructor, ERC20};
 use crate::utils::{self, AuroraRunner, DEFAULT_AURORA_ACCOUNT_ID};
-use aurora_engine_precompiles::xcc::{costs, cross_contract_call};
+use aurora_engine_precompiles::xcc::{abi as xcc_abi, costs, cross_contract_call};
 use aurora_engine_transactions::legacy::TransactionLegacy;
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::BorshDeserialize;
+use aurora_engine_types::parameters::engine::SubmitResult;
+use aurora_engine_types::parameters::xcc::XccEvmCallbackArgs;
 use aurora_engine_types::parameters::{
-    CrossContractCallArgs, NearPromise, PromiseArgs, PromiseCreateArgs, PromiseWithCallbackArgs,
-    SimpleNearPromise,
+    CrossContractCallArgs, EvmCallback, NearPromise, PromiseAction, PromiseArgs,
+    PromiseBatchAction, PromiseCreateArgs, PromiseWithCallbackArgs, SimpleNearPromise,
 };
-use aurora_engine_types::types::{Address, EthGas, NearGas, Wei, Yocto};
+use aurora_engine_types::public_key::PublicKey;
+use aurora_engine_types::types::{Address, EthGas, NearGas, PromiseResult, Wei, Yocto};
 use aurora_engine_types::U256;
 use aurora_engine_workspace::types::NearToken;
 use near_vm_runner::logic::mocks::mock_external::MockAction;
@@ -283,6 +286,191 @@ fn test_xcc_exec_gas() {
     }
 }
 
+#[test]
+fn test_xcc_evm_callback() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let constructor = utils::solidity::ContractConstructor::compile_from_source(
+        "src/tests/res",
+        "target/solidity_build",
+        "xcc_callback.sol",
+        "XccCallbackReceiver",
+    );
+    let nonce = signer.use_nonce();
+    let contract = runner.deploy_contract(
+        &signer.secret_key,
+        |c| c.deploy_without_constructor(nonce.into()),
+        constructor,
+    );
+    let sender = utils::address_from_secret_key(&signer.secret_key);
+    let args = XccEvmCallbackArgs {
+        sender,
+        callback: EvmCallback {
+            address: contract.address,
+            selector: contract
+                .abi
+                .function("onXccResult")
+                .unwrap()
+                .short_signature(),
+            gas: NearGas::new(100_000_000_000_000),
+        },
+    };
+    runner.promise_results = vec![PromiseResult::Successful(b"hello".to_vec())];
+
+    // Only the engine itself can deliver the results.
+    let result = runner.call(
+        "xcc_evm_callback",
+        "someone.near",
+        borsh::to_vec(&args).unwrap(),
+    );
+    assert!(result.is_err());
+
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let outcome = runner
+        .call(
+            "xcc_evm_callback",
+            &aurora_account_id,
+            borsh::to_vec(&args).unwrap(),
+        )
+        .unwrap();
+    let result = SubmitResult::try_from_slice(&outcome.return_data.as_value().unwrap()).unwrap();
+    assert!(result.status.is_ok());
+    runner.promise_results.clear();
+
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            contract.call_method_without_args("getLastResult", nonce)
+        })
+        .unwrap();
+    let output = ethabi::decode(
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Bool,
+            ethabi::ParamType::Bytes,
+        ],
+        &utils::unwrap_success(result),
+    )
+    .unwrap();
+    assert_eq!(
+        output,
+        vec![
+            ethabi::Token::Address(sender.raw()),
+            ethabi::Token::Uint(U256::one()),
+            ethabi::Token::Bool(true),
+            ethabi::Token::Bytes(b"hello".to_vec()),
+        ]
+    );
+
+    // The contract rejects the results which don't come from the precompile address.
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            contract.call_method_with_args(
+                "onXccResult",
+                &[
+                    ethabi::Token::Address(sender.raw()),
+                    ethabi::Token::Array(Vec::new()),
+                ],
+                nonce,
+            )
+        })
+        .unwrap();
+    assert!(!result.status.is_ok());
+}
+
+#[test]
+fn test_xcc_abi_encoded_input() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let constructor = utils::solidity::ContractConstructor::compile_from_source(
+        "../etc/eth-contracts/contracts",
+        "target/solidity_build",
+        "test/CrossContractCallTester.sol",
+        "CrossContractCallTester",
+    );
+    let nonce = signer.use_nonce();
+    let contract = runner.deploy_contract(
+        &signer.secret_key,
+        |c| c.deploy_without_constructor(nonce.into()),
+        constructor,
+    );
+
+    let call = |target: &str, method: &str| PromiseCreateArgs {
+        target_account_id: target.parse().unwrap(),
+        method: method.into(),
+        args: b"{}".to_vec(),
+        attached_balance: Yocto::new(1),
+        attached_gas: NearGas::new(5_000_000_000_000),
+    };
+    let batch = PromiseBatchAction {
+        target_account_id: "new.aurora".parse().unwrap(),
+        actions: vec![
+            PromiseAction::CreateAccount,
+            PromiseAction::Transfer {
+                amount: Yocto::new(1_000_000),
+            },
+            PromiseAction::AddFullAccessKey {
+                public_key: PublicKey::Ed25519([7; 32]),
+                nonce: 1,
+            },
+            PromiseAction::FunctionCall {
+                name: "new".into(),
+                args: b"{}".to_vec(),
+                attached_yocto: Yocto::new(0),
+                gas: NearGas::new(10_000_000_000_000),
+            },
+            PromiseAction::DeleteAccount {
+                beneficiary_id: "beneficiary.near".parse().unwrap(),
+            },
+        ],
+    };
+    let cases = [
+        (
+            "encodeFunctionCall",
+            CrossContractCallArgs::Eager(PromiseArgs::Create(call("a.near", "a"))),
+        ),
+        (
+            "encodeBatch",
+            CrossContractCallArgs::Delayed(PromiseArgs::Recursive(NearPromise::Simple(
+                SimpleNearPromise::Batch(batch.clone()),
+            ))),
+        ),
+        (
+            "encodePromise",
+            CrossContractCallArgs::Eager(PromiseArgs::Recursive(NearPromise::Then {
+                base: Box::new(NearPromise::And(vec![
+                    NearPromise::Simple(SimpleNearPromise::Create(call("a.near", "a"))),
+                    NearPromise::Then {
+                        base: Box::new(NearPromise::Simple(SimpleNearPromise::Create(call(
+                            "b.near", "b",
+                        )))),
+                        callback: SimpleNearPromise::Batch(batch),
+                    },
+                ])),
+                callback: SimpleNearPromise::Create(call("c.near", "c")),
+            })),
+        ),
+    ];
+
+    for (method, expected) in cases {
+        let result = runner
+            .submit_with_signer(&mut signer, |nonce| {
+                contract.call_method_without_args(method, nonce)
+            })
+            .unwrap();
+        let input = ethabi::decode(&[ethabi::ParamType::Bytes], &utils::unwrap_success(result))
+            .unwrap()
+            .pop()
+            .and_then(ethabi::Token::into_bytes)
+            .unwrap();
+
+        // Both encodings are decoded into the same arguments.
+        let expected = borsh::to_vec(&expected).unwrap();
+        let from_abi = xcc_abi::decode_args(&input).unwrap();
+        let from_borsh = xcc_abi::decode_args(&expected).unwrap();
+        assert_eq!(borsh::to_vec(&from_abi).unwrap(), expected);
+        assert_eq!(borsh::to_vec(&from_borsh).unwrap(), expected);
+    }
+}
+
 fn deploy_router() -> AuroraRunner {
     let mut router = AuroraRunner::default();
     router.set_code(ContractCode::new(contract_bytes(), None));
@@ -1060,6 +1060,7 @@ fn into_engine_error(gas_used: u64, aborted: &FunctionCallError) -> EngineError
                 "ERR_NOT_ALLOWED" => EngineErrorKind::NotAllowed,
                 "ERR_SAME_OWNER" => EngineErrorKind::SameOwner,
                 "ERR_FIXED_GAS_OVERFLOW" => EngineErrorKind::FixedGasOverflow,
+                "ERR_BLOCK_GAS_LIMIT_EXCEEDED" => EngineErrorKind::BlockGasLimitExceeded,
                 "ERR_PAUSED" => EngineErrorKind::EvmFatal(ExitFatal::Other("ERR_PAUSED".into())),
                 msg if msg.starts_with("ERR_INCORRECT_NONCE") => {
                     EngineErrorKind::IncorrectNonce(msg.to_string())
@@ -55,6 +55,8 @@ impl StandaloneRunner {
             hash: transaction_hash,
             info: tx_msg,
             diff: result.diff,
+            output: result.engine_output,
+            logs: result.logs,
             maybe_result: Ok(None),
         };
         self.cumulative_diff.append(outcome.diff.clone());
@@ -90,6 +92,8 @@ impl StandaloneRunner {
             hash: transaction_hash,
             info: tx_msg,
             diff: result.diff,
+            output: result.engine_output,
+            logs: result.logs,
             maybe_result: Ok(None),
         };
         self.cumulative_diff.append(outcome.diff.clone());
@@ -0,0 +1,367 @@
+//! Schemas of the [NEP-297](https://nomicon.io/Standards/EventsFormat) events emitted by the
+//! engine on admin and configuration changes.
+
+use crate::account_id::AccountId;
+use crate::parameters::connector::{Erc20Identifier, Erc20Metadata, PausedMask};
+use crate::parameters::engine::CoinbaseMode;
+use crate::parameters::governance::ProposalActionKind;
+use crate::parameters::roles::Role;
+use crate::parameters::silo::{SiloParamsArgs, WhitelistArgs, WhitelistKind};
+use crate::parameters::withdrawal_limits::{WithdrawalLimit, WithdrawalLimitTarget};
+use crate::public_key::PublicKey;
+use crate::types::{Address, EthGas};
+use crate::{format, String, ToString, Vec, H256};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the log line which contains an event.
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+/// Name of the standard of the events emitted by the engine.
+pub const EVENT_STANDARD: &str = "aurora_engine";
+/// Version of the event schemas below. Must be bumped on any breaking change of the schemas.
+pub const EVENT_VERSION: &str = "1.0.0";
+
+/// Events emitted by the engine on admin and configuration changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum AdminEvent {
+    ContractPaused,
+    ContractResumed,
+    PrecompilesPaused {
+        paused_mask: u32,
+    },
+    PrecompilesResumed {
+        paused_mask: u32,
+    },
+    OwnerChanged {
+        new_owner: AccountId,
+    },
+    UpgradeDelayBlocksChanged {
+        upgrade_delay_blocks: u64,
+    },
+    /// Code of the upgrade has been staged. The hash is the sha256 of the code.
+    UpgradeStaged {
+        code_hash: H256,
+        unlock_height: u64,
+    },
+    /// The staged code has been deployed. The hash is the sha256 of the code.
+    UpgradeDeployed {
+        code_hash: H256,
+    },
+    /// The staged code and the code hash commitment have been removed.
+    UpgradeCancelled,
+    /// The hash of the code which is going to be staged next has been committed to. The
+    /// commitment is removed if `code_hash` is `None`.
+    UpgradeCodeHashCommitted {
+        code_hash: Option<H256>,
+    },
+    CoinbaseModeChanged {
+        mode: CoinbaseMode,
+    },
+    /// The limit is removed if `block_gas_limit` is `None`.
+    BlockGasLimitChanged {
+        block_gas_limit: Option<EthGas>,
+    },
+    KeyManagerChanged {
+        key_manager: Option<AccountId>,
+    },
+    RelayerKeyAdded {
+        public_key: PublicKey,
+    },
+    RelayerKeyRemoved {
+        public_key: PublicKey,
+    },
+    RelayerRegistered {
+        account_id: AccountId,
+        address: Address,
+    },
+    /// The hashchain has been started from the hashchain of the block with the given height.
+    HashchainStarted {
+        block_height: u64,
+        block_hashchain: H256,
+    },
+    FixedGasChanged {
+        fixed_gas: Option<EthGas>,
+    },
+    /// Silo mode is turned off if both values are `None`.
+    SiloParamsChanged {
+        fixed_gas: Option<EthGas>,
+        erc20_fallback_address: Option<Address>,
+    },
+    WhitelistStatusChanged {
+        #[serde(with = "variant_name")]
+        kind: WhitelistKind,
+        active: bool,
+    },
+    /// The `entry` is either a NEAR account id or a hex-encoded EVM address.
+    WhitelistEntryAdded {
+        #[serde(with = "variant_name")]
+        kind: WhitelistKind,
+        entry: String,
+    },
+    WhitelistEntryRemoved {
+        #[serde(with = "variant_name")]
+        kind: WhitelistKind,
+        entry: String,
+    },
+    /// Bytecode of the XCC router has been updated. The hash is the sha256 of the bytecode.
+    RouterCodeUpdated {
+        code_hash: H256,
+    },
+    /// Bytecode of the ERC-721 template used to mirror NEP-171 tokens has been updated.
+    /// The hash is the sha256 of the bytecode.
+    Erc721TemplateUpdated {
+        code_hash: H256,
+    },
+    /// Bytecode of the ERC-1155 template used to mirror NEP-245 tokens has been updated.
+    /// The hash is the sha256 of the bytecode.
+    Erc1155TemplateUpdated {
+        code_hash: H256,
+    },
+    WnearAddressChanged {
+        address: Address,
+    },
+    EthConnectorAccountChanged {
+        account: AccountId,
+    },
+    ConnectorPausedFlagsChanged {
+        paused_mask: PausedMask,
+    },
+    Erc20MetadataChanged {
+        erc20_identifier: Erc20Identifier,
+        metadata: Erc20Metadata,
+    },
+    RoleGranted {
+        #[serde(with = "variant_name")]
+        role: Role,
+        account_id: AccountId,
+    },
+    RoleRevoked {
+        #[serde(with = "variant_name")]
+        role: Role,
+        account_id: AccountId,
+    },
+    GovernanceConfigChanged {
+        approvers: Vec<AccountId>,
+        threshold: u32,
+    },
+    ProposalCreated {
+        id: u64,
+        proposer: AccountId,
+        #[serde(with = "variant_name")]
+        action: ProposalActionKind,
+    },
+    ProposalApproved {
+        id: u64,
+        approver: AccountId,
+    },
+    ProposalExecuted {
+        id: u64,
+    },
+    ProposalCancelled {
+        id: u64,
+    },
+    /// The limit is removed if `limit` is `None`.
+    WithdrawalLimitChanged {
+        target: WithdrawalLimitTarget,
+        limit: Option<WithdrawalLimit>,
+    },
+    QueuedWithdrawalApproved {
+        id: u64,
+    },
+    /// The withdrawn tokens have been minted back to the sender.
+    QueuedWithdrawalRejected {
+        id: u64,
+    },
+}
+
+impl AdminEvent {
+    #[must_use]
+    pub fn silo_params_changed(args: Option<&SiloParamsArgs>) -> Self {
+        Self::SiloParamsChanged {
+            fixed_gas: args.map(|params| params.fixed_gas),
+            erc20_fallback_address: args.map(|params| params.erc20_fallback_address),
+        }
+    }
+
+    #[must_use]
+    pub fn whitelist_entry_added(args: &WhitelistArgs) -> Self {
+        let (kind, entry) = whitelist_entry(args);
+        Self::WhitelistEntryAdded { kind, entry }
+    }
+
+    #[must_use]
+    pub fn whitelist_entry_removed(args: &WhitelistArgs) -> Self {
+        let (kind, entry) = whitelist_entry(args);
+        Self::WhitelistEntryRemoved { kind, entry }
+    }
+
+    /// Returns the log line in the NEP-297 format: `EVENT_JSON:` followed by the JSON object
+    /// with the `standard`, `version`, `event` and `data` fields.
+    #[must_use]
+    pub fn to_log(&self) -> String {
+        let log = EventLogRef {
+            standard: EVENT_STANDARD,
+            version: EVENT_VERSION,
+            event: self,
+        };
+        // Serialization of the event into a string can't fail.
+        let json = serde_json::to_string(&log).unwrap_or_default();
+        format!("{EVENT_JSON_PREFIX}{json}")
+    }
+}
+
+/// The NEP-297 envelope of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    #[serde(flatten)]
+    pub event: AdminEvent,
+}
+
+impl EventLog {
+    /// Parses the log line produced by `AdminEvent::to_log`. Returns `None` if the log
+    /// isn't an event of the `aurora_engine` standard.
+    #[must_use]
+    pub fn parse(log: &str) -> Option<Self> {
+        let json = log.strip_prefix(EVENT_JSON_PREFIX)?;
+        serde_json::from_str::<Self>(json)
+            .ok()
+            .filter(|log| log.standard == EVENT_STANDARD)
+    }
+}
+
+#[derive(Serialize)]
+struct EventLogRef<'a> {
+    standard: &'a str,
+    version: &'a str,
+    #[serde(flatten)]
+    event: &'a AdminEvent,
+}
+
+/// `Role`, `WhitelistKind` and `ProposalActionKind` only derive serde with the `impl-serde`
+/// feature, so the events serialize them by the names of the variants like the derives do.
+mod variant_name {
+    use super::{ProposalActionKind, Role, WhitelistKind};
+    use crate::{format, String};
+    use core::fmt::Debug;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub trait Variants: Debug + Copy + 'static {
+        const ALL: &'static [Self];
+    }
+
+    impl Variants for Role {
+        const ALL: &'static [Self] = &Self::ALL;
+    }
+
+    impl Variants for WhitelistKind {
+        const ALL: &'static [Self] = &[Self::Admin, Self::EvmAdmin, Self::Account, Self::Address];
+    }
+
+    impl Variants for ProposalActionKind {
+        const ALL: &'static [Self] = &[
+            Self::SetOwner,
+            Self::SetUpgradeDelayBlocks,
+            Self::SetSiloParams,
+            Self::FactoryUpdate,
+            Self::SetEthConnectorContractAccount,
+            Self::SetKeyManager,
+            Self::GrantRole,
+            Self::RevokeRole,
+            Self::UpdateGovernanceConfig,
+        ];
+    }
+
+    pub fn serialize<T: Variants, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{value:?}"))
+    }
+
+    pub fn deserialize<'de, T: Variants, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        T::ALL
+            .iter()
+            .copied()
+            .find(|value| format!("{value:?}") == name)
+            .ok_or_else(|| D::Error::custom(format!("unknown variant `{name}`")))
+    }
+}
+
+fn whitelist_entry(args: &WhitelistArgs) -> (WhitelistKind, String) {
+    match args {
+        WhitelistArgs::WhitelistAddressArgs(args) => {
+            (args.kind, format!("0x{}", args.address.encode()))
+        }
+        WhitelistArgs::WhitelistAccountArgs(args) => (args.kind, args.account_id.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_log_format() {
+        let event = AdminEvent::OwnerChanged {
+            new_owner: "new_owner.near".parse().unwrap(),
+        };
+        assert_eq!(
+            event.to_log(),
+            r#"EVENT_JSON:{"standard":"aurora_engine","version":"1.0.0","event":"owner_changed","data":{"new_owner":"new_owner.near"}}"#
+        );
+        assert_eq!(
+            AdminEvent::ContractPaused.to_log(),
+            r#"EVENT_JSON:{"standard":"aurora_engine","version":"1.0.0","event":"contract_paused"}"#
+        );
+        let event = AdminEvent::RoleGranted {
+            role: Role::Pauser,
+            account_id: "pauser.near".parse().unwrap(),
+        };
+        assert_eq!(
+            event.to_log(),
+            r#"EVENT_JSON:{"standard":"aurora_engine","version":"1.0.0","event":"role_granted","data":{"role":"Pauser","account_id":"pauser.near"}}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_event_log() {
+        let events = [
+            AdminEvent::ContractResumed,
+            AdminEvent::UpgradeCancelled,
+            AdminEvent::UpgradeCodeHashCommitted {
+                code_hash: Some(H256::repeat_byte(1)),
+            },
+            AdminEvent::RoleGranted {
+                role: Role::Pauser,
+                account_id: "pauser.near".parse().unwrap(),
+            },
+            AdminEvent::silo_params_changed(None),
+            AdminEvent::CoinbaseModeChanged {
+                mode: CoinbaseMode::Treasury(Address::zero()),
+            },
+            AdminEvent::whitelist_entry_added(&WhitelistArgs::WhitelistAddressArgs(
+                crate::parameters::silo::WhitelistAddressArgs {
+                    kind: WhitelistKind::Address,
+                    address: Address::zero(),
+                },
+            )),
+        ];
+
+        for event in events {
+            let log = EventLog::parse(&event.to_log()).unwrap();
+            assert_eq!(log.version, EVENT_VERSION);
+            assert_eq!(log.event, event);
+        }
+
+        assert!(EventLog::parse("Some other log").is_none());
+        assert!(EventLog::parse(
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"contract_paused"}"#
+        )
+        .is_none());
+    }
+}
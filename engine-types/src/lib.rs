@@ -7,6 +7,7 @@
 )]
 
 pub mod account_id;
+pub mod events;
 pub mod parameters;
 pub mod public_key;
 pub mod storage;
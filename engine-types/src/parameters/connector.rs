@@ -146,6 +146,15 @@ pub struct NEP141FtOnTransferArgs {
     pub msg: String,
 }
 
+/// JSON-encoded parameters of the `ft_transfer_call` function (NEP-141).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP141FtTransferCallArgs {
+    pub receiver_id: AccountId,
+    pub amount: Balance,
+    pub memo: Option<String>,
+    pub msg: String,
+}
+
 #[derive(BorshSerialize)]
 pub struct EngineWithdrawCallArgs {
     pub sender_id: AccountId,
@@ -333,4 +342,19 @@ mod tests {
 
         assert_eq!(expected_log_entry, actual_log_entry);
     }
+
+    #[test]
+    fn test_serialize_ft_transfer_call_args() {
+        let args = NEP141FtTransferCallArgs {
+            receiver_id: "pool.near".parse().unwrap(),
+            amount: Balance::new(100),
+            memo: None,
+            msg: r#"{"pool_id": 1}"#.to_string(),
+        };
+        // The message is escaped, so it can't inject fields in the arguments.
+        assert_eq!(
+            serde_json::to_string(&args).unwrap(),
+            r#"{"receiver_id":"pool.near","amount":"100","memo":null,"msg":"{\"pool_id\": 1}"}"#
+        );
+    }
 }
@@ -1,8 +1,8 @@
 use crate::{
     account_id::AccountId,
     public_key::PublicKey,
-    types::{Address, RawH256, RawU256, WeiU256, Yocto},
-    Vec,
+    types::{Address, EthGas, RawH256, RawU256, WeiU256, Yocto},
+    String, Vec,
 };
 use borsh::{io, BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
@@ -127,6 +127,46 @@ pub struct SetUpgradeDelayBlocksArgs {
     pub upgrade_delay_blocks: u64,
 }
 
+/// Source of the address returned by the `COINBASE` opcode.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinbaseMode {
+    /// The EVM address of the Aurora account `0x4444588443C3a91288c5002483449Aba1054192b`.
+    #[default]
+    Fixed,
+    /// The EVM address registered by the relayer of the transaction with `register_relayer`.
+    /// Falls back to the fixed address if the relayer hasn't registered one.
+    Relayer,
+    /// The provided treasury address.
+    Treasury(Address),
+}
+
+/// Borsh-encoded parameters for the `set_coinbase_mode` function.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(Serialize, Deserialize))]
+pub struct SetCoinbaseModeArgs {
+    pub mode: CoinbaseMode,
+}
+
+/// Borsh-encoded parameters for the `set_block_gas_limit` function.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(Serialize, Deserialize))]
+pub struct SetBlockGasLimitArgs {
+    /// The limit is removed if the value is `None`.
+    pub block_gas_limit: Option<EthGas>,
+}
+
 /// Borsh-encoded submit arguments used by the `submit_with_args` function.
 #[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub struct SubmitArgs {
@@ -355,6 +395,29 @@ pub struct DeployErc20TokenArgs {
 /// Borsh-encoded parameters for `get_erc20_from_nep141` function.
 pub type GetErc20FromNep141CallArgs = DeployErc20TokenArgs;
 
+/// Borsh-encoded parameters for `deploy_erc20_tokens` function.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub struct DeployErc20TokensArgs {
+    pub nep141s: Vec<AccountId>,
+}
+
+/// Outcome of the deployment of one token by `deploy_erc20_tokens`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub struct DeployErc20TokenResult {
+    pub nep141: AccountId,
+    pub status: Erc20DeployStatus,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub enum Erc20DeployStatus {
+    /// The ERC-20 token is deployed and its metadata is set from `ft_metadata`.
+    Deployed(Address),
+    /// The ERC-20 token is deployed, but its metadata couldn't be fetched or set.
+    DeployedWithoutMetadata(Address),
+    /// The ERC-20 token isn't deployed, e.g. because the NEP-141 token is already registered.
+    Failed(String),
+}
+
 /// Borsh-encoded parameters for the `get_storage_at` function.
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct GetStorageAtArgs {
@@ -396,6 +459,28 @@ pub struct UpgradeParams {
     pub state_migration_gas: Option<u64>,
 }
 
+/// Borsh-encoded parameters for the `commit_upgrade_code_hash` function.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(Serialize, Deserialize))]
+pub struct CommitUpgradeCodeHashArgs {
+    /// SHA-256 hash of the code which is going to be staged. `None` removes the commitment.
+    pub code_hash: Option<RawH256>,
+}
+
+/// Borsh-encoded information about the staged upgrade returned by the `get_staged_upgrade` function.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(Serialize, Deserialize))]
+pub struct StagedUpgradeInfo {
+    /// SHA-256 hash of the staged code.
+    pub code_hash: RawH256,
+    /// Size of the staged code in bytes.
+    pub code_size: u64,
+    /// Block height after which the staged code can be deployed.
+    pub unlock_height: u64,
+    /// SHA-256 hash the staged code was committed to before staging, if any.
+    pub committed_code_hash: Option<RawH256>,
+}
+
 mod chain_id_deserialize {
     use crate::types::{u256_to_arr, RawU256};
     use primitive_types::U256;
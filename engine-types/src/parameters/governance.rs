@@ -0,0 +1,149 @@
+use crate::account_id::AccountId;
+use crate::borsh::{self, BorshDeserialize, BorshSerialize};
+use crate::parameters::connector::SetEthConnectorContractAccountArgs;
+use crate::parameters::engine::{RelayerKeyManagerArgs, SetOwnerArgs, SetUpgradeDelayBlocksArgs};
+use crate::parameters::roles::RoleArgs;
+use crate::parameters::silo::SiloParamsArgs;
+use crate::Vec;
+
+/// Configuration changes which could be applied by executing an approved proposal.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ProposalAction {
+    SetOwner(SetOwnerArgs),
+    SetUpgradeDelayBlocks(SetUpgradeDelayBlocksArgs),
+    SetSiloParams(Option<SiloParamsArgs>),
+    /// Update the bytecode of the XCC router.
+    FactoryUpdate(Vec<u8>),
+    SetEthConnectorContractAccount(SetEthConnectorContractAccountArgs),
+    SetKeyManager(RelayerKeyManagerArgs),
+    GrantRole(RoleArgs),
+    RevokeRole(RoleArgs),
+    UpdateGovernanceConfig(GovernanceConfig),
+}
+
+impl ProposalAction {
+    #[must_use]
+    pub const fn kind(&self) -> ProposalActionKind {
+        match self {
+            Self::SetOwner(_) => ProposalActionKind::SetOwner,
+            Self::SetUpgradeDelayBlocks(_) => ProposalActionKind::SetUpgradeDelayBlocks,
+            Self::SetSiloParams(_) => ProposalActionKind::SetSiloParams,
+            Self::FactoryUpdate(_) => ProposalActionKind::FactoryUpdate,
+            Self::SetEthConnectorContractAccount(_) => {
+                ProposalActionKind::SetEthConnectorContractAccount
+            }
+            Self::SetKeyManager(_) => ProposalActionKind::SetKeyManager,
+            Self::GrantRole(_) => ProposalActionKind::GrantRole,
+            Self::RevokeRole(_) => ProposalActionKind::RevokeRole,
+            Self::UpdateGovernanceConfig(_) => ProposalActionKind::UpdateGovernanceConfig,
+        }
+    }
+}
+
+/// Kind of the `ProposalAction` without its arguments. Used for configuring timelocks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+#[borsh(use_discriminant = false)]
+pub enum ProposalActionKind {
+    SetOwner = 0x0,
+    SetUpgradeDelayBlocks = 0x1,
+    SetSiloParams = 0x2,
+    FactoryUpdate = 0x3,
+    SetEthConnectorContractAccount = 0x4,
+    SetKeyManager = 0x5,
+    GrantRole = 0x6,
+    RevokeRole = 0x7,
+    UpdateGovernanceConfig = 0x8,
+}
+
+/// Timelock of a specific kind of actions.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ActionTimelock {
+    pub kind: ProposalActionKind,
+    pub delay_blocks: u64,
+}
+
+/// Set of accounts allowed to approve proposals, the number of approvals required to execute
+/// a proposal and the timelocks applied after a proposal has been approved.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct GovernanceConfig {
+    pub approvers: Vec<AccountId>,
+    pub threshold: u32,
+    /// Timelock in blocks for the actions which aren't listed in `action_timelocks`.
+    pub default_timelock_blocks: u64,
+    pub action_timelocks: Vec<ActionTimelock>,
+}
+
+impl GovernanceConfig {
+    /// Returns the number of blocks which should pass between approval and execution
+    /// of the action of the provided kind.
+    #[must_use]
+    pub fn timelock_blocks(&self, kind: ProposalActionKind) -> u64 {
+        self.action_timelocks
+            .iter()
+            .find(|timelock| timelock.kind == kind)
+            .map_or(self.default_timelock_blocks, |timelock| {
+                timelock.delay_blocks
+            })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ProposalStatus {
+    /// The proposal is waiting for approvals or for the timelock to pass.
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// A proposal stored in the proposal log. The approvers, the threshold and the timelock are
+/// taken from the governance config at the moment the proposal is created.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Proposal {
+    pub proposer: AccountId,
+    pub action: ProposalAction,
+    pub approvers: Vec<AccountId>,
+    pub threshold: u32,
+    pub timelock_blocks: u64,
+    pub approvals: Vec<AccountId>,
+    /// Block height of the proposal creation.
+    pub created_at: u64,
+    /// Block height when the threshold of approvals has been reached.
+    pub approved_at: Option<u64>,
+    pub status: ProposalStatus,
+}
+
+impl Proposal {
+    /// Returns the block height starting from which the proposal can be executed.
+    #[must_use]
+    pub fn unlock_height(&self) -> Option<u64> {
+        self.approved_at
+            .map(|height| height.saturating_add(self.timelock_blocks))
+    }
+}
+
+/// Borsh-encoded parameters for the `approve_proposal`, `execute_proposal`, `cancel_proposal`
+/// and `get_proposal` functions.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProposalIdArgs {
+    pub id: u64,
+}
+
+#[test]
+fn test_timelock_blocks() {
+    let config = GovernanceConfig {
+        approvers: Vec::new(),
+        threshold: 1,
+        default_timelock_blocks: 10,
+        action_timelocks: crate::vec![ActionTimelock {
+            kind: ProposalActionKind::SetOwner,
+            delay_blocks: 100,
+        }],
+    };
+
+    assert_eq!(config.timelock_blocks(ProposalActionKind::SetOwner), 100);
+    assert_eq!(
+        config.timelock_blocks(ProposalActionKind::FactoryUpdate),
+        10
+    );
+}
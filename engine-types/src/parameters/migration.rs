@@ -0,0 +1,84 @@
+use crate::borsh::{self, BorshDeserialize, BorshSerialize};
+use crate::types::Address;
+use crate::Vec;
+
+/// Engine-owned storage records with versioned schemas. The version of each record is
+/// increased by the migration steps applied in `state_migration`. The records start at
+/// version 1, which is the layout used before the versions have been introduced.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+#[borsh(use_discriminant = false)]
+pub enum StorageRecord {
+    /// The `EngineState` stored under the `STATE` key.
+    EngineState = 0x0,
+    /// Contract code of the accounts.
+    Code = 0x1,
+    /// Records of the eth connector and the NEP-141 <-> ERC-20 mappings.
+    Connector = 0x2,
+    /// Bytecode and versions of the XCC router and the wNEAR address.
+    Xcc = 0x3,
+    /// Silo parameters and whitelists.
+    Silo = 0x4,
+    /// Relayer addresses, function call keys and the key manager.
+    Relayer = 0x5,
+}
+
+impl StorageRecord {
+    /// All existing records.
+    pub const ALL: [Self; 6] = [
+        Self::EngineState,
+        Self::Code,
+        Self::Connector,
+        Self::Xcc,
+        Self::Silo,
+        Self::Relayer,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordVersion {
+    pub record: StorageRecord,
+    pub version: u32,
+}
+
+/// Optional borsh-encoded input of the `state_migration` function.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateMigrationArgs {
+    /// Addresses whose code is stored in the layout preceding the code deduplication.
+    /// The contract can't enumerate them, so they are provided by the caller.
+    pub code_addresses: Vec<Address>,
+}
+
+/// Position of the migration step which has been suspended because of the lack of gas.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationCursor {
+    pub record: StorageRecord,
+    /// Version of the record which the suspended step migrates to.
+    pub to_version: u32,
+    /// Number of items already migrated by the step.
+    pub position: u64,
+}
+
+/// Borsh-encoded result of the `state_migration` and `get_migration_progress` functions.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationProgress {
+    /// Versions of the records in the storage.
+    pub versions: Vec<RecordVersion>,
+    /// Versions of the records expected by the deployed code.
+    pub latest_versions: Vec<RecordVersion>,
+    /// Number of the migration steps which haven't been completed yet.
+    pub pending_steps: u32,
+    /// Set if the migration has been suspended in the middle of a step.
+    pub cursor: Option<MigrationCursor>,
+}
+
+impl MigrationProgress {
+    #[must_use]
+    pub const fn is_completed(&self) -> bool {
+        self.pending_steps == 0
+    }
+}
@@ -2,6 +2,12 @@ pub use promise::*;
 
 pub mod connector;
 pub mod engine;
+pub mod governance;
+pub mod migration;
+pub mod mt;
+pub mod nft;
 pub mod promise;
+pub mod roles;
 pub mod silo;
+pub mod withdrawal_limits;
 pub mod xcc;
@@ -0,0 +1,67 @@
+use crate::account_id::AccountId;
+use crate::borsh::{BorshDeserialize, BorshSerialize};
+use crate::types::{Address, Balance, RawU256};
+use crate::{String, Vec};
+use serde::{Deserialize, Serialize};
+
+/// JSON-encoded parameters of the `mt_on_transfer` function (NEP-245).
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP245MtOnTransferArgs {
+    pub sender_id: AccountId,
+    pub previous_owner_ids: Vec<AccountId>,
+    pub token_ids: Vec<String>,
+    pub amounts: Vec<Balance>,
+    /// The recipient of the tokens on Aurora: 40 characters of the hex-encoded address.
+    pub msg: String,
+}
+
+/// JSON-encoded parameters of the `mt_transfer` function (NEP-245).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP245MtTransferArgs {
+    pub receiver_id: AccountId,
+    pub token_id: String,
+    pub amount: Balance,
+    pub approval: Option<(AccountId, u64)>,
+    pub memo: Option<String>,
+}
+
+/// JSON-encoded parameters of the `mt_batch_transfer` function (NEP-245).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP245MtBatchTransferArgs {
+    pub receiver_id: AccountId,
+    pub token_ids: Vec<String>,
+    pub amounts: Vec<Balance>,
+    pub approvals: Option<Vec<Option<(AccountId, u64)>>>,
+    pub memo: Option<String>,
+}
+
+/// Borsh-encoded parameters of the `mt_exit_callback` function, used to mint the withdrawn
+/// tokens back if the transfer on NEAR failed.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct MtExitCallbackArgs {
+    pub refund_address: Address,
+    pub erc1155: Address,
+    pub token_ids: Vec<RawU256>,
+    pub amounts: Vec<RawU256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec;
+
+    #[test]
+    fn test_serialize_mt_batch_transfer_args() {
+        let args = NEP245MtBatchTransferArgs {
+            receiver_id: "bob.near".parse().unwrap(),
+            token_ids: vec!["sword".into(), "shield\"".into()],
+            amounts: vec![Balance::new(1), Balance::new(10)],
+            approvals: None,
+            memo: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&args).unwrap(),
+            r#"{"receiver_id":"bob.near","token_ids":["sword","shield\""],"amounts":["1","10"],"approvals":null,"memo":null}"#
+        );
+    }
+}
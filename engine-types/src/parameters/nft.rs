@@ -0,0 +1,85 @@
+use crate::account_id::AccountId;
+use crate::borsh::{BorshDeserialize, BorshSerialize};
+use crate::{String, ToString};
+use serde::{Deserialize, Serialize};
+
+/// JSON-encoded parameters of the `nft_on_transfer` function (NEP-171).
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP171NftOnTransferArgs {
+    pub sender_id: AccountId,
+    pub previous_owner_id: AccountId,
+    pub token_id: String,
+    /// The recipient of the token on Aurora: 40 characters of the hex-encoded address.
+    pub msg: String,
+}
+
+/// JSON-encoded parameters of the `nft_transfer` function (NEP-171).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NEP171NftTransferArgs {
+    pub receiver_id: AccountId,
+    pub token_id: String,
+    pub approval_id: Option<u64>,
+    pub memo: Option<String>,
+}
+
+/// Metadata of the ERC-721 contract, synchronized with the result of `nft_metadata` (NEP-177)
+/// of the NEP-171 contract. The other fields of the NEP-177 metadata are ignored.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Erc721Metadata {
+    pub name: String,
+    pub symbol: String,
+    /// Prefix of the URI of the tokens, the URI of the token is the concatenation of
+    /// the prefix and the NEAR token id.
+    #[serde(default)]
+    pub base_uri: Option<String>,
+}
+
+impl Erc721Metadata {
+    /// The metadata the ERC-721 contract is deployed with until the metadata of the NEP-171
+    /// contract is received.
+    #[must_use]
+    pub fn from_account_id(nep171: &AccountId) -> Self {
+        Self {
+            name: nep171.to_string(),
+            symbol: nep171.to_string(),
+            base_uri: None,
+        }
+    }
+}
+
+/// Borsh-encoded parameters of the `sync_erc721_metadata` function and of its callback.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct SyncErc721MetadataArgs {
+    pub nep171: AccountId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_nep177_metadata() {
+        let json = r#"{
+            "spec": "nft-1.0.0",
+            "name": "Collection",
+            "symbol": "COL",
+            "icon": null,
+            "base_uri": "https://example.com/",
+            "reference": null,
+            "reference_hash": null
+        }"#;
+        let metadata: Erc721Metadata = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            metadata,
+            Erc721Metadata {
+                name: "Collection".into(),
+                symbol: "COL".into(),
+                base_uri: Some("https://example.com/".into()),
+            }
+        );
+
+        let json = r#"{"spec": "nft-1.0.0", "name": "Collection", "symbol": "COL"}"#;
+        let metadata: Erc721Metadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.base_uri, None);
+    }
+}
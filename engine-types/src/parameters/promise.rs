@@ -311,4 +311,28 @@ pub enum CrossContractCallArgs {
     /// much gas left to perform it. In this case, the promise could be `Delayed` (stored in the router)
     /// and executed in a separate transaction with a fresh 300 Tgas available for it.
     Delayed(PromiseArgs),
+    /// Same as `Eager`, but once the promise resolves the engine calls the EVM contract
+    /// with the results of the promise.
+    EagerWithCallback(PromiseArgs, EvmCallback),
+    /// Same as `Delayed`, but the promise can't be executed after the provided block height.
+    /// Once the deadline has passed the promise can be cancelled by anyone.
+    DelayedWithDeadline(PromiseArgs, u64),
+    /// Cancels the promise stored in the router with the provided nonce. The NEAR attached
+    /// to the promise is returned to the address as wNEAR.
+    CancelScheduled(u64),
+    /// Deletes the router account of the address. The wNEAR owned by the router is unwrapped
+    /// and the whole balance is transferred to the provided beneficiary account.
+    CloseAccount(AccountId),
+}
+
+/// EVM contract method the engine calls with the results of the cross contract call.
+/// The calldata is the `selector` followed by the ABI-encoded `(address, (bool, bytes)[])`
+/// tuple: the address which made the cross contract call and the success flag and output
+/// of each promise result. The call is made from the cross contract call precompile address.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct EvmCallback {
+    pub address: Address,
+    pub selector: [u8; 4],
+    /// NEAR gas attached to the engine's callback which executes the EVM call.
+    pub gas: NearGas,
 }
@@ -0,0 +1,61 @@
+use crate::account_id::AccountId;
+use crate::borsh::{self, BorshDeserialize, BorshSerialize};
+
+/// Roles which could be granted by the owner of the contract to NEAR accounts. Each role allows
+/// calling a subset of the admin methods of the contract. The owner implicitly has all the roles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+#[borsh(use_discriminant = true)]
+pub enum Role {
+    /// Allows pausing and resuming the contract and precompiles.
+    Pauser = 0x0,
+    /// Allows staging and applying upgrades of the contract and the XCC router.
+    Upgrader = 0x1,
+    /// Allows changing the silo parameters and whitelists.
+    SiloAdmin = 0x2,
+    /// Allows setting the relayer key manager.
+    RelayerKeyManager = 0x3,
+    /// Allows changing the eth connector and ERC-20 metadata settings.
+    ConnectorAdmin = 0x4,
+    /// Allows changing the withdrawal limits and approving or rejecting the queued withdrawals.
+    RiskAdmin = 0x5,
+}
+
+impl Role {
+    /// All existing roles.
+    pub const ALL: [Self; 6] = [
+        Self::Pauser,
+        Self::Upgrader,
+        Self::SiloAdmin,
+        Self::RelayerKeyManager,
+        Self::ConnectorAdmin,
+        Self::RiskAdmin,
+    ];
+}
+
+/// Arguments for granting or revoking a role.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoleArgs {
+    pub role: Role,
+    pub account_id: AccountId,
+}
+
+/// Arguments for getting the list of accounts which have the role.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "impl-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoleKindArgs {
+    pub role: Role,
+}
+
+#[test]
+fn test_role_args_serialize() {
+    let args = RoleArgs {
+        role: Role::ConnectorAdmin,
+        account_id: "admin.aurora".parse().unwrap(),
+    };
+    let bytes = borsh::to_vec(&args).unwrap();
+
+    assert_eq!(bytes[0], Role::ConnectorAdmin as u8);
+    assert_eq!(RoleArgs::try_from_slice(&bytes).unwrap(), args);
+}
@@ -0,0 +1,253 @@
+//! Rolling-window limits of the withdrawals made through the exit precompiles.
+
+use crate::borsh::{self, BorshDeserialize, BorshSerialize};
+use crate::parameters::{PromiseArgs, RefundCallArgs};
+use crate::types::Address;
+use crate::Vec;
+
+/// Withdrawals covered by a limit.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalLimitTarget {
+    /// All the withdrawals of ETH, ERC-20 and ERC-1155 tokens. The amounts of the different
+    /// tokens are summed in their base units, so the global limit is meant as a backstop.
+    Global,
+    /// Withdrawals of ETH, in wei.
+    Eth,
+    /// Withdrawals of the ERC-20 token mirroring a NEP-141 token, in the token base units.
+    Erc20(Address),
+    /// Withdrawals of the ERC-1155 contract mirroring a NEP-245 contract, the amounts of
+    /// all the token ids are summed.
+    Erc1155(Address),
+}
+
+impl WithdrawalLimitTarget {
+    /// Bytes identifying the target in the storage keys.
+    #[must_use]
+    pub fn to_key_bytes(&self) -> Vec<u8> {
+        borsh::to_vec(self).unwrap()
+    }
+}
+
+/// What happens to a withdrawal exceeding the limit.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ExceedAction {
+    /// The exit fails and the transaction is reverted.
+    Reject,
+    /// The exit succeeds but the transfer on NEAR waits for the approval of an admin.
+    Queue,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct WithdrawalLimit {
+    /// Maximum amount withdrawn over the rolling window.
+    pub max_amount: u128,
+    /// Length of the rolling window in seconds.
+    pub window_secs: u64,
+    pub on_exceed: ExceedAction,
+    /// The exit precompiles are paused once the amount withdrawn (or queued) over the
+    /// rolling window crosses the threshold.
+    pub pause_threshold: Option<u128>,
+}
+
+impl WithdrawalLimit {
+    /// Returns the action to take if withdrawing `amount` on top of `usage` exceeds the limit.
+    #[must_use]
+    pub fn check(&self, usage: &WithdrawalUsage, now: u64, amount: u128) -> Option<ExceedAction> {
+        let used = usage.amount(self.window_secs, now);
+        (used.saturating_add(amount) > self.max_amount).then_some(self.on_exceed)
+    }
+
+    /// Checks if the `usage` crossed the pause threshold.
+    #[must_use]
+    pub fn should_pause(&self, usage: &WithdrawalUsage, now: u64) -> bool {
+        self.pause_threshold.map_or(false, |threshold| {
+            usage.amount(self.window_secs, now) > threshold
+        })
+    }
+}
+
+/// Borsh-encoded parameters of the `set_withdrawal_limit` function.
+/// The limit of the target is removed if `limit` is `None`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SetWithdrawalLimitArgs {
+    pub target: WithdrawalLimitTarget,
+    pub limit: Option<WithdrawalLimit>,
+}
+
+/// Amount withdrawn over the rolling window. The window is approximated with two fixed
+/// windows: the amount of the previous one is weighted by its overlap with the rolling window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawalUsage {
+    /// Start of the current fixed window, in seconds.
+    pub window_start: u64,
+    pub previous: u128,
+    pub current: u128,
+}
+
+impl WithdrawalUsage {
+    /// Moves the fixed windows forward to the one containing `now`.
+    #[must_use]
+    pub const fn at(self, window_secs: u64, now: u64) -> Self {
+        let window_secs = if window_secs == 0 { 1 } else { window_secs };
+        let window_start = now - now % window_secs;
+        if window_start <= self.window_start {
+            self
+        } else if window_start - self.window_start == window_secs {
+            Self {
+                window_start,
+                previous: self.current,
+                current: 0,
+            }
+        } else {
+            Self {
+                window_start,
+                previous: 0,
+                current: 0,
+            }
+        }
+    }
+
+    /// Estimated amount withdrawn over the rolling window ending at `now`.
+    #[must_use]
+    pub fn amount(&self, window_secs: u64, now: u64) -> u128 {
+        let usage = self.at(window_secs, now);
+        let window_secs = window_secs.max(1);
+        let elapsed = now.saturating_sub(usage.window_start);
+        let overlap = u128::from(window_secs.saturating_sub(elapsed));
+        let previous = usage.previous.saturating_mul(overlap) / u128::from(window_secs);
+        previous.saturating_add(usage.current)
+    }
+
+    /// Adds the withdrawn `amount` to the current window.
+    #[must_use]
+    pub const fn record(self, window_secs: u64, now: u64, amount: u128) -> Self {
+        let mut usage = self.at(window_secs, now);
+        usage.current = usage.current.saturating_add(amount);
+        usage
+    }
+
+    /// Removes the `amount` recorded at `timestamp`, e.g. once the withdrawal is rejected.
+    /// Nothing changes if the window of the withdrawal is over.
+    #[must_use]
+    pub const fn release(self, window_secs: u64, timestamp: u64, amount: u128) -> Self {
+        let window_secs = if window_secs == 0 { 1 } else { window_secs };
+        let window_start = timestamp - timestamp % window_secs;
+        let mut usage = self;
+        if window_start == self.window_start {
+            usage.current = usage.current.saturating_sub(amount);
+        } else if window_start.saturating_add(window_secs) == self.window_start {
+            usage.previous = usage.previous.saturating_sub(amount);
+        }
+        usage
+    }
+}
+
+/// Withdrawal made through an exit precompile. The precompiles pass it to the engine in
+/// a log, so the usage is only updated by successful transactions.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawalRecord {
+    /// Either `Eth`, `Erc20` or `Erc1155`, the withdrawal is also accounted in the `Global`
+    /// target.
+    pub target: WithdrawalLimitTarget,
+    pub amount: u128,
+    /// Block timestamp in seconds.
+    pub timestamp: u64,
+}
+
+/// Withdrawal exceeding a limit with the `Queue` action. The promise is created once
+/// the withdrawal is approved by an admin.
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+pub struct QueuedWithdrawal {
+    /// The withdrawal is removed from the usage of the limits if it is rejected.
+    pub record: WithdrawalRecord,
+    pub promise: PromiseArgs,
+    /// The withdrawn tokens are minted back to the sender if the withdrawal is rejected,
+    /// so the exits which can't be refunded are never queued.
+    pub refund: RefundCallArgs,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: u64 = 100;
+
+    #[test]
+    fn test_usage_rolling_window() {
+        let usage = WithdrawalUsage::default().record(WINDOW, 1_050, 10);
+        assert_eq!(usage.window_start, 1_000);
+        assert_eq!(usage.amount(WINDOW, 1_099), 10);
+
+        // The previous window is weighted by its overlap with the rolling window.
+        let usage = usage.record(WINDOW, 1_125, 4);
+        assert_eq!(usage.previous, 10);
+        assert_eq!(usage.amount(WINDOW, 1_125), 7 + 4);
+        assert_eq!(usage.amount(WINDOW, 1_175), 2 + 4);
+        // Both windows are over.
+        assert_eq!(usage.amount(WINDOW, 1_300), 0);
+    }
+
+    #[test]
+    fn test_usage_release() {
+        let usage = WithdrawalUsage::default()
+            .record(WINDOW, 1_050, 10)
+            .record(WINDOW, 1_125, 4);
+
+        assert_eq!(usage.release(WINDOW, 1_130, 3).current, 1);
+        assert_eq!(usage.release(WINDOW, 1_050, 3).previous, 7);
+        // The amount can't go below zero.
+        assert_eq!(usage.release(WINDOW, 1_130, 5).current, 0);
+        // The window of the withdrawal is over.
+        assert_eq!(usage.release(WINDOW, 950, 3), usage);
+    }
+
+    #[test]
+    fn test_limit_check() {
+        let limit = WithdrawalLimit {
+            max_amount: 100,
+            window_secs: WINDOW,
+            on_exceed: ExceedAction::Queue,
+            pause_threshold: Some(150),
+        };
+        let usage = WithdrawalUsage::default().record(WINDOW, 0, 90);
+        assert_eq!(limit.check(&usage, 10, 10), None);
+        assert_eq!(limit.check(&usage, 10, 11), Some(ExceedAction::Queue));
+        assert!(!limit.should_pause(&usage, 10));
+        assert!(limit.should_pause(&usage.record(WINDOW, 10, 61), 10));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::account_id::AccountId;
 use crate::borsh::{BorshDeserialize, BorshSerialize};
+use crate::parameters::promise::{EvmCallback, PromiseArgs};
 use crate::types::{Address, Yocto};
 
 #[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
@@ -20,6 +21,23 @@ pub struct WithdrawWnearToRouterArgs {
     pub amount: Yocto,
 }
 
+/// Args of the engine's `xcc_evm_callback` function, called once the promise scheduled by
+/// the cross contract call precompile resolves.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct XccEvmCallbackArgs {
+    /// Address which called the cross contract call precompile.
+    pub sender: Address,
+    pub callback: EvmCallback,
+}
+
+/// Args of the router's `schedule_with_deadline` function.
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub struct ScheduleWithDeadlineArgs {
+    pub promise: PromiseArgs,
+    /// The last block height when the promise can be executed.
+    pub deadline: u64,
+}
+
 /// Type wrapper for version of router contracts.
 #[derive(
     Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, BorshDeserialize, BorshSerialize,
@@ -34,6 +34,16 @@ pub enum KeyPrefix {
     Hashchain = 0xc,
     Silo = 0xd,
     Whitelist = 0xe,
+    Nep171Erc721Map = 0xf,
+    Erc721Nep171Map = 0x10,
+    Nft = 0x11,
+    Nep245Erc1155Map = 0x12,
+    Erc1155Nep245Map = 0x13,
+    MultiToken = 0x14,
+    WithdrawalLimits = 0x15,
+    CodeStore = 0x18,
+    BlockGasUsed = 0x19,
+    Account = 0x1a,
 }
 
 impl From<KeyPrefix> for u8 {
@@ -54,6 +64,16 @@ impl From<KeyPrefix> for u8 {
             KeyPrefix::Hashchain => 0xc,
             KeyPrefix::Silo => 0xd,
             KeyPrefix::Whitelist => 0xe,
+            KeyPrefix::Nep171Erc721Map => 0xf,
+            KeyPrefix::Erc721Nep171Map => 0x10,
+            KeyPrefix::Nft => 0x11,
+            KeyPrefix::Nep245Erc1155Map => 0x12,
+            KeyPrefix::Erc1155Nep245Map => 0x13,
+            KeyPrefix::MultiToken => 0x14,
+            KeyPrefix::WithdrawalLimits => 0x15,
+            KeyPrefix::CodeStore => 0x18,
+            KeyPrefix::BlockGasUsed => 0x19,
+            KeyPrefix::Account => 0x1a,
         }
     }
 }
@@ -109,6 +129,16 @@ impl From<KeyPrefixU8> for KeyPrefix {
             0xc => Self::Hashchain,
             0xd => Self::Silo,
             0xe => Self::Whitelist,
+            0xf => Self::Nep171Erc721Map,
+            0x10 => Self::Erc721Nep171Map,
+            0x11 => Self::Nft,
+            0x12 => Self::Nep245Erc1155Map,
+            0x13 => Self::Erc1155Nep245Map,
+            0x14 => Self::MultiToken,
+            0x15 => Self::WithdrawalLimits,
+            0x18 => Self::CodeStore,
+            0x19 => Self::BlockGasUsed,
+            0x1a => Self::Account,
             _ => unreachable!("Unknown key prefix"),
         }
     }
@@ -34,6 +34,15 @@ pub const ERC20_SYMBOL_SELECTOR: &[u8] = &[149, 216, 155, 65];
 /// Selector to call `digits` function in ERC-20 contact.
 /// `keccak(b"digits()")[..4];`
 pub const ERC20_DIGITS_SELECTOR: &[u8] = &[49, 60, 229, 103];
+/// Selector to call `mint` function in ERC-721 contract.
+/// `keccak(b"mint(address,uint256,string)")[..4];`
+pub const ERC721_MINT_SELECTOR: &[u8] = &[211, 252, 152, 100];
+/// Selector to call `setMetadata` function in ERC-721 contract.
+/// `keccak(b"setMetadata(string,string,string)")[..4];`
+pub const ERC721_SET_METADATA_SELECTOR: &[u8] = &[8, 144, 216, 12];
+/// Selector to call `mintBatch` function in ERC-1155 contract.
+/// `keccak(b"mintBatch(address,uint256[],uint256[],string[])")[..4];`
+pub const ERC1155_MINT_BATCH_SELECTOR: &[u8] = &[165, 87, 132, 239];
 
 #[derive(Debug)]
 pub enum AddressValidationError {
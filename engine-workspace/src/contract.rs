@@ -2,23 +2,30 @@ use crate::account::Account;
 use crate::node::Node;
 use crate::operation::{
     CallAddEntryToWhitelist, CallAddEntryToWhitelistBatch, CallAddRelayerKey,
-    CallAttachFullAccessKey, CallCall, CallDeployCode, CallDeployErc20Token, CallDeployUpgrade,
-    CallDeposit, CallFactorySetWNearAddress, CallFactoryUpdate, CallFactoryUpdateAddressVersion,
-    CallFtOnTransfer, CallFtTransfer, CallFtTransferCall, CallFundXccSubAccount, CallMintAccount,
-    CallMirrorErc20Token, CallNew, CallNewEthConnector, CallPauseContract, CallPausePrecompiles,
-    CallRefundOnError, CallRegisterRelayer, CallRemoveEntryFromWhitelist, CallRemoveRelayerKey,
-    CallResumeContract, CallResumePrecompiles, CallSetErc20Metadata,
-    CallSetEthConnectorContractAccount, CallSetEthConnectorContractData, CallSetFixedGas,
-    CallSetKeyManager, CallSetOwner, CallSetPausedFlags, CallSetSiloParams, CallSetWhitelistStatus,
-    CallStageUpgrade, CallStateMigration, CallStorageDeposit, CallStorageUnregister,
-    CallStorageWithdraw, CallSubmit, CallUpgrade, CallWithdraw, ViewAccountsCounter, ViewBalance,
-    ViewBlockHash, ViewBridgeProver, ViewChainId, ViewCode, ViewErc20FromNep141,
-    ViewFactoryWnearAddress, ViewFtBalanceOf, ViewFtBalanceOfEth, ViewFtBalancesOf, ViewFtMetadata,
-    ViewFtTotalEthSupplyOnAurora, ViewFtTotalEthSupplyOnNear, ViewFtTotalSupply,
-    ViewGetErc20Metadata, ViewGetEthConnectorContractAccount, ViewGetFixedGas, ViewGetSiloParams,
-    ViewGetWhitelistStatus, ViewIsUsedProof, ViewNep141FromErc20, ViewNonce, ViewOwner,
-    ViewPausedFlags, ViewPausedPrecompiles, ViewStorageAt, ViewStorageBalanceOf, ViewUpgradeIndex,
-    ViewVersion, ViewView,
+    CallApproveQueuedWithdrawal, CallAttachFullAccessKey, CallCall, CallCancelStagedUpgrade,
+    CallCommitUpgradeCodeHash, CallDeployCode, CallDeployErc20Token, CallDeployErc20Tokens,
+    CallDeployUpgrade, CallDeposit, CallFactorySetWNearAddress, CallFactoryUpdate,
+    CallFactoryUpdateAddressVersion, CallFtOnTransfer, CallFtTransfer, CallFtTransferCall,
+    CallFundXccSubAccount, CallMintAccount, CallMirrorErc20Token, CallMtOnTransfer, CallNew,
+    CallNewEthConnector, CallNftOnTransfer, CallPauseContract, CallPausePrecompiles,
+    CallRefundOnError, CallRegisterRelayer, CallRejectQueuedWithdrawal,
+    CallRemoveEntryFromWhitelist, CallRemoveRelayerKey, CallResumeContract, CallResumePrecompiles,
+    CallSetBlockGasLimit, CallSetCoinbaseMode, CallSetErc1155Template, CallSetErc20Metadata,
+    CallSetErc721Template, CallSetEthConnectorContractAccount, CallSetEthConnectorContractData,
+    CallSetFixedGas, CallSetKeyManager, CallSetOwner, CallSetPausedFlags, CallSetSiloParams,
+    CallSetWhitelistStatus, CallSetWithdrawalLimit, CallStageUpgrade, CallStateMigration,
+    CallStorageDeposit, CallStorageUnregister, CallStorageWithdraw, CallSubmit,
+    CallSyncErc721Metadata, CallUpgrade, CallWithdraw, ViewAccountsCounter, ViewBalance,
+    ViewBlockHash, ViewBridgeProver, ViewChainId, ViewCode, ViewErc1155FromNep245,
+    ViewErc20FromNep141, ViewErc721FromNep171, ViewFactoryWnearAddress, ViewFtBalanceOf,
+    ViewFtBalanceOfEth, ViewFtBalancesOf, ViewFtMetadata, ViewFtTotalEthSupplyOnAurora,
+    ViewFtTotalEthSupplyOnNear, ViewFtTotalSupply, ViewGetBlockGasLimit, ViewGetBlockGasUsed,
+    ViewGetCoinbaseMode, ViewGetErc20Metadata, ViewGetEthConnectorContractAccount, ViewGetFixedGas,
+    ViewGetQueuedWithdrawal, ViewGetSiloParams, ViewGetWhitelistStatus, ViewGetWithdrawalLimit,
+    ViewIsUsedProof, ViewMigrationProgress, ViewNep141FromErc20, ViewNep171FromErc721,
+    ViewNep245FromErc1155, ViewNonce, ViewOwner, ViewPausedFlags, ViewPausedPrecompiles,
+    ViewStagedUpgrade, ViewStorageAt, ViewStorageBalanceOf, ViewUpgradeIndex, ViewVersion,
+    ViewView,
 };
 use crate::transaction::{CallTransaction, ViewTransaction};
 use aurora_engine_types::account_id::AccountId;
@@ -27,15 +34,22 @@ use aurora_engine_types::parameters::connector::{
     SetErc20MetadataArgs, SetEthConnectorContractAccountArgs, WithdrawSerializeType,
 };
 use aurora_engine_types::parameters::engine::{
-    CallArgs, FullAccessKeyArgs, FunctionCallArgsV2, NewCallArgs, NewCallArgsV2, RelayerKeyArgs,
-    RelayerKeyManagerArgs,
+    CallArgs, CoinbaseMode, CommitUpgradeCodeHashArgs, DeployErc20TokensArgs, FullAccessKeyArgs,
+    FunctionCallArgsV2, NewCallArgs, NewCallArgsV2, RelayerKeyArgs, RelayerKeyManagerArgs,
+    SetBlockGasLimitArgs, SetCoinbaseModeArgs,
 };
+use aurora_engine_types::parameters::migration::StateMigrationArgs;
+use aurora_engine_types::parameters::mt::NEP245MtOnTransferArgs;
+use aurora_engine_types::parameters::nft::{NEP171NftOnTransferArgs, SyncErc721MetadataArgs};
 use aurora_engine_types::parameters::silo::{
     FixedGasArgs, SiloParamsArgs, WhitelistArgs, WhitelistKindArgs, WhitelistStatusArgs,
 };
+use aurora_engine_types::parameters::withdrawal_limits::{
+    SetWithdrawalLimitArgs, WithdrawalLimitTarget,
+};
 use aurora_engine_types::parameters::xcc::FundXccArgs;
 use aurora_engine_types::public_key::PublicKey;
-use aurora_engine_types::types::{Address, RawU256, WeiU256};
+use aurora_engine_types::types::{Address, EthGas, RawH256, RawU256, WeiU256};
 use aurora_engine_types::{H256, U256};
 use near_sdk::json_types::U128;
 use near_workspaces::types::SecretKey;
@@ -222,6 +236,10 @@ impl EngineContract {
         CallDeployErc20Token::call(&self.contract).args_borsh(account_id)
     }
 
+    pub fn deploy_erc20_tokens(&self, nep141s: Vec<AccountId>) -> CallDeployErc20Tokens {
+        CallDeployErc20Tokens::call(&self.contract).args_borsh(DeployErc20TokensArgs { nep141s })
+    }
+
     pub fn mirror_erc20_token(&self, args: MirrorErc20TokenArgs) -> CallMirrorErc20Token {
         CallMirrorErc20Token::call(&self.contract).args_borsh(args)
     }
@@ -289,6 +307,18 @@ impl EngineContract {
         CallDeployUpgrade::call(&self.contract)
     }
 
+    pub fn cancel_staged_upgrade(&self) -> CallCancelStagedUpgrade {
+        CallCancelStagedUpgrade::call(&self.contract)
+    }
+
+    pub fn commit_upgrade_code_hash(
+        &self,
+        code_hash: Option<RawH256>,
+    ) -> CallCommitUpgradeCodeHash {
+        CallCommitUpgradeCodeHash::call(&self.contract)
+            .args_borsh(CommitUpgradeCodeHashArgs { code_hash })
+    }
+
     pub fn pause_precompiles(&self, paused_mask: u32) -> CallPausePrecompiles {
         CallPausePrecompiles::call(&self.contract).args_borsh(paused_mask)
     }
@@ -301,6 +331,10 @@ impl EngineContract {
         CallStateMigration::call(&self.contract)
     }
 
+    pub fn migrate_code(&self, code_addresses: Vec<Address>) -> CallStateMigration {
+        CallStateMigration::call(&self.contract).args_borsh(StateMigrationArgs { code_addresses })
+    }
+
     pub fn mint_account(
         &self,
         address: Address,
@@ -368,6 +402,58 @@ impl EngineContract {
         CallSetErc20Metadata::call(&self.contract).args_json(metadata)
     }
 
+    pub fn set_erc721_template(&self, bytes: Vec<u8>) -> CallSetErc721Template {
+        CallSetErc721Template::call(&self.contract).args(bytes)
+    }
+
+    pub fn sync_erc721_metadata(&self, nep171: AccountId) -> CallSyncErc721Metadata {
+        CallSyncErc721Metadata::call(&self.contract).args_borsh(SyncErc721MetadataArgs { nep171 })
+    }
+
+    pub fn nft_on_transfer(
+        &self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: String,
+        msg: String,
+    ) -> CallNftOnTransfer {
+        CallNftOnTransfer::call(&self.contract).args_json(NEP171NftOnTransferArgs {
+            sender_id,
+            previous_owner_id,
+            token_id,
+            msg,
+        })
+    }
+
+    pub fn set_erc1155_template(&self, bytes: Vec<u8>) -> CallSetErc1155Template {
+        CallSetErc1155Template::call(&self.contract).args(bytes)
+    }
+
+    pub fn mt_on_transfer(&self, args: NEP245MtOnTransferArgs) -> CallMtOnTransfer {
+        CallMtOnTransfer::call(&self.contract).args_json(args)
+    }
+
+    pub fn set_coinbase_mode(&self, mode: CoinbaseMode) -> CallSetCoinbaseMode {
+        CallSetCoinbaseMode::call(&self.contract).args_borsh(SetCoinbaseModeArgs { mode })
+    }
+
+    pub fn set_block_gas_limit(&self, block_gas_limit: Option<EthGas>) -> CallSetBlockGasLimit {
+        CallSetBlockGasLimit::call(&self.contract)
+            .args_borsh(SetBlockGasLimitArgs { block_gas_limit })
+    }
+
+    pub fn set_withdrawal_limit(&self, args: SetWithdrawalLimitArgs) -> CallSetWithdrawalLimit {
+        CallSetWithdrawalLimit::call(&self.contract).args_borsh(args)
+    }
+
+    pub fn approve_queued_withdrawal(&self, id: u64) -> CallApproveQueuedWithdrawal {
+        CallApproveQueuedWithdrawal::call(&self.contract).args_borsh(id)
+    }
+
+    pub fn reject_queued_withdrawal(&self, id: u64) -> CallRejectQueuedWithdrawal {
+        CallRejectQueuedWithdrawal::call(&self.contract).args_borsh(id)
+    }
+
     pub fn attach_full_access_key(&self, args: FullAccessKeyArgs) -> CallAttachFullAccessKey {
         CallAttachFullAccessKey::call(&self.contract).args_json(args)
     }
@@ -419,6 +505,14 @@ impl EngineContract {
         ViewUpgradeIndex::view(&self.contract)
     }
 
+    pub fn get_staged_upgrade(&self) -> ViewStagedUpgrade {
+        ViewStagedUpgrade::view(&self.contract)
+    }
+
+    pub fn get_migration_progress(&self) -> ViewMigrationProgress {
+        ViewMigrationProgress::view(&self.contract)
+    }
+
     pub fn get_paused_precompiles(&self) -> ViewPausedPrecompiles {
         ViewPausedPrecompiles::view(&self.contract)
     }
@@ -480,6 +574,42 @@ impl EngineContract {
         ViewNep141FromErc20::view(&self.contract).args_borsh(address)
     }
 
+    pub fn get_erc721_from_nep171(&self, account: AccountId) -> ViewErc721FromNep171 {
+        ViewErc721FromNep171::view(&self.contract).args_borsh(account)
+    }
+
+    pub fn get_nep171_from_erc721(&self, address: Address) -> ViewNep171FromErc721 {
+        ViewNep171FromErc721::view(&self.contract).args_borsh(address)
+    }
+
+    pub fn get_erc1155_from_nep245(&self, account: AccountId) -> ViewErc1155FromNep245 {
+        ViewErc1155FromNep245::view(&self.contract).args_borsh(account)
+    }
+
+    pub fn get_nep245_from_erc1155(&self, address: Address) -> ViewNep245FromErc1155 {
+        ViewNep245FromErc1155::view(&self.contract).args_borsh(address)
+    }
+
+    pub fn get_coinbase_mode(&self) -> ViewGetCoinbaseMode {
+        ViewGetCoinbaseMode::view(&self.contract)
+    }
+
+    pub fn get_block_gas_limit(&self) -> ViewGetBlockGasLimit {
+        ViewGetBlockGasLimit::view(&self.contract)
+    }
+
+    pub fn get_block_gas_used(&self, block_height: u64) -> ViewGetBlockGasUsed {
+        ViewGetBlockGasUsed::view(&self.contract).args_borsh(block_height)
+    }
+
+    pub fn get_withdrawal_limit(&self, target: WithdrawalLimitTarget) -> ViewGetWithdrawalLimit {
+        ViewGetWithdrawalLimit::view(&self.contract).args_borsh(target)
+    }
+
+    pub fn get_queued_withdrawal(&self, id: u64) -> ViewGetQueuedWithdrawal {
+        ViewGetQueuedWithdrawal::view(&self.contract).args_borsh(id)
+    }
+
     pub fn get_paused_flags(&self) -> ViewPausedFlags {
         ViewPausedFlags::view(&self.contract)
     }
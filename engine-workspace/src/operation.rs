@@ -2,9 +2,14 @@ use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::parameters::connector::{
     Erc20Metadata, FungibleTokenMetadata, WithdrawResult,
 };
-use aurora_engine_types::parameters::engine::{StorageBalance, SubmitResult, TransactionStatus};
+use aurora_engine_types::parameters::engine::{
+    CoinbaseMode, DeployErc20TokenResult, StagedUpgradeInfo, StorageBalance, SubmitResult,
+    TransactionStatus,
+};
+use aurora_engine_types::parameters::migration::MigrationProgress;
 use aurora_engine_types::parameters::silo::{FixedGasArgs, SiloParamsArgs, WhitelistStatusArgs};
-use aurora_engine_types::types::Address;
+use aurora_engine_types::parameters::withdrawal_limits::{QueuedWithdrawal, WithdrawalLimit};
+use aurora_engine_types::types::{Address, EthGas};
 use aurora_engine_types::{HashMap, H256, U256};
 use near_sdk::json_types::U128;
 use near_sdk::PromiseOrValue;
@@ -38,6 +43,8 @@ impl_call_return![
     (CallPausePrecompiles, Call::PausePrecompiles),
     (CallUpgrade, Call::Upgrade),
     (CallStageUpgrade, Call::StageUpgrade),
+    (CallCancelStagedUpgrade, Call::CancelStagedUpgrade),
+    (CallCommitUpgradeCodeHash, Call::CommitUpgradeCodeHash),
     (CallStateMigration, Call::StateMigration),
     (CallMintAccount, Call::MintAccount),
     (CallSetPausedFlags, Call::SetPausedFlags),
@@ -57,7 +64,14 @@ impl_call_return![
     (CallAddEntryToWhitelistBatch, Call::AddEntryToWhitelistBatch),
     (CallRemoveEntryFromWhitelist, Call::RemoveEntryFromWhitelist),
     (CallSetErc20Metadata, Call::SetErc20Metadata),
-    (CallAttachFullAccessKey, Call::AttachFullAccessKey)
+    (CallSetErc721Template, Call::SetErc721Template),
+    (CallSyncErc721Metadata, Call::SyncErc721Metadata),
+    (CallSetErc1155Template, Call::SetErc1155Template),
+    (CallAttachFullAccessKey, Call::AttachFullAccessKey),
+    (CallSetWithdrawalLimit, Call::SetWithdrawalLimit),
+    (CallApproveQueuedWithdrawal, Call::ApproveQueuedWithdrawal),
+    (CallSetCoinbaseMode, Call::SetCoinbaseMode),
+    (CallSetBlockGasLimit, Call::SetBlockGasLimit)
 ];
 
 impl_call_return![
@@ -68,10 +82,14 @@ impl_call_return![
     (CallWithdraw => WithdrawResult, Call::Withdraw, borsh),
     (CallDeployCode => SubmitResult, Call::DeployCode, borsh),
     (CallDeployErc20Token => Address, Call::DeployErc20Token, borsh_address),
+    (CallDeployErc20Tokens => Vec<DeployErc20TokenResult>, Call::DeployErc20Tokens, borsh),
     (CallMirrorErc20Token => Address, Call::MirrorErc20Token, borsh_address),
     (CallCall => SubmitResult, Call::Call, borsh),
     (CallSubmit => SubmitResult, Call::Submit, borsh),
     (CallFtOnTransfer => U128, Call::FtOnTransfer, json),
+    (CallNftOnTransfer => bool, Call::NftOnTransfer, json),
+    (CallMtOnTransfer => Vec<U128>, Call::MtOnTransfer, json),
+    (CallRejectQueuedWithdrawal => SubmitResult, Call::RejectQueuedWithdrawal, borsh),
 ];
 
 impl_view_return![
@@ -85,6 +103,8 @@ impl_view_return![
     (ViewBridgeProver => AccountId, View::BridgeProver, borsh),
     (ViewChainId => U256, View::ChainId, borsh_U256),
     (ViewUpgradeIndex => u64, View::UpgradeIndex, borsh),
+    (ViewStagedUpgrade => StagedUpgradeInfo, View::StagedUpgrade, borsh),
+    (ViewMigrationProgress => MigrationProgress, View::MigrationProgress, borsh),
     (ViewPausedPrecompiles => u32, View::PausedPrecompiles, borsh),
     (ViewBlockHash => H256, View::BlockHash, borsh_H256),
     (ViewCode => Vec<u8>, View::Code, vec),
@@ -98,6 +118,10 @@ impl_view_return![
     (ViewFtBalanceOfEth => U128, View::FtBalanceOfEth, json),
     (ViewErc20FromNep141 => Address, View::Erc20FromNep141, borsh),
     (ViewNep141FromErc20 => AccountId, View::Nep141FromErc20, borsh),
+    (ViewErc721FromNep171 => Address, View::Erc721FromNep171, borsh),
+    (ViewNep171FromErc721 => AccountId, View::Nep171FromErc721, from_bytes),
+    (ViewErc1155FromNep245 => Address, View::Erc1155FromNep245, borsh),
+    (ViewNep245FromErc1155 => AccountId, View::Nep245FromErc1155, from_bytes),
     (ViewPausedFlags => u8, View::PausedFlags, borsh),
     (ViewAccountsCounter => u64, View::AccountsCounter, borsh),
     (ViewGetEthConnectorContractAccount => AccountId, View::GetEthConnectorContractAccount, borsh),
@@ -105,7 +129,12 @@ impl_view_return![
     (ViewGetSiloParams => SiloParamsArgs, View::GetSiloParams, borsh),
     (ViewGetWhitelistStatus => WhitelistStatusArgs, View::GetWhitelistStatus, borsh),
     (ViewFactoryWnearAddress => Address, View::FactoryWnearAddress, borsh),
-    (ViewGetErc20Metadata => Erc20Metadata, View::GetErc20Metadata, json)
+    (ViewGetErc20Metadata => Erc20Metadata, View::GetErc20Metadata, json),
+    (ViewGetWithdrawalLimit => Option<WithdrawalLimit>, View::GetWithdrawalLimit, borsh),
+    (ViewGetQueuedWithdrawal => QueuedWithdrawal, View::GetQueuedWithdrawal, borsh),
+    (ViewGetCoinbaseMode => CoinbaseMode, View::GetCoinbaseMode, borsh),
+    (ViewGetBlockGasLimit => Option<EthGas>, View::GetBlockGasLimit, borsh),
+    (ViewGetBlockGasUsed => Option<EthGas>, View::GetBlockGasUsed, borsh)
 ];
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -115,6 +144,7 @@ pub(crate) enum Call {
     NewEthConnector,
     DeployCode,
     DeployErc20Token,
+    DeployErc20Tokens,
     MirrorErc20Token,
     Call,
     Submit,
@@ -131,6 +161,8 @@ pub(crate) enum Call {
     PausePrecompiles,
     Upgrade,
     StageUpgrade,
+    CancelStagedUpgrade,
+    CommitUpgradeCodeHash,
     DeployUpgrade,
     StateMigration,
     ResumePrecompiles,
@@ -155,7 +187,17 @@ pub(crate) enum Call {
     AddEntryToWhitelistBatch,
     RemoveEntryFromWhitelist,
     SetErc20Metadata,
+    SetErc721Template,
+    SyncErc721Metadata,
+    NftOnTransfer,
+    SetErc1155Template,
+    MtOnTransfer,
     AttachFullAccessKey,
+    SetWithdrawalLimit,
+    ApproveQueuedWithdrawal,
+    RejectQueuedWithdrawal,
+    SetCoinbaseMode,
+    SetBlockGasLimit,
 }
 
 impl AsRef<str> for Call {
@@ -165,6 +207,7 @@ impl AsRef<str> for Call {
             Call::NewEthConnector => "new_eth_connector",
             Call::DeployCode => "deploy_code",
             Call::DeployErc20Token => "deploy_erc20_token",
+            Call::DeployErc20Tokens => "deploy_erc20_tokens",
             Call::MirrorErc20Token => "mirror_erc20_token",
             Call::Call => "call",
             Call::Submit => "submit",
@@ -181,6 +224,8 @@ impl AsRef<str> for Call {
             Call::PausePrecompiles => "pause_precompiles",
             Call::Upgrade => "upgrade",
             Call::StageUpgrade => "stage_upgrade",
+            Call::CancelStagedUpgrade => "cancel_staged_upgrade",
+            Call::CommitUpgradeCodeHash => "commit_upgrade_code_hash",
             Call::DeployUpgrade => "deploy_upgrade",
             Call::StateMigration => "state_migration",
             Call::ResumePrecompiles => "resume_precompiles",
@@ -205,7 +250,17 @@ impl AsRef<str> for Call {
             Call::AddEntryToWhitelistBatch => "add_entry_to_whitelist_batch",
             Call::RemoveEntryFromWhitelist => "remove_entry_from_whitelist",
             Call::SetErc20Metadata => "set_erc20_metadata",
+            Call::SetErc721Template => "set_erc721_template",
+            Call::SyncErc721Metadata => "sync_erc721_metadata",
+            Call::NftOnTransfer => "nft_on_transfer",
+            Call::SetErc1155Template => "set_erc1155_template",
+            Call::MtOnTransfer => "mt_on_transfer",
             Call::AttachFullAccessKey => "attach_full_access_key",
+            Call::SetWithdrawalLimit => "set_withdrawal_limit",
+            Call::ApproveQueuedWithdrawal => "approve_queued_withdrawal",
+            Call::RejectQueuedWithdrawal => "reject_queued_withdrawal",
+            Call::SetCoinbaseMode => "set_coinbase_mode",
+            Call::SetBlockGasLimit => "set_block_gas_limit",
         }
     }
 }
@@ -217,6 +272,8 @@ pub enum View {
     BridgeProver,
     ChainId,
     UpgradeIndex,
+    StagedUpgrade,
+    MigrationProgress,
     PausedPrecompiles,
     BlockHash,
     Code,
@@ -235,6 +292,10 @@ pub enum View {
     StorageBalanceOf,
     PausedFlags,
     Erc20FromNep141,
+    Erc721FromNep171,
+    Nep171FromErc721,
+    Erc1155FromNep245,
+    Nep245FromErc1155,
     Nep141FromErc20,
     AccountsCounter,
     GetEthConnectorContractAccount,
@@ -243,6 +304,11 @@ pub enum View {
     GetWhitelistStatus,
     FactoryWnearAddress,
     GetErc20Metadata,
+    GetWithdrawalLimit,
+    GetQueuedWithdrawal,
+    GetCoinbaseMode,
+    GetBlockGasLimit,
+    GetBlockGasUsed,
 }
 
 impl AsRef<str> for View {
@@ -253,6 +319,8 @@ impl AsRef<str> for View {
             View::BridgeProver => "get_bridge_prover",
             View::ChainId => "get_chain_id",
             View::UpgradeIndex => "get_upgrade_index",
+            View::StagedUpgrade => "get_staged_upgrade",
+            View::MigrationProgress => "get_migration_progress",
             View::PausedPrecompiles => "get_paused_precompiles",
             View::BlockHash => "get_block_hash",
             View::Code => "get_code",
@@ -271,6 +339,10 @@ impl AsRef<str> for View {
             View::StorageBalanceOf => "storage_balance_of",
             View::PausedFlags => "get_paused_flags",
             View::Erc20FromNep141 => "get_erc20_from_nep141",
+            View::Erc721FromNep171 => "get_erc721_from_nep171",
+            View::Nep171FromErc721 => "get_nep171_from_erc721",
+            View::Erc1155FromNep245 => "get_erc1155_from_nep245",
+            View::Nep245FromErc1155 => "get_nep245_from_erc1155",
             View::Nep141FromErc20 => "get_nep141_from_erc20",
             View::AccountsCounter => "get_accounts_counter",
             View::GetEthConnectorContractAccount => "get_eth_connector_contract_account",
@@ -279,6 +351,11 @@ impl AsRef<str> for View {
             View::GetWhitelistStatus => "get_whitelist_status",
             View::FactoryWnearAddress => "factory_get_wnear_address",
             View::GetErc20Metadata => "get_erc20_metadata",
+            View::GetWithdrawalLimit => "get_withdrawal_limit",
+            View::GetQueuedWithdrawal => "get_queued_withdrawal",
+            View::GetCoinbaseMode => "get_coinbase_mode",
+            View::GetBlockGasLimit => "get_block_gas_limit",
+            View::GetBlockGasUsed => "get_block_gas_used",
         }
     }
 }
@@ -9,9 +9,10 @@
 use crate::{
     contract_methods::connector::EthConnectorContract,
     contract_methods::{
-        predecessor_address, require_key_manager_only, require_owner_only, require_paused,
-        require_running, ContractError,
+        emit_event, predecessor_address, require_key_manager_only, require_owner_only,
+        require_paused, require_role, require_running, ContractError,
     },
+    contract_methods::{governance, migration},
     engine::{self, Engine},
     errors,
     hashchain::with_hashchain,
@@ -23,31 +24,38 @@ use crate::{
 };
 use aurora_engine_hashchain::{bloom::Bloom, hashchain::Hashchain};
 use aurora_engine_modexp::AuroraModExp;
+use aurora_engine_precompiles::{block_gas_used, hashchain_history};
 use aurora_engine_sdk::{
     env::Env,
     error::ReadU64Error,
     io::{StorageIntermediate, IO},
     promise::PromiseHandler,
 };
-use aurora_engine_types::parameters::engine::{FullAccessKeyArgs, UpgradeParams};
+use aurora_engine_types::parameters::engine::{
+    CommitUpgradeCodeHashArgs, FullAccessKeyArgs, StagedUpgradeInfo, UpgradeParams,
+};
 use aurora_engine_types::types::{NearGas, ZERO_YOCTO};
 use aurora_engine_types::{
-    borsh::BorshDeserialize,
+    borsh::{self, BorshDeserialize},
+    events::AdminEvent,
     parameters::{
         engine::{
             NewCallArgs, PausePrecompilesCallArgs, RelayerKeyArgs, RelayerKeyManagerArgs,
-            SetOwnerArgs, SetUpgradeDelayBlocksArgs, StartHashchainArgs,
+            SetBlockGasLimitArgs, SetCoinbaseModeArgs, SetOwnerArgs, SetUpgradeDelayBlocksArgs,
+            StartHashchainArgs,
         },
         promise::{PromiseAction, PromiseBatchAction},
+        roles::Role,
     },
     storage::{self, KeyPrefix},
-    types::{Address, Yocto},
-    vec, ToString,
+    types::{Address, RawH256, Yocto},
+    vec, ToString, H256,
 };
 use function_name::named;
 
 const CODE_KEY: &[u8; 4] = b"CODE";
 const CODE_STAGE_KEY: &[u8; 10] = b"CODE_STAGE";
+const CODE_HASH_COMMITMENT_KEY: &[u8; 16] = b"CODE_HASH_COMMIT";
 const GAS_FOR_STATE_MIGRATION: NearGas = NearGas::new(50_000_000_000_000);
 const GAS_FOR_STORING_RELAYER_KEY: NearGas = NearGas::new(5_000_000_000_000);
 
@@ -83,6 +91,7 @@ pub fn new<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), ContractError
     }
 
     state::set_state(&mut io, &state)?;
+    migration::set_latest_versions(&mut io, &migration::steps());
     Ok(())
 }
 
@@ -106,6 +115,7 @@ pub fn set_owner<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractErr
 
         require_running(&state)?;
         require_owner_only(&state, &env.predecessor_account_id())?;
+        governance::require_no_governance(&io)?;
 
         let args: SetOwnerArgs = io.read_input_borsh()?;
         if state.owner_id == args.new_owner {
@@ -114,6 +124,9 @@ pub fn set_owner<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractErr
 
         state.owner_id = args.new_owner;
         state::set_state(&mut io, &state)?;
+        emit_event(&AdminEvent::OwnerChanged {
+            new_owner: state.owner_id,
+        });
 
         Ok(())
     })
@@ -152,13 +165,67 @@ pub fn set_upgrade_delay_blocks<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<
         let mut state = state::get_state(&io)?;
         require_running(&state)?;
         require_owner_only(&state, &env.predecessor_account_id())?;
+        governance::require_no_governance(&io)?;
         let args: SetUpgradeDelayBlocksArgs = io.read_input_borsh()?;
         state.upgrade_delay_blocks = args.upgrade_delay_blocks;
         state::set_state(&mut io, &state)?;
+        emit_event(&AdminEvent::UpgradeDelayBlocksChanged {
+            upgrade_delay_blocks: args.upgrade_delay_blocks,
+        });
+        Ok(())
+    })
+}
+
+pub fn get_coinbase_mode<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let mode = engine::get_coinbase_mode(&io);
+    let bytes = borsh::to_vec(&mode).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn set_coinbase_mode<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+        let args: SetCoinbaseModeArgs = io.read_input_borsh()?;
+        engine::set_coinbase_mode(&mut io, args.mode);
+        emit_event(&AdminEvent::CoinbaseModeChanged { mode: args.mode });
+        Ok(())
+    })
+}
+
+pub fn get_block_gas_limit<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let block_gas_limit = engine::get_block_gas_limit(&io);
+    let bytes = borsh::to_vec(&block_gas_limit).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn set_block_gas_limit<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+        let args: SetBlockGasLimitArgs = io.read_input_borsh()?;
+        engine::set_block_gas_limit(&mut io, args.block_gas_limit);
+        emit_event(&AdminEvent::BlockGasLimitChanged {
+            block_gas_limit: args.block_gas_limit,
+        });
         Ok(())
     })
 }
 
+pub fn get_block_gas_used<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let block_height: u64 = io.read_input_borsh()?;
+    let gas_used = block_gas_used::get(&io, block_height);
+    let bytes = borsh::to_vec(&gas_used).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
 pub fn get_upgrade_index<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
     let index = internal_get_upgrade_index(&io)?;
     io.return_output(&index.to_le_bytes());
@@ -171,24 +238,124 @@ pub fn stage_upgrade<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contrac
         let state = state::get_state(&io)?;
         require_running(&state)?;
         let delay_block_height = env.block_height() + state.upgrade_delay_blocks;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        let code_hash = aurora_engine_sdk::sha256(&io.read_input().to_vec());
+        if let Some(committed_code_hash) = internal_get_code_hash_commitment(&io) {
+            if code_hash.0 != committed_code_hash {
+                return Err(errors::ERR_UPGRADE_CODE_HASH_MISMATCH.into());
+            }
+        }
         io.read_input_and_store(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY));
         io.write_storage(
             &storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY),
             &delay_block_height.to_le_bytes(),
         );
+        emit_event(&AdminEvent::UpgradeStaged {
+            code_hash,
+            unlock_height: delay_block_height,
+        });
         Ok(())
     })
 }
 
+pub fn get_staged_upgrade<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let unlock_height = internal_get_upgrade_index(&io)?;
+    let code = io
+        .read_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY))
+        .ok_or(errors::ERR_NO_UPGRADE)?
+        .to_vec();
+    let info = StagedUpgradeInfo {
+        code_hash: aurora_engine_sdk::sha256(&code).0,
+        code_size: code.len() as u64,
+        unlock_height,
+        committed_code_hash: internal_get_code_hash_commitment(&io),
+    };
+    let bytes = borsh::to_vec(&info).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn cancel_staged_upgrade<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        internal_get_upgrade_index(&io)?;
+        io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY));
+        io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY));
+        io.remove_storage(&storage::bytes_to_key(
+            KeyPrefix::Config,
+            CODE_HASH_COMMITMENT_KEY,
+        ));
+        emit_event(&AdminEvent::UpgradeCancelled);
+        Ok(())
+    })
+}
+
+/// Commits to the hash of the code which is going to be staged next. The commitment can't be
+/// changed while an upgrade is staged, so the code which will be deployed is known in advance.
+#[named]
+pub fn commit_upgrade_code_hash<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        if io.storage_has_key(&storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY)) {
+            return Err(errors::ERR_UPGRADE_ALREADY_STAGED.into());
+        }
+        let args: CommitUpgradeCodeHashArgs = io.read_input_borsh()?;
+        let key = storage::bytes_to_key(KeyPrefix::Config, CODE_HASH_COMMITMENT_KEY);
+        if let Some(code_hash) = args.code_hash {
+            io.write_storage(&key, &code_hash);
+        } else {
+            io.remove_storage(&key);
+        }
+        emit_event(&AdminEvent::UpgradeCodeHashCommitted {
+            code_hash: args.code_hash.map(H256),
+        });
+        Ok(())
+    })
+}
+
+/// Checks that the staged code matches the committed code hash (if any) and removes
+/// the commitment. Called right before the staged code is deployed.
+pub fn consume_code_hash_commitment<I: IO>(io: &mut I) -> Result<(), ContractError> {
+    if internal_get_code_hash_commitment(io).is_some() {
+        let code = io
+            .read_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY))
+            .ok_or(errors::ERR_NO_UPGRADE)?
+            .to_vec();
+        check_and_remove_code_hash_commitment(io, &code)?;
+    }
+    Ok(())
+}
+
+/// Checks that the code matches the committed code hash (if any) and removes the commitment.
+fn check_and_remove_code_hash_commitment<I: IO>(
+    io: &mut I,
+    code: &[u8],
+) -> Result<(), ContractError> {
+    if let Some(committed_code_hash) = internal_get_code_hash_commitment(io) {
+        if aurora_engine_sdk::sha256(code).0 != committed_code_hash {
+            return Err(errors::ERR_UPGRADE_CODE_HASH_MISMATCH.into());
+        }
+        io.remove_storage(&storage::bytes_to_key(
+            KeyPrefix::Config,
+            CODE_HASH_COMMITMENT_KEY,
+        ));
+    }
+    Ok(())
+}
+
 pub fn upgrade<I: IO + Copy, E: Env, H: PromiseHandler>(
-    io: I,
+    mut io: I,
     env: &E,
     handler: &mut H,
 ) -> Result<(), ContractError> {
     let state = state::get_state(&io)?;
     require_running(&state)?;
-    require_owner_only(&state, &env.predecessor_account_id())?;
+    require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
 
     let input = io.read_input().to_vec();
     let (code, state_migration_gas) = match UpgradeParams::try_from_slice(&input) {
@@ -199,6 +366,8 @@ pub fn upgrade<I: IO + Copy, E: Env, H: PromiseHandler>(
         ),
         Err(_) => (input, GAS_FOR_STATE_MIGRATION), // Backward compatibility
     };
+    // The code deployed right away must match the committed code hash as well.
+    check_and_remove_code_hash_commitment(&mut io, &code)?;
 
     let target_account_id = env.current_account_id();
     let batch = PromiseBatchAction {
@@ -227,12 +396,15 @@ pub fn resume_precompiles<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Co
         require_running(&state)?;
         let predecessor_account_id = env.predecessor_account_id();
 
-        require_owner_only(&state, &predecessor_account_id)?;
+        require_role(&io, &state, Role::Pauser, &predecessor_account_id)?;
 
         let args: PausePrecompilesCallArgs = io.read_input_borsh()?;
         let flags = PrecompileFlags::from_bits_truncate(args.paused_mask);
         let mut pauser = EnginePrecompilesPauser::from_io(io);
         pauser.resume_precompiles(flags);
+        emit_event(&AdminEvent::PrecompilesResumed {
+            paused_mask: args.paused_mask,
+        });
         Ok(())
     })
 }
@@ -251,6 +423,9 @@ pub fn pause_precompiles<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Con
         let flags = PrecompileFlags::from_bits_truncate(args.paused_mask);
         let mut pauser = EnginePrecompilesPauser::from_io(io);
         pauser.pause_precompiles(flags);
+        emit_event(&AdminEvent::PrecompilesPaused {
+            paused_mask: args.paused_mask,
+        });
         Ok(())
     })
 }
@@ -266,10 +441,11 @@ pub fn paused_precompiles<I: IO + Copy>(mut io: I) -> Result<(), ContractError>
 pub fn pause_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |mut io| {
         let mut state = state::get_state(&io)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&io, &state, Role::Pauser, &env.predecessor_account_id())?;
         require_running(&state)?;
         state.is_paused = true;
         state::set_state(&mut io, &state)?;
+        emit_event(&AdminEvent::ContractPaused);
         Ok(())
     })
 }
@@ -278,10 +454,11 @@ pub fn pause_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contra
 pub fn resume_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |mut io| {
         let mut state = state::get_state(&io)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&io, &state, Role::Pauser, &env.predecessor_account_id())?;
         require_paused(&state)?;
         state.is_paused = false;
         state::set_state(&mut io, &state)?;
+        emit_event(&AdminEvent::ContractResumed);
         Ok(())
     })
 }
@@ -292,7 +469,13 @@ pub fn set_key_manager<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contr
         let mut state = state::get_state(&io)?;
 
         require_running(&state)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(
+            &io,
+            &state,
+            Role::RelayerKeyManager,
+            &env.predecessor_account_id(),
+        )?;
+        governance::require_no_governance(&io)?;
 
         let key_manager =
             serde_json::from_slice::<RelayerKeyManagerArgs>(&io.read_input().to_vec())
@@ -305,6 +488,9 @@ pub fn set_key_manager<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contr
 
         state.key_manager = key_manager;
         state::set_state(&mut io, &state)?;
+        emit_event(&AdminEvent::KeyManagerChanged {
+            key_manager: state.key_manager,
+        });
 
         Ok(())
     })
@@ -376,6 +562,7 @@ pub fn store_relayer_key_callback<I: IO + Copy, E: Env>(
         aurora_engine_sdk::log!("add relayer public key: {public_key}");
 
         engine::add_function_call_key(&mut io, &public_key);
+        emit_event(&AdminEvent::RelayerKeyAdded { public_key });
 
         Ok(())
     })
@@ -397,6 +584,9 @@ pub fn remove_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
             .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
 
         engine::remove_function_call_key(&mut io, &args.public_key)?;
+        emit_event(&AdminEvent::RelayerKeyRemoved {
+            public_key: args.public_key,
+        });
 
         let action = PromiseAction::DeleteKey {
             public_key: args.public_key,
@@ -429,10 +619,12 @@ pub fn register_relayer<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Cont
             io,
             env,
         );
-        engine.register_relayer(
-            predecessor_account_id.as_bytes(),
-            Address::from_array(relayer_address),
-        );
+        let address = Address::from_array(relayer_address);
+        engine.register_relayer(predecessor_account_id.as_bytes(), address);
+        emit_event(&AdminEvent::RelayerRegistered {
+            account_id: predecessor_account_id,
+            address,
+        });
         Ok(())
     })
 }
@@ -459,9 +651,9 @@ pub fn start_hashchain<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), C
         args.block_hashchain,
     );
 
-    if hashchain.get_current_block_height() < block_height {
-        hashchain.move_to_block(block_height)?;
-    }
+    // The blocks recorded by a previous hashchain don't precede the new one.
+    hashchain_history::reset(&mut io);
+    crate::hashchain::move_to_block(&mut io, &mut hashchain, block_height)?;
 
     hashchain.add_block_tx(
         block_height,
@@ -474,6 +666,29 @@ pub fn start_hashchain<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), C
 
     state.is_paused = false;
     state::set_state(&mut io, &state)?;
+    emit_event(&AdminEvent::HashchainStarted {
+        block_height: args.block_height,
+        block_hashchain: H256(args.block_hashchain),
+    });
+
+    Ok(())
+}
+
+pub fn get_hashchain_at<I: IO, E: Env>(io: &mut I, env: &E) -> Result<(), ContractError> {
+    let block_height: u64 = io.read_input_borsh()?;
+    let chain_id = state::get_state(io)?.chain_id;
+    let current_account_id = env.current_account_id();
+    let result =
+        hashchain_history::get(io, &chain_id, &current_account_id, block_height).map(|hashchain| {
+            serde_json::json!({
+                "block_height": block_height,
+                "hashchain": hex::encode(hashchain),
+            })
+        });
+
+    let bytes = serde_json::to_vec(&serde_json::json!({ "result": result }))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
 
     Ok(())
 }
@@ -527,6 +742,14 @@ pub fn attach_full_access_key<I: IO + Copy, E: Env, H: PromiseHandler>(
     Ok(())
 }
 
+fn internal_get_code_hash_commitment<I: IO>(io: &I) -> Option<RawH256> {
+    io.read_storage(&storage::bytes_to_key(
+        KeyPrefix::Config,
+        CODE_HASH_COMMITMENT_KEY,
+    ))
+    .and_then(|value| value.to_vec().try_into().ok())
+}
+
 fn internal_get_upgrade_index<I: IO>(io: &I) -> Result<u64, ContractError> {
     match io.read_u64(&storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY)) {
         Ok(index) => Ok(index),
@@ -1,7 +1,7 @@
 use crate::contract_methods::connector::deposit_event::FtTransferMessageData;
 use crate::contract_methods::connector::{construct_contract_key, errors, ZERO_ATTACHED_BALANCE};
 use crate::contract_methods::{
-    predecessor_address, require_owner_only, require_running, ContractError,
+    emit_event, governance, predecessor_address, require_role, require_running, ContractError,
 };
 use crate::engine::Engine;
 use crate::hashchain::with_hashchain;
@@ -16,6 +16,7 @@ use aurora_engine_sdk::env::{Env, DEFAULT_PREPAID_GAS};
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use aurora_engine_sdk::promise::PromiseHandler;
 use aurora_engine_types::borsh::{self, BorshDeserialize, BorshSerialize};
+use aurora_engine_types::events::AdminEvent;
 use aurora_engine_types::parameters::connector::{
     EngineWithdrawCallArgs, InitCallArgs, SetEthConnectorContractAccountArgs,
     StorageDepositCallArgs, StorageUnregisterCallArgs, StorageWithdrawCallArgs, TransferCallArgs,
@@ -23,6 +24,7 @@ use aurora_engine_types::parameters::connector::{
 };
 use aurora_engine_types::parameters::engine::errors::ParseArgsError;
 use aurora_engine_types::parameters::engine::SubmitResult;
+use aurora_engine_types::parameters::roles::Role;
 use aurora_engine_types::parameters::{PromiseWithCallbackArgs, WithdrawCallArgs};
 use function_name::named;
 
@@ -270,7 +272,13 @@ pub fn set_eth_connector_account_id<I: IO + Copy, E: Env>(
         let is_private = env.assert_private_call();
 
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(
+                &io,
+                &state,
+                Role::ConnectorAdmin,
+                &env.predecessor_account_id(),
+            )?;
+            governance::require_no_governance(&io)?;
         }
 
         let args: SetEthConnectorContractAccountArgs = io.read_input_borsh()?;
@@ -278,6 +286,9 @@ pub fn set_eth_connector_account_id<I: IO + Copy, E: Env>(
 
         connector.set_eth_connector_contract_account(&args.account);
         connector.set_withdraw_serialize_type(&args.withdraw_serialize_type);
+        emit_event(&AdminEvent::EthConnectorAccountChanged {
+            account: args.account,
+        });
 
         Ok(())
     })
@@ -8,7 +8,7 @@ use crate::contract_methods::connector::{
     construct_contract_key, proof_key, ZERO_ATTACHED_BALANCE,
 };
 use crate::contract_methods::{
-    predecessor_address, require_owner_only, require_running, ContractError,
+    emit_event, predecessor_address, require_role, require_running, ContractError,
 };
 use crate::engine::Engine;
 use crate::hashchain::with_hashchain;
@@ -19,6 +19,7 @@ use aurora_engine_sdk::io::StorageIntermediate;
 use aurora_engine_sdk::promise::PromiseHandler;
 use aurora_engine_sdk::{env::Env, io::IO};
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::events::AdminEvent;
 use aurora_engine_types::parameters::connector::{
     BalanceOfCallArgs, BalanceOfEthCallArgs, FinishDepositCallArgs, FungibleTokenMetadata,
     IsUsedProofCallArgs, PauseEthConnectorCallArgs, PausedMask, Proof, StorageBalanceOfCallArgs,
@@ -26,6 +27,7 @@ use aurora_engine_types::parameters::connector::{
 };
 use aurora_engine_types::parameters::engine::errors::ParseArgsError;
 use aurora_engine_types::parameters::engine::SubmitResult;
+use aurora_engine_types::parameters::roles::Role;
 use aurora_engine_types::parameters::{PromiseBatchAction, PromiseCreateArgs, WithdrawCallArgs};
 use aurora_engine_types::storage::EthConnectorStorageId;
 use aurora_engine_types::types::address::error::AddressError;
@@ -65,10 +67,15 @@ pub fn new_eth_connector<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Con
     with_hashchain(io, env, function_name!(), |io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        // Only the owner can initialize the EthConnector
+        // Only the owner or a connector admin can initialize the EthConnector
         let is_private = env.assert_private_call();
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(
+                &io,
+                &state,
+                Role::ConnectorAdmin,
+                &env.predecessor_account_id(),
+            )?;
         }
 
         let args: InitCallArgs = io.read_input_borsh()?;
@@ -87,10 +94,15 @@ pub fn set_eth_connector_contract_data<I: IO + Copy, E: Env>(
     with_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        // Only the owner can set the EthConnector contract data
+        // Only the owner or a connector admin can set the EthConnector contract data
         let is_private = env.assert_private_call();
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(
+                &io,
+                &state,
+                Role::ConnectorAdmin,
+                &env.predecessor_account_id(),
+            )?;
         }
 
         let args: SetContractDataCallArgs = io.read_input_borsh()?;
@@ -341,10 +353,18 @@ pub fn set_paused_flags<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Cont
         require_running(&state)?;
         let is_private = env.assert_private_call();
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(
+                &io,
+                &state,
+                Role::ConnectorAdmin,
+                &env.predecessor_account_id(),
+            )?;
         }
         let args: PauseEthConnectorCallArgs = io.read_input_borsh()?;
         EthConnectorContract::init(io)?.set_paused_flags(&args);
+        emit_event(&AdminEvent::ConnectorPausedFlagsChanged {
+            paused_mask: args.paused_mask,
+        });
         Ok(())
     })
 }
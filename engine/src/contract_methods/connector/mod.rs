@@ -1,7 +1,7 @@
 #![allow(clippy::missing_const_for_fn)]
 
 use crate::contract_methods::{
-    predecessor_address, require_owner_only, require_running, ContractError,
+    emit_event, predecessor_address, require_role, require_running, ContractError,
 };
 use crate::engine::Engine;
 use crate::hashchain::with_hashchain;
@@ -12,19 +12,24 @@ use aurora_engine_sdk::env::Env;
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use aurora_engine_sdk::promise::PromiseHandler;
 use aurora_engine_types::borsh::{self, BorshDeserialize};
+use aurora_engine_types::events::AdminEvent;
 use aurora_engine_types::parameters::connector::{
-    Erc20Identifier, MirrorErc20TokenArgs, SetErc20MetadataArgs,
+    Erc20Identifier, Erc20Metadata, FungibleTokenMetadata, MirrorErc20TokenArgs,
+    SetErc20MetadataArgs,
 };
 use aurora_engine_types::parameters::engine::errors::ParseArgsError;
 use aurora_engine_types::parameters::engine::{
-    DeployErc20TokenArgs, GetErc20FromNep141CallArgs, SubmitResult,
+    DeployErc20TokenArgs, DeployErc20TokenResult, DeployErc20TokensArgs, Erc20DeployStatus,
+    GetErc20FromNep141CallArgs, SubmitResult,
 };
+use aurora_engine_types::parameters::roles::Role;
 use aurora_engine_types::parameters::{
-    ExitToNearPrecompileCallbackCallArgs, PromiseAction, PromiseBatchAction,
+    ExitToNearPrecompileCallbackCallArgs, PromiseAction, PromiseBatchAction, RefundCallArgs,
 };
 use aurora_engine_types::parameters::{PromiseCreateArgs, PromiseWithCallbackArgs};
 use aurora_engine_types::storage::{EthConnectorStorageId, KeyPrefix};
-use aurora_engine_types::types::{Address, NearGas, PromiseResult, Yocto};
+use aurora_engine_types::types::{u256_to_arr, Address, Balance, NearGas, PromiseResult, Yocto};
+use aurora_engine_types::U256;
 use function_name::named;
 
 #[cfg(feature = "ext-connector")]
@@ -48,6 +53,12 @@ pub const ZERO_ATTACHED_BALANCE: Yocto = Yocto::new(0);
 const READ_PROMISE_ATTACHED_GAS: NearGas = NearGas::new(6_000_000_000_000);
 /// Amount of attached gas for the `mirror_erc20_token_callback`.
 const MIRROR_ERC20_TOKEN_CALLBACK_ATTACHED_GAS: NearGas = NearGas::new(10_000_000_000_000);
+/// Amount of attached gas for the `deploy_erc20_tokens_callback` per deployed token.
+const DEPLOY_ERC20_TOKENS_CALLBACK_GAS_PER_TOKEN: NearGas = NearGas::new(10_000_000_000_000);
+/// Maximum number of tokens deployed by one `deploy_erc20_tokens` call. Every token costs
+/// an EVM deployment plus 16 Tgas of attached gas for its `ft_metadata` promise and its share
+/// of the callback, so bigger batches don't fit into the 300 Tgas of a transaction.
+const MAX_DEPLOY_ERC20_TOKENS_BATCH_SIZE: usize = 8;
 
 /// Create new eth-connector;
 pub fn new_eth_connector<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
@@ -133,6 +144,125 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, H: PromiseHandler>(
     })
 }
 
+/// Deploys the ERC-20 tokens of many NEP-141 tokens. The metadata of the deployed tokens
+/// is fetched with joint `ft_metadata` promises and set in `deploy_erc20_tokens_callback`.
+#[named]
+pub fn deploy_erc20_tokens<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        require_running(&state::get_state(&io)?)?;
+        let args: DeployErc20TokensArgs = io.read_input_borsh()?;
+        if args.nep141s.is_empty() {
+            return Err(crate::errors::ERR_EMPTY_TOKENS_LIST.into());
+        }
+        if args.nep141s.len() > MAX_DEPLOY_ERC20_TOKENS_BATCH_SIZE {
+            return Err(crate::errors::ERR_TOO_MANY_TOKENS.into());
+        }
+        let results = engine::deploy_erc20_tokens(args.nep141s, io, env, handler);
+
+        let promises: Vec<_> = results
+            .iter()
+            .filter(|result| matches!(result.status, Erc20DeployStatus::DeployedWithoutMetadata(_)))
+            .map(|result| PromiseCreateArgs {
+                target_account_id: result.nep141.clone(),
+                method: "ft_metadata".to_string(),
+                args: Vec::new(),
+                attached_balance: ZERO_ATTACHED_BALANCE,
+                attached_gas: READ_PROMISE_ATTACHED_GAS,
+            })
+            .collect();
+        let bytes = borsh::to_vec(&results).map_err(|_| crate::errors::ERR_SERIALIZE)?;
+        if promises.is_empty() {
+            io.return_output(&bytes);
+            return Ok(());
+        }
+
+        let callback = PromiseCreateArgs {
+            target_account_id: env.current_account_id(),
+            method: "deploy_erc20_tokens_callback".to_string(),
+            args: bytes,
+            attached_balance: ZERO_ATTACHED_BALANCE,
+            attached_gas: NearGas::new(
+                DEPLOY_ERC20_TOKENS_CALLBACK_GAS_PER_TOKEN
+                    .as_u64()
+                    .saturating_mul(promises.len() as u64),
+            ),
+        };
+        // Safe because the promises are read-only calls and the callback is a call
+        // to the engine contract itself.
+        let promise_id = unsafe {
+            let promise_id = handler.promise_create_and_combine(&promises);
+            handler.promise_attach_callback(promise_id, &callback)
+        };
+        handler.promise_return(promise_id);
+
+        Ok(())
+    })
+}
+
+/// Sets the metadata of the ERC-20 tokens deployed by `deploy_erc20_tokens` from the results
+/// of the `ft_metadata` promises. Returns the outcome of the deployment of each token.
+#[named]
+pub fn deploy_erc20_tokens_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        env.assert_private_call()?;
+
+        let mut results: Vec<DeployErc20TokenResult> = io.read_input_borsh()?;
+        let deployed: Vec<_> = results
+            .iter_mut()
+            .filter_map(|result| match result.status {
+                Erc20DeployStatus::DeployedWithoutMetadata(address) => Some((address, result)),
+                _ => None,
+            })
+            .collect();
+        // This function should only be called as the callback of
+        // the `ft_metadata` promises of the deployed tokens.
+        if handler.promise_results_count() != deployed.len() as u64 {
+            return Err(crate::errors::ERR_PROMISE_COUNT.into());
+        }
+
+        let current_account_id = env.current_account_id();
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&current_account_id),
+            current_account_id,
+            io,
+            env,
+        );
+
+        for (index, (address, result)) in (0..).zip(deployed) {
+            let Some(PromiseResult::Successful(bytes)) = handler.promise_result(index) else {
+                continue;
+            };
+            let Ok(metadata) = serde_json::from_slice::<FungibleTokenMetadata>(&bytes) else {
+                continue;
+            };
+            let metadata = Erc20Metadata {
+                name: metadata.name,
+                symbol: metadata.symbol,
+                decimals: metadata.decimals,
+            };
+            let is_set = engine
+                .set_erc20_metadata(&Erc20Identifier::Erc20 { address }, metadata, handler)
+                .is_ok_and(|result| result.status.is_ok());
+            if is_set {
+                result.status = Erc20DeployStatus::Deployed(address);
+            }
+        }
+        io.return_output(&borsh::to_vec(&results).map_err(|_| crate::errors::ERR_SERIALIZE)?);
+        Ok(())
+    })
+}
+
 #[named]
 pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
     io: I,
@@ -152,7 +282,7 @@ pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>
 
         let args: ExitToNearPrecompileCallbackCallArgs = io.read_input_borsh()?;
 
-        let maybe_result = if let Some(PromiseResult::Successful(_)) = handler.promise_result(0) {
+        let refund = if let Some(PromiseResult::Successful(result)) = handler.promise_result(0) {
             if let Some(args) = args.transfer_near {
                 let action = PromiseAction::Transfer {
                     amount: Yocto::new(args.amount),
@@ -168,9 +298,14 @@ pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>
                 handler.promise_return(promise_id);
             }
 
-            None
-        } else if let Some(args) = args.refund {
+            // The tokens not used by the receiver of a `ft_transfer_call` are refunded.
+            args.refund.and_then(|args| unused_refund(&result, args))
+        } else {
             // Exit call failed; need to refund tokens
+            args.refund
+        };
+
+        let maybe_result = if let Some(args) = refund {
             let refund_result = engine::refund_on_error(io, env, state, &args, handler)?;
 
             if !refund_result.status.is_ok() {
@@ -186,6 +321,18 @@ pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>
     })
 }
 
+/// Returns the refund of the amount not used by the receiver if the result is the one of
+/// a `ft_transfer_call` (the used amount). The result of `ft_transfer` is empty.
+fn unused_refund(result: &[u8], args: RefundCallArgs) -> Option<RefundCallArgs> {
+    let used: Balance = serde_json::from_slice(result).ok()?;
+    let amount = U256::from_big_endian(&args.amount);
+    let unused = amount.checked_sub(U256::from(used.as_u128()))?;
+    (!unused.is_zero()).then(|| RefundCallArgs {
+        amount: u256_to_arr(&unused),
+        ..args
+    })
+}
+
 pub fn finish_deposit<I: IO + Copy, E: Env, H: PromiseHandler>(
     io: I,
     env: &E,
@@ -375,10 +522,14 @@ pub fn set_erc20_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_hashchain(io, env, function_name!(), |io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        // TODO: Define special role for this transaction. Potentially via multisig?
         let is_private = env.assert_private_call();
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(
+                &io,
+                &state,
+                Role::ConnectorAdmin,
+                &env.predecessor_account_id(),
+            )?;
         }
 
         let args: SetErc20MetadataArgs = serde_json::from_slice(&io.read_input().to_vec())
@@ -391,7 +542,14 @@ pub fn set_erc20_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
             io,
             env,
         );
-        let result = engine.set_erc20_metadata(&args.erc20_identifier, args.metadata, handler)?;
+        let result =
+            engine.set_erc20_metadata(&args.erc20_identifier, args.metadata.clone(), handler)?;
+        if result.status.is_ok() {
+            emit_event(&AdminEvent::Erc20MetadataChanged {
+                erc20_identifier: args.erc20_identifier,
+                metadata: args.metadata,
+            });
+        }
 
         Ok(result)
     })
@@ -456,8 +614,7 @@ pub fn mirror_erc20_token<I: IO + Env + Copy, H: PromiseHandler>(
 ) -> Result<(), ContractError> {
     let state = state::get_state(&io)?;
     require_running(&state)?;
-    // TODO: Add an admin access list of accounts allowed to do it.
-    require_owner_only(&state, &io.predecessor_account_id())?;
+    require_role(&io, &state, Role::SiloAdmin, &io.predecessor_account_id())?;
 
     if !crate::contract_methods::silo::is_silo_mode_on(&io) {
         return Err(crate::errors::ERR_ALLOWED_IN_SILO_MODE_ONLY.into());
@@ -582,12 +739,13 @@ fn proof_key(proof: &aurora_engine_types::parameters::connector::Proof) -> crate
 
 #[cfg(test)]
 mod tests {
-    use super::proof_key;
+    use super::{proof_key, unused_refund};
     use crate::contract_methods::connector::deposit_event::{
         DepositedEvent, TokenMessageData, DEPOSITED_EVENT,
     };
     use aurora_engine_types::parameters::connector::{LogEntry, Proof};
-    use aurora_engine_types::types::{make_address, Address, Fee, NEP141Wei, Wei};
+    use aurora_engine_types::parameters::RefundCallArgs;
+    use aurora_engine_types::types::{make_address, u256_to_arr, Address, Fee, NEP141Wei, Wei};
     use aurora_engine_types::{H160, U256};
 
     const ETH_CUSTODIAN_ADDRESS: Address =
@@ -606,6 +764,24 @@ mod tests {
         assert_eq!(expected_key, actual_key);
     }
 
+    #[test]
+    fn test_unused_refund() {
+        let args = RefundCallArgs {
+            recipient_address: Address::new(H160([22u8; 20])),
+            erc20_address: None,
+            amount: u256_to_arr(&U256::from(100)),
+        };
+
+        // `ft_transfer` returns nothing.
+        assert_eq!(unused_refund(b"", args.clone()), None);
+        assert_eq!(unused_refund(br#""100""#, args.clone()), None);
+        assert_eq!(
+            unused_refund(br#""40""#, args.clone()).unwrap().amount,
+            u256_to_arr(&U256::from(60))
+        );
+        assert_eq!(unused_refund(br#""101""#, args), None);
+    }
+
     fn create_proof(recipient_address: Address, deposit_amount: Wei) -> Proof {
         let eth_custodian_address = ETH_CUSTODIAN_ADDRESS;
 
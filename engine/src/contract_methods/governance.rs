@@ -0,0 +1,391 @@
+//! Proposals allow applying critical configuration changes only after they have been approved
+//! by a threshold of approvers and a timelock has passed. The approvers, the threshold and the
+//! timelocks are defined by the `GovernanceConfig`, which can only be changed via a proposal
+//! once it has been set.
+
+use crate::{
+    contract_methods::{
+        emit_event, require_owner_only, require_running, roles, silo, ContractError,
+    },
+    errors,
+    hashchain::with_hashchain,
+    prelude::Vec,
+    state, xcc,
+};
+use aurora_engine_sdk::{
+    env::Env,
+    io::{StorageIntermediate, IO},
+};
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh,
+    events::AdminEvent,
+    parameters::governance::{
+        GovernanceConfig, Proposal, ProposalAction, ProposalIdArgs, ProposalStatus,
+    },
+    storage::{bytes_to_key, KeyPrefix},
+};
+use function_name::named;
+
+const GOVERNANCE_CONFIG_KEY: &[u8] = b"GOV_CONFIG";
+const NEXT_PROPOSAL_ID_KEY: &[u8] = b"GOV_NEXT_ID";
+const PROPOSAL_KEY: &[u8] = b"GOV_PROPOSAL";
+
+/// Sets the initial governance config. Further changes of the config are only possible
+/// via the `UpdateGovernanceConfig` proposal.
+#[named]
+pub fn set_governance_config<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+
+        if get_config(&io).is_some() {
+            return Err(errors::ERR_GOVERNANCE_ALREADY_CONFIGURED.into());
+        }
+
+        let config: GovernanceConfig = io.read_input_borsh()?;
+        set_config(&mut io, &config)
+    })
+}
+
+/// Once the governance is configured, the changes which can be proposed are only applied
+/// via proposals, so the direct methods can't bypass the approvals and the timelock.
+pub fn require_no_governance<I: IO>(io: &I) -> Result<(), ContractError> {
+    if get_config(io).is_some() {
+        return Err(errors::ERR_GOVERNANCE_PROPOSAL_REQUIRED.into());
+    }
+
+    Ok(())
+}
+
+pub fn get_governance_config<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let config = get_config(&io).ok_or(errors::ERR_GOVERNANCE_NOT_CONFIGURED)?;
+    let bytes = borsh::to_vec(&config).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn create_proposal<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+
+        let config = get_config(&io).ok_or(errors::ERR_GOVERNANCE_NOT_CONFIGURED)?;
+        let proposer = env.predecessor_account_id();
+        let is_approver = config.approvers.contains(&proposer);
+
+        if !is_approver && proposer != state.owner_id {
+            return Err(errors::ERR_NOT_ALLOWED.into());
+        }
+
+        let action: ProposalAction = io.read_input_borsh()?;
+        // The eth-connector account only exists with the external eth-connector.
+        #[cfg(not(feature = "ext-connector"))]
+        if matches!(action, ProposalAction::SetEthConnectorContractAccount(_)) {
+            return Err(errors::ERR_UNSUPPORTED_PROPOSAL_ACTION.into());
+        }
+
+        let block_height = env.block_height();
+        let mut proposal = Proposal {
+            proposer: proposer.clone(),
+            timelock_blocks: config.timelock_blocks(action.kind()),
+            action,
+            approvers: config.approvers,
+            threshold: config.threshold,
+            approvals: Vec::new(),
+            created_at: block_height,
+            approved_at: None,
+            status: ProposalStatus::Pending,
+        };
+
+        // The proposer implicitly approves their own proposal.
+        if is_approver {
+            add_approval(&mut proposal, proposer, block_height);
+        }
+
+        let id = get_next_proposal_id(&io);
+        set_proposal(&mut io, id, &proposal);
+        io.write_storage(&next_proposal_id_key(), &(id + 1).to_le_bytes());
+        emit_event(&AdminEvent::ProposalCreated {
+            id,
+            proposer: proposal.proposer,
+            action: proposal.action.kind(),
+        });
+
+        let bytes = borsh::to_vec(&id).map_err(|_| errors::ERR_SERIALIZE)?;
+        io.return_output(&bytes);
+        Ok(())
+    })
+}
+
+#[named]
+pub fn approve_proposal<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        require_running(&state::get_state(&io)?)?;
+
+        let args: ProposalIdArgs = io.read_input_borsh()?;
+        let mut proposal = get_pending_proposal(&io, args.id)?;
+        let approver = env.predecessor_account_id();
+
+        if !proposal.approvers.contains(&approver) {
+            return Err(errors::ERR_NOT_ALLOWED.into());
+        }
+
+        if proposal.approvals.contains(&approver) {
+            return Err(errors::ERR_PROPOSAL_ALREADY_APPROVED.into());
+        }
+
+        add_approval(&mut proposal, approver.clone(), env.block_height());
+        set_proposal(&mut io, args.id, &proposal);
+        emit_event(&AdminEvent::ProposalApproved {
+            id: args.id,
+            approver,
+        });
+        Ok(())
+    })
+}
+
+#[named]
+pub fn execute_proposal<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        require_running(&state::get_state(&io)?)?;
+
+        let args: ProposalIdArgs = io.read_input_borsh()?;
+        let mut proposal = get_pending_proposal(&io, args.id)?;
+        let unlock_height = proposal
+            .unlock_height()
+            .ok_or(errors::ERR_PROPOSAL_NOT_APPROVED)?;
+
+        if env.block_height() < unlock_height {
+            return Err(errors::ERR_NOT_ALLOWED_TOO_EARLY.into());
+        }
+
+        apply_action(io, proposal.action.clone())?;
+        proposal.status = ProposalStatus::Executed;
+        set_proposal(&mut io, args.id, &proposal);
+        emit_event(&AdminEvent::ProposalExecuted { id: args.id });
+        Ok(())
+    })
+}
+
+#[named]
+pub fn cancel_proposal<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+
+        let args: ProposalIdArgs = io.read_input_borsh()?;
+        let mut proposal = get_pending_proposal(&io, args.id)?;
+        let predecessor_account_id = env.predecessor_account_id();
+
+        if predecessor_account_id != proposal.proposer && predecessor_account_id != state.owner_id {
+            return Err(errors::ERR_NOT_ALLOWED.into());
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        set_proposal(&mut io, args.id, &proposal);
+        emit_event(&AdminEvent::ProposalCancelled { id: args.id });
+        Ok(())
+    })
+}
+
+pub fn get_proposal<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let args: ProposalIdArgs = io.read_input_borsh()?;
+    let proposal = read_proposal(&io, args.id).ok_or(errors::ERR_PROPOSAL_NOT_FOUND)?;
+    let bytes = borsh::to_vec(&proposal).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+fn apply_action<I: IO + Copy>(mut io: I, action: ProposalAction) -> Result<(), ContractError> {
+    match action {
+        ProposalAction::SetOwner(args) => {
+            let mut state = state::get_state(&io)?;
+            if state.owner_id == args.new_owner {
+                return Err(errors::ERR_SAME_OWNER.into());
+            }
+            state.owner_id = args.new_owner;
+            state::set_state(&mut io, &state)?;
+            emit_event(&AdminEvent::OwnerChanged {
+                new_owner: state.owner_id,
+            });
+        }
+        ProposalAction::SetUpgradeDelayBlocks(args) => {
+            let mut state = state::get_state(&io)?;
+            state.upgrade_delay_blocks = args.upgrade_delay_blocks;
+            state::set_state(&mut io, &state)?;
+            emit_event(&AdminEvent::UpgradeDelayBlocksChanged {
+                upgrade_delay_blocks: args.upgrade_delay_blocks,
+            });
+        }
+        ProposalAction::SetKeyManager(args) => {
+            let mut state = state::get_state(&io)?;
+            state.key_manager = args.key_manager;
+            state::set_state(&mut io, &state)?;
+            emit_event(&AdminEvent::KeyManagerChanged {
+                key_manager: state.key_manager,
+            });
+        }
+        ProposalAction::SetSiloParams(args) => {
+            emit_event(&AdminEvent::silo_params_changed(args.as_ref()));
+            silo::set_silo_params(&mut io, args);
+        }
+        ProposalAction::FactoryUpdate(code) => {
+            let code_hash = aurora_engine_sdk::sha256(&code);
+            xcc::update_router_code(&mut io, &xcc::RouterCode::new(code));
+            emit_event(&AdminEvent::RouterCodeUpdated { code_hash });
+        }
+        ProposalAction::SetEthConnectorContractAccount(args) => {
+            #[cfg(feature = "ext-connector")]
+            {
+                use crate::contract_methods::connector::{AdminControlled, EthConnectorContract};
+
+                let mut connector = EthConnectorContract::init(io)?;
+                connector.set_eth_connector_contract_account(&args.account);
+                connector.set_withdraw_serialize_type(&args.withdraw_serialize_type);
+                emit_event(&AdminEvent::EthConnectorAccountChanged {
+                    account: args.account,
+                });
+            }
+            #[cfg(not(feature = "ext-connector"))]
+            {
+                let _ = args;
+                return Err(errors::ERR_UNSUPPORTED_PROPOSAL_ACTION.into());
+            }
+        }
+        ProposalAction::GrantRole(args) => roles::grant(&mut io, args)?,
+        ProposalAction::RevokeRole(args) => roles::revoke(&mut io, &args)?,
+        ProposalAction::UpdateGovernanceConfig(config) => set_config(&mut io, &config)?,
+    }
+
+    Ok(())
+}
+
+fn add_approval(proposal: &mut Proposal, approver: AccountId, block_height: u64) {
+    proposal.approvals.push(approver);
+
+    if proposal.approved_at.is_none() && proposal.approvals.len() >= proposal.threshold as usize {
+        proposal.approved_at = Some(block_height);
+    }
+}
+
+fn get_config<I: IO>(io: &I) -> Option<GovernanceConfig> {
+    io.read_storage(&bytes_to_key(KeyPrefix::Config, GOVERNANCE_CONFIG_KEY))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+fn set_config<I: IO>(io: &mut I, config: &GovernanceConfig) -> Result<(), ContractError> {
+    let mut approvers = config.approvers.clone();
+    approvers.sort();
+    approvers.dedup();
+
+    if config.threshold == 0
+        || approvers.len() != config.approvers.len()
+        || config.threshold as usize > approvers.len()
+    {
+        return Err(errors::ERR_INVALID_GOVERNANCE_CONFIG.into());
+    }
+
+    io.write_borsh(
+        &bytes_to_key(KeyPrefix::Config, GOVERNANCE_CONFIG_KEY),
+        config,
+    );
+    emit_event(&AdminEvent::GovernanceConfigChanged {
+        approvers: config.approvers.clone(),
+        threshold: config.threshold,
+    });
+    Ok(())
+}
+
+fn get_next_proposal_id<I: IO>(io: &I) -> u64 {
+    io.read_u64(&next_proposal_id_key()).unwrap_or_default()
+}
+
+/// Storage key of the id which will be assigned to the next created proposal.
+fn next_proposal_id_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::Config, NEXT_PROPOSAL_ID_KEY)
+}
+
+fn get_pending_proposal<I: IO>(io: &I, id: u64) -> Result<Proposal, ContractError> {
+    let proposal = read_proposal(io, id).ok_or(errors::ERR_PROPOSAL_NOT_FOUND)?;
+
+    if proposal.status != ProposalStatus::Pending {
+        return Err(errors::ERR_PROPOSAL_NOT_PENDING.into());
+    }
+
+    Ok(proposal)
+}
+
+fn read_proposal<I: IO>(io: &I, id: u64) -> Option<Proposal> {
+    io.read_storage(&proposal_key(id))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+fn set_proposal<I: IO>(io: &mut I, id: u64, proposal: &Proposal) {
+    io.write_borsh(&proposal_key(id), proposal);
+}
+
+fn proposal_key(id: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(PROPOSAL_KEY.len() + 8);
+    bytes.extend_from_slice(PROPOSAL_KEY);
+    bytes.extend_from_slice(&id.to_le_bytes());
+    bytes_to_key(KeyPrefix::Config, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    fn config(approvers: &[&str], threshold: u32) -> GovernanceConfig {
+        GovernanceConfig {
+            approvers: approvers.iter().map(|a| a.parse().unwrap()).collect(),
+            threshold,
+            default_timelock_blocks: 0,
+            action_timelocks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_invalid_governance_config_is_rejected() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+
+        assert!(set_config(&mut io, &config(&["a.near", "b.near"], 0)).is_err());
+        assert!(set_config(&mut io, &config(&["a.near", "b.near"], 3)).is_err());
+        assert!(set_config(&mut io, &config(&["a.near", "a.near"], 2)).is_err());
+        assert!(get_config(&io).is_none());
+
+        let valid = config(&["a.near", "b.near"], 2);
+        assert!(set_config(&mut io, &valid).is_ok());
+        assert_eq!(get_config(&io), Some(valid));
+    }
+
+    #[test]
+    fn test_proposal_is_approved_when_threshold_is_reached() {
+        let mut proposal = Proposal {
+            proposer: "a.near".parse().unwrap(),
+            action: ProposalAction::FactoryUpdate(Vec::new()),
+            approvers: config(&["a.near", "b.near", "c.near"], 2).approvers,
+            threshold: 2,
+            timelock_blocks: 10,
+            approvals: Vec::new(),
+            created_at: 1,
+            approved_at: None,
+            status: ProposalStatus::Pending,
+        };
+
+        add_approval(&mut proposal, "a.near".parse().unwrap(), 1);
+        assert_eq!(proposal.unlock_height(), None);
+
+        add_approval(&mut proposal, "b.near".parse().unwrap(), 5);
+        assert_eq!(proposal.unlock_height(), Some(15));
+
+        // Further approvals don't move the unlock height.
+        add_approval(&mut proposal, "c.near".parse().unwrap(), 7);
+        assert_eq!(proposal.unlock_height(), Some(15));
+    }
+}
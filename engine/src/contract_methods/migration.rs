@@ -0,0 +1,535 @@
+//! Migrations of the engine-owned storage records between the versions of their schemas.
+//! Each record has a version stored in the contract. The migration steps from the registry
+//! below are applied in order in `state_migration`, which is called right after the deployment
+//! of the new code. Steps processing many items are suspended once the attached gas is about
+//! to run out and continue from the same position on the next `state_migration` call.
+
+use crate::{
+    contract_methods::{require_role, ContractError},
+    engine, errors, state,
+};
+use aurora_engine_sdk::{
+    env::Env,
+    io::{StorageIntermediate, IO},
+};
+use aurora_engine_types::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    parameters::{
+        migration::{
+            MigrationCursor, MigrationProgress, RecordVersion, StateMigrationArgs, StorageRecord,
+        },
+        roles::Role,
+    },
+    storage::{bytes_to_key, KeyPrefix},
+    types::{Address, NearGas},
+    vec, Vec,
+};
+
+const MIGRATION_KEY: &[u8] = b"MIGRATION";
+const CODE_MIGRATION_KEY: &[u8] = b"CODE_MIGRATION";
+/// Gas which should remain after migrating an item to store the progress of the migration.
+const GAS_RESERVE: NearGas = NearGas::new(10_000_000_000_000);
+/// Version of the records created before the versions have been introduced.
+const INITIAL_VERSION: u32 = 1;
+
+/// Result of running a migration step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    Completed,
+    /// The budget has been exhausted after migrating the provided number of items.
+    Suspended(u64),
+}
+
+/// Limits the amount of work done in one `state_migration` call.
+pub trait Budget {
+    fn is_exhausted(&self) -> bool;
+}
+
+/// Budget which is exhausted when the remaining gas of the call is less than `GAS_RESERVE`.
+pub struct GasBudget<'a, E> {
+    env: &'a E,
+}
+
+impl<'a, E: Env> GasBudget<'a, E> {
+    pub const fn new(env: &'a E) -> Self {
+        Self { env }
+    }
+}
+
+impl<E: Env> Budget for GasBudget<'_, E> {
+    fn is_exhausted(&self) -> bool {
+        let remaining = self
+            .env
+            .prepaid_gas()
+            .as_u64()
+            .saturating_sub(self.env.used_gas().as_u64());
+        remaining < GAS_RESERVE.as_u64()
+    }
+}
+
+/// Migration of the record to the next version of its schema. Steps processing many items
+/// should check the budget before each item and return `StepOutcome::Suspended` once it
+/// has been exhausted. The suspended step is called again with the returned position.
+pub struct MigrationStep<I> {
+    pub record: StorageRecord,
+    pub to_version: u32,
+    pub migrate: fn(&mut I, u64, &dyn Budget) -> Result<StepOutcome, ContractError>,
+}
+
+/// Registry of the migration steps in the order they are applied.
+#[must_use]
+pub fn steps<I: IO + Copy>() -> Vec<MigrationStep<I>> {
+    vec![
+        MigrationStep {
+            record: StorageRecord::Connector,
+            to_version: INITIAL_VERSION,
+            migrate: keep_initial_version,
+        },
+        MigrationStep {
+            record: StorageRecord::Xcc,
+            to_version: INITIAL_VERSION,
+            migrate: keep_initial_version,
+        },
+        MigrationStep {
+            record: StorageRecord::Silo,
+            to_version: INITIAL_VERSION,
+            migrate: keep_initial_version,
+        },
+        MigrationStep {
+            record: StorageRecord::Relayer,
+            to_version: INITIAL_VERSION,
+            migrate: keep_initial_version,
+        },
+        MigrationStep {
+            record: StorageRecord::EngineState,
+            to_version: 2,
+            migrate: migrate_engine_state_v2,
+        },
+        MigrationStep {
+            record: StorageRecord::Code,
+            to_version: CODE_DEDUPLICATION_VERSION,
+            migrate: migrate_code_v2,
+        },
+    ]
+}
+
+/// Version of the `Code` record in which the code is stored once per code hash.
+const CODE_DEDUPLICATION_VERSION: u32 = 2;
+
+pub fn state_migration<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), ContractError> {
+    // The function is called by the contract itself after the deployment of the new code.
+    // Suspended migrations could also be continued by the upgraders.
+    if env.assert_private_call().is_err() {
+        let state = state::get_state(&io)?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+    }
+
+    if !io.read_input().is_empty() {
+        let args: StateMigrationArgs = io.read_input_borsh()?;
+        queue_code_migration(&mut io, args.code_addresses);
+    }
+
+    let steps = steps();
+    migrate(&mut io, &steps, &GasBudget::new(env))?;
+
+    let progress = get_progress(&io, &steps);
+    let bytes = borsh::to_vec(&progress).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_migration_progress<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let progress = get_progress(&io, &steps());
+    let bytes = borsh::to_vec(&progress).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+/// Apply the pending migration steps until all of them are completed or the budget is exhausted.
+pub fn migrate<I: IO + Copy>(
+    io: &mut I,
+    steps: &[MigrationStep<I>],
+    budget: &dyn Budget,
+) -> Result<(), ContractError> {
+    let mut status = read_status(io);
+
+    for step in steps {
+        let version = status.version(step.record);
+
+        if step.to_version <= version {
+            continue;
+        }
+
+        if step.to_version != version + 1 {
+            return Err(errors::ERR_MIGRATION_VERSION_GAP.into());
+        }
+
+        if budget.is_exhausted() {
+            break;
+        }
+
+        let position = status
+            .cursor
+            .as_ref()
+            .filter(|cursor| cursor.record == step.record && cursor.to_version == step.to_version)
+            .map_or(0, |cursor| cursor.position);
+
+        match (step.migrate)(io, position, budget)? {
+            StepOutcome::Completed => {
+                status.set_version(step.record, step.to_version);
+                status.cursor = None;
+            }
+            StepOutcome::Suspended(position) => {
+                status.cursor = Some(MigrationCursor {
+                    record: step.record,
+                    to_version: step.to_version,
+                    position,
+                });
+                break;
+            }
+        }
+    }
+
+    write_status(io, &status);
+    Ok(())
+}
+
+/// Mark all the records as having the latest versions. Used on initialization of the contract
+/// since there is nothing to migrate.
+pub fn set_latest_versions<I: IO + Copy>(io: &mut I, steps: &[MigrationStep<I>]) {
+    let status = MigrationStatus {
+        versions: latest_versions(steps),
+        cursor: None,
+    };
+    write_status(io, &status);
+}
+
+#[must_use]
+pub fn get_progress<I: IO>(io: &I, steps: &[MigrationStep<I>]) -> MigrationProgress {
+    let status = read_status(io);
+    let pending_steps = steps
+        .iter()
+        .filter(|step| step.to_version > status.version(step.record))
+        .count();
+
+    MigrationProgress {
+        versions: StorageRecord::ALL
+            .into_iter()
+            .map(|record| RecordVersion {
+                record,
+                version: status.version(record),
+            })
+            .collect(),
+        latest_versions: latest_versions(steps),
+        pending_steps: u32::try_from(pending_steps).unwrap_or(u32::MAX),
+        cursor: status.cursor,
+    }
+}
+
+/// The layout of the records created before the versions have been introduced. There is
+/// nothing to migrate, the step registers the version the first schema change migrates from.
+#[allow(clippy::unnecessary_wraps)]
+fn keep_initial_version<I: IO + Copy>(
+    _io: &mut I,
+    _position: u64,
+    _budget: &dyn Budget,
+) -> Result<StepOutcome, ContractError> {
+    Ok(StepOutcome::Completed)
+}
+
+/// Rewrite the engine state in the latest layout, so it isn't converted from the legacy layouts
+/// on every read.
+fn migrate_engine_state_v2<I: IO + Copy>(
+    io: &mut I,
+    _position: u64,
+    _budget: &dyn Budget,
+) -> Result<StepOutcome, ContractError> {
+    let state = state::get_state(io)?;
+    state::set_state(io, &state)?;
+    Ok(StepOutcome::Completed)
+}
+
+/// Move the code of the queued addresses to the shared code storage. The addresses whose code
+/// is already stored by its hash are skipped.
+fn migrate_code_v2<I: IO + Copy>(
+    io: &mut I,
+    position: u64,
+    budget: &dyn Budget,
+) -> Result<StepOutcome, ContractError> {
+    let queue = read_code_migration_queue(io);
+    let position = usize::try_from(position).unwrap_or(usize::MAX);
+
+    for (index, address) in queue.iter().enumerate().skip(position) {
+        if budget.is_exhausted() {
+            return Ok(StepOutcome::Suspended(
+                u64::try_from(index).unwrap_or(u64::MAX),
+            ));
+        }
+
+        engine::migrate_code(io, address);
+    }
+
+    io.remove_storage(&code_migration_key());
+    Ok(StepOutcome::Completed)
+}
+
+/// Add the addresses to the queue of the code migration step. The step is applied again if it
+/// has been completed already, the addresses queued before are kept in place, so the position
+/// of a suspended step remains valid.
+fn queue_code_migration<I: IO>(io: &mut I, addresses: Vec<Address>) {
+    if addresses.is_empty() {
+        return;
+    }
+
+    let mut queue = read_code_migration_queue(io);
+    queue.extend(addresses);
+    io.write_borsh(&code_migration_key(), &queue);
+
+    let mut status = read_status(io);
+    if status.version(StorageRecord::Code) >= CODE_DEDUPLICATION_VERSION {
+        status.set_version(StorageRecord::Code, CODE_DEDUPLICATION_VERSION - 1);
+        write_status(io, &status);
+    }
+}
+
+fn read_code_migration_queue<I: IO>(io: &I) -> Vec<Address> {
+    io.read_storage(&code_migration_key())
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_default()
+}
+
+fn latest_versions<I>(steps: &[MigrationStep<I>]) -> Vec<RecordVersion> {
+    StorageRecord::ALL
+        .into_iter()
+        .map(|record| RecordVersion {
+            record,
+            version: steps
+                .iter()
+                .filter(|step| step.record == record)
+                .map(|step| step.to_version)
+                .fold(INITIAL_VERSION, u32::max),
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+struct MigrationStatus {
+    versions: Vec<RecordVersion>,
+    cursor: Option<MigrationCursor>,
+}
+
+impl MigrationStatus {
+    fn version(&self, record: StorageRecord) -> u32 {
+        self.versions
+            .iter()
+            .find(|v| v.record == record)
+            .map_or(INITIAL_VERSION, |v| v.version)
+    }
+
+    fn set_version(&mut self, record: StorageRecord, version: u32) {
+        match self.versions.iter_mut().find(|v| v.record == record) {
+            Some(v) => v.version = version,
+            None => self.versions.push(RecordVersion { record, version }),
+        }
+    }
+}
+
+fn read_status<I: IO>(io: &I) -> MigrationStatus {
+    io.read_storage(&migration_key())
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_default()
+}
+
+fn write_status<I: IO>(io: &mut I, status: &MigrationStatus) {
+    io.write_borsh(&migration_key(), status);
+}
+
+fn migration_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::Config, MIGRATION_KEY)
+}
+
+fn code_migration_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::Config, CODE_MIGRATION_KEY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use aurora_engine_types::Cow;
+    use std::cell::{Cell, RefCell};
+
+    const ITEMS: u64 = 5;
+
+    /// Budget which allows the provided number of checks.
+    struct ChecksBudget(Cell<u32>);
+
+    impl Budget for ChecksBudget {
+        fn is_exhausted(&self) -> bool {
+            let remaining = self.0.get();
+            self.0.set(remaining.saturating_sub(1));
+            remaining == 0
+        }
+    }
+
+    fn item_key(index: u64) -> Vec<u8> {
+        bytes_to_key(KeyPrefix::Config, &index.to_le_bytes())
+    }
+
+    fn double_items<I: IO + Copy>(
+        io: &mut I,
+        position: u64,
+        budget: &dyn Budget,
+    ) -> Result<StepOutcome, ContractError> {
+        for index in position..ITEMS {
+            if budget.is_exhausted() {
+                return Ok(StepOutcome::Suspended(index));
+            }
+
+            let value = io.read_u64(&item_key(index)).unwrap();
+            io.write_storage(&item_key(index), &(value * 2).to_le_bytes());
+        }
+
+        Ok(StepOutcome::Completed)
+    }
+
+    fn test_steps<'a>() -> Vec<MigrationStep<StoragePointer<'a>>> {
+        vec![MigrationStep {
+            record: StorageRecord::EngineState,
+            to_version: 2,
+            migrate: double_items,
+        }]
+    }
+
+    #[test]
+    fn test_suspended_migration_is_resumed() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let steps = test_steps();
+
+        for index in 0..ITEMS {
+            io.write_storage(&item_key(index), &index.to_le_bytes());
+        }
+
+        // One check of the budget is done before the step and two items are migrated.
+        migrate(&mut io, &steps, &ChecksBudget(Cell::new(3))).unwrap();
+        let progress = get_progress(&io, &steps);
+        assert_eq!(progress.pending_steps, 1);
+        assert_eq!(
+            progress.cursor,
+            Some(MigrationCursor {
+                record: StorageRecord::EngineState,
+                to_version: 2,
+                position: 2,
+            })
+        );
+
+        migrate(&mut io, &steps, &ChecksBudget(Cell::new(u32::MAX))).unwrap();
+        let progress = get_progress(&io, &steps);
+        assert!(progress.is_completed());
+        assert_eq!(progress.cursor, None);
+        assert_eq!(progress.versions, progress.latest_versions);
+
+        // Each item is migrated exactly once.
+        for index in 0..ITEMS {
+            assert_eq!(io.read_u64(&item_key(index)).unwrap(), index * 2);
+        }
+
+        // Completed steps aren't applied again.
+        migrate(&mut io, &steps, &ChecksBudget(Cell::new(u32::MAX))).unwrap();
+        assert_eq!(io.read_u64(&item_key(1)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_no_migrations_after_initialization() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let steps = test_steps();
+
+        assert_eq!(get_progress(&io, &steps).pending_steps, 1);
+
+        set_latest_versions(&mut io, &steps);
+        let progress = get_progress(&io, &steps);
+        assert!(progress.is_completed());
+        assert_eq!(progress.versions, progress.latest_versions);
+    }
+
+    #[test]
+    fn test_queued_code_is_migrated() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let steps = steps();
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        let addresses: Vec<_> = (1..=3).map(|i| Address::from_array([i; 20])).collect();
+        let legacy_keys: Vec<_> = addresses
+            .iter()
+            .map(|address| aurora_engine_types::storage::address_to_key(KeyPrefix::Code, address))
+            .collect();
+
+        state::set_state(&mut io, &state::EngineState::default()).unwrap();
+        set_latest_versions(&mut io, &steps);
+        for key in &legacy_keys {
+            io.write_storage(key, &code);
+        }
+
+        queue_code_migration(&mut io, addresses.clone());
+        assert_eq!(get_progress(&io, &steps).pending_steps, 1);
+
+        // One check of the budget is done before the step and one address is migrated.
+        migrate(&mut io, &steps, &ChecksBudget(Cell::new(2))).unwrap();
+        assert_eq!(
+            get_progress(&io, &steps)
+                .cursor
+                .map(|cursor| cursor.position),
+            Some(1)
+        );
+
+        migrate(&mut io, &steps, &ChecksBudget(Cell::new(u32::MAX))).unwrap();
+        assert!(get_progress(&io, &steps).is_completed());
+        assert!(read_code_migration_queue(&io).is_empty());
+        for (address, key) in addresses.iter().zip(&legacy_keys) {
+            assert!(io.read_storage(key).is_none());
+            assert_eq!(engine::get_code(&io, address), code);
+        }
+    }
+
+    #[test]
+    fn test_legacy_engine_state_is_rewritten() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let key = bytes_to_key(KeyPrefix::Config, state::STATE_KEY);
+        let legacy = borsh::to_vec(&state::BorshableEngineStateV1 {
+            chain_id: [1; 32],
+            owner_id: Cow::Owned("owner.near".parse().unwrap()),
+            bridge_prover_id: Cow::Owned("prover.near".parse().unwrap()),
+            upgrade_delay_blocks: 3,
+        })
+        .unwrap();
+        io.write_storage(&key, &legacy);
+
+        // The legacy layout is read without modifying the storage.
+        let state = state::get_state(&io).unwrap();
+        assert_eq!(io.read_storage(&key).unwrap().to_vec(), legacy);
+
+        migrate(&mut io, &steps(), &ChecksBudget(Cell::new(u32::MAX))).unwrap();
+        assert_eq!(
+            io.read_storage(&key).unwrap().to_vec(),
+            state.borsh_serialize().unwrap()
+        );
+        assert_eq!(state::get_state(&io).unwrap(), state);
+    }
+
+    #[test]
+    fn test_migration_version_gap() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let steps = vec![MigrationStep {
+            record: StorageRecord::EngineState,
+            to_version: 3,
+            migrate: double_items,
+        }];
+
+        assert!(migrate(&mut io, &steps, &ChecksBudget(Cell::new(u32::MAX))).is_err());
+    }
+}
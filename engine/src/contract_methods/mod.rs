@@ -7,12 +7,21 @@
 //! the smart contract and the standalone.
 
 use crate::{errors, state};
-use aurora_engine_types::{account_id::AccountId, fmt, types::Address, Box};
+use aurora_engine_sdk::io::IO;
+use aurora_engine_types::{
+    account_id::AccountId, events::AdminEvent, fmt, parameters::roles::Role, types::Address, Box,
+};
 
 pub mod admin;
 pub mod connector;
 pub mod evm_transactions;
+pub mod governance;
+pub mod migration;
+pub mod mt;
+pub mod nft;
+pub mod roles;
 pub mod silo;
+pub mod withdrawal_limits;
 pub mod xcc;
 
 pub struct ContractError {
@@ -85,6 +94,21 @@ fn require_owner_only(
     Ok(())
 }
 
+/// The owner implicitly has all the roles, so it passes the check as well.
+fn require_role<I: IO>(
+    io: &I,
+    state: &state::EngineState,
+    role: Role,
+    predecessor_account_id: &AccountId,
+) -> Result<(), ContractError> {
+    if &state.owner_id != predecessor_account_id
+        && !roles::has_role(io, role, predecessor_account_id)
+    {
+        return Err(errors::ERR_NOT_ALLOWED.into());
+    }
+    Ok(())
+}
+
 fn require_key_manager_only(
     state: &state::EngineState,
     predecessor_account_id: &AccountId,
@@ -99,6 +123,11 @@ fn require_key_manager_only(
     Ok(())
 }
 
+/// Emit the NEP-297 event about the admin or configuration change.
+pub(crate) fn emit_event(event: &AdminEvent) {
+    aurora_engine_sdk::log(&event.to_log());
+}
+
 fn predecessor_address(predecessor_account_id: &AccountId) -> Address {
     aurora_engine_sdk::types::near_account_to_evm_address(predecessor_account_id.as_bytes())
 }
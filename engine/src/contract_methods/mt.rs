@@ -0,0 +1,124 @@
+use crate::{
+    contract_methods::{
+        emit_event, predecessor_address, require_role, require_running, ContractError,
+    },
+    engine::Engine,
+    errors,
+    hashchain::with_hashchain,
+    mt, state,
+};
+use aurora_engine_modexp::AuroraModExp;
+use aurora_engine_sdk::{
+    env::Env,
+    io::{StorageIntermediate, IO},
+    promise::PromiseHandler,
+};
+use aurora_engine_types::{
+    account_id::AccountId,
+    events::AdminEvent,
+    parameters::{
+        engine::{errors::ParseArgsError, SubmitResult},
+        mt::{MtExitCallbackArgs, NEP245MtOnTransferArgs},
+        roles::Role,
+    },
+    types::{Address, PromiseResult},
+};
+use function_name::named;
+
+#[named]
+pub fn set_erc1155_template<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        let bytes = io.read_input().to_vec();
+        let code_hash = aurora_engine_sdk::sha256(&bytes);
+        mt::set_template(&mut io, &bytes);
+        emit_event(&AdminEvent::Erc1155TemplateUpdated { code_hash });
+        Ok(())
+    })
+}
+
+/// NEP-245 receiver: mints the transferred tokens on the ERC-1155 contract mirroring
+/// the NEP-245 contract (the predecessor).
+#[named]
+pub fn mt_on_transfer<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<Option<SubmitResult>, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        let current_account_id = env.current_account_id();
+        let predecessor_account_id = env.predecessor_account_id();
+        let args: NEP245MtOnTransferArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(Into::<ParseArgsError>::into)?;
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&predecessor_account_id),
+            current_account_id.clone(),
+            io,
+            env,
+        );
+        let result = mt::receive_mt(
+            &mut engine,
+            io,
+            &predecessor_account_id,
+            &args,
+            &current_account_id,
+            handler,
+        );
+        Ok(result.ok())
+    })
+}
+
+/// Callback of the `mt_transfer` / `mt_batch_transfer` promise created by the exit
+/// to NEAR precompile. The withdrawn tokens are minted back if the transfer failed.
+#[named]
+pub fn mt_exit_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<Option<SubmitResult>, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        env.assert_private_call()?;
+
+        // This function should only be called as the callback of
+        // exactly one promise.
+        if handler.promise_results_count() != 1 {
+            return Err(errors::ERR_PROMISE_COUNT.into());
+        }
+        if let Some(PromiseResult::Successful(_)) = handler.promise_result(0) {
+            return Ok(None);
+        }
+
+        let args: MtExitCallbackArgs = io.read_input_borsh()?;
+        let current_account_id = env.current_account_id();
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&current_account_id),
+            current_account_id.clone(),
+            io,
+            env,
+        );
+        let result = mt::refund(&mut engine, io, &args, &current_account_id, handler)?;
+        Ok(Some(result))
+    })
+}
+
+pub fn get_erc1155_from_nep245<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let nep245: AccountId = io.read_input_borsh()?;
+    let address = mt::get_erc1155_from_nep245(io, &nep245).ok_or(errors::ERR_NEP245_NOT_FOUND)?;
+    io.return_output(address.as_bytes());
+    Ok(())
+}
+
+pub fn get_nep245_from_erc1155<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let address = Address::from_array(io.read_input_arr20()?);
+    let nep245 = mt::get_nep245_from_erc1155(io, &address).ok_or(errors::ERR_ERC1155_NOT_FOUND)?;
+    io.return_output(nep245.as_bytes());
+    Ok(())
+}
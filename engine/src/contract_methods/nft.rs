@@ -0,0 +1,142 @@
+use crate::{
+    contract_methods::{
+        emit_event, predecessor_address, require_role, require_running, ContractError,
+    },
+    engine::Engine,
+    errors,
+    hashchain::with_hashchain,
+    nft, state,
+};
+use aurora_engine_modexp::AuroraModExp;
+use aurora_engine_sdk::{
+    env::Env,
+    io::{StorageIntermediate, IO},
+    promise::PromiseHandler,
+};
+use aurora_engine_types::{
+    account_id::AccountId,
+    events::AdminEvent,
+    parameters::{
+        engine::{errors::ParseArgsError, SubmitResult},
+        nft::{Erc721Metadata, NEP171NftOnTransferArgs, SyncErc721MetadataArgs},
+        roles::Role,
+    },
+    types::{Address, PromiseResult},
+};
+use function_name::named;
+
+#[named]
+pub fn set_erc721_template<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        let bytes = io.read_input().to_vec();
+        let code_hash = aurora_engine_sdk::sha256(&bytes);
+        nft::set_template(&mut io, &bytes);
+        emit_event(&AdminEvent::Erc721TemplateUpdated { code_hash });
+        Ok(())
+    })
+}
+
+/// NEP-171 receiver: mints the transferred token on the ERC-721 contract mirroring
+/// the NEP-171 contract (the predecessor).
+#[named]
+pub fn nft_on_transfer<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<Option<SubmitResult>, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        let current_account_id = env.current_account_id();
+        let predecessor_account_id = env.predecessor_account_id();
+        let args: NEP171NftOnTransferArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(Into::<ParseArgsError>::into)?;
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&predecessor_account_id),
+            current_account_id.clone(),
+            io,
+            env,
+        );
+        let result = nft::receive_nft(
+            &mut engine,
+            io,
+            &predecessor_account_id,
+            &args,
+            &current_account_id,
+            handler,
+        );
+        Ok(result.ok())
+    })
+}
+
+/// Requests the metadata of the NEP-171 contract to update the metadata of the
+/// corresponding ERC-721 contract.
+#[named]
+pub fn sync_erc721_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        require_running(&state::get_state(&io)?)?;
+        let args: SyncErc721MetadataArgs = io.read_input_borsh()?;
+        if nft::get_erc721_from_nep171(io, &args.nep171).is_none() {
+            return Err(errors::ERR_NEP171_NOT_FOUND.into());
+        }
+        let promise = nft::metadata_promise(&args.nep171, &env.current_account_id());
+        // Safety: the callback is the trusted engine method checking the promise result.
+        let promise_id = unsafe { handler.promise_create_with_callback(&promise) };
+        handler.promise_return(promise_id);
+        Ok(())
+    })
+}
+
+#[named]
+pub fn erc721_metadata_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<SubmitResult, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        env.assert_private_call()?;
+        let metadata: Erc721Metadata = match handler.promise_result(0) {
+            Some(PromiseResult::Successful(bytes)) => {
+                serde_json::from_slice(&bytes).map_err(Into::<ParseArgsError>::into)?
+            }
+            _ => return Err(b"ERR_CALLBACK_OF_FAILED_PROMISE".into()),
+        };
+        let args: SyncErc721MetadataArgs = io.read_input_borsh()?;
+        let erc721 =
+            nft::get_erc721_from_nep171(io, &args.nep171).ok_or(errors::ERR_NEP171_NOT_FOUND)?;
+        let current_account_id = env.current_account_id();
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&current_account_id),
+            current_account_id,
+            io,
+            env,
+        );
+        let result = nft::set_metadata(&mut engine, erc721, metadata, handler)?;
+        Ok(result)
+    })
+}
+
+pub fn get_erc721_from_nep171<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let nep171: AccountId = io.read_input_borsh()?;
+    let address = nft::get_erc721_from_nep171(io, &nep171).ok_or(errors::ERR_NEP171_NOT_FOUND)?;
+    io.return_output(address.as_bytes());
+    Ok(())
+}
+
+pub fn get_nep171_from_erc721<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let address = Address::from_array(io.read_input_arr20()?);
+    let nep171 = nft::get_nep171_from_erc721(io, &address).ok_or(errors::ERR_ERC721_NOT_FOUND)?;
+    io.return_output(nep171.as_bytes());
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::{
+    contract_methods::{
+        emit_event, governance, require_owner_only, require_running, ContractError,
+    },
+    errors,
+    hashchain::with_hashchain,
+    prelude::{BTreeSet, Vec},
+    state,
+};
+use aurora_engine_sdk::{
+    env::Env,
+    io::{StorageIntermediate, IO},
+};
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh,
+    events::AdminEvent,
+    parameters::roles::{Role, RoleArgs, RoleKindArgs},
+    storage::{bytes_to_key, KeyPrefix},
+};
+use function_name::named;
+
+const ROLE_KEY: &[u8] = b"ROLE";
+
+#[named]
+pub fn grant_role<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+        governance::require_no_governance(&io)?;
+
+        let args: RoleArgs = io.read_input_borsh()?;
+        grant(&mut io, args)
+    })
+}
+
+#[named]
+pub fn revoke_role<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+        governance::require_no_governance(&io)?;
+
+        let args: RoleArgs = io.read_input_borsh()?;
+        revoke(&mut io, &args)
+    })
+}
+
+pub fn get_role_members<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let args: RoleKindArgs = io.read_input_borsh()?;
+    let members: Vec<_> = get_members(&io, args.role).into_iter().collect();
+    let bytes = borsh::to_vec(&members).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_account_roles<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let account_id: AccountId = io.read_input_borsh()?;
+    let roles: Vec<_> = Role::ALL
+        .into_iter()
+        .filter(|role| has_role(&io, *role, &account_id))
+        .collect();
+    let bytes = borsh::to_vec(&roles).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+/// Grant the role to the account.
+pub fn grant<I: IO>(io: &mut I, args: RoleArgs) -> Result<(), ContractError> {
+    let mut members = get_members(io, args.role);
+
+    if !members.insert(args.account_id.clone()) {
+        return Err(errors::ERR_ROLE_ALREADY_GRANTED.into());
+    }
+
+    set_members(io, args.role, &members);
+    emit_event(&AdminEvent::RoleGranted {
+        role: args.role,
+        account_id: args.account_id,
+    });
+    Ok(())
+}
+
+/// Revoke the role from the account.
+pub fn revoke<I: IO>(io: &mut I, args: &RoleArgs) -> Result<(), ContractError> {
+    let mut members = get_members(io, args.role);
+
+    if !members.remove(&args.account_id) {
+        return Err(errors::ERR_ROLE_NOT_GRANTED.into());
+    }
+
+    set_members(io, args.role, &members);
+    emit_event(&AdminEvent::RoleRevoked {
+        role: args.role,
+        account_id: args.account_id.clone(),
+    });
+    Ok(())
+}
+
+/// Return the accounts which have been granted the `role`.
+pub fn get_members<I: IO>(io: &I, role: Role) -> BTreeSet<AccountId> {
+    io.read_storage(&role_key(role))
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_default()
+}
+
+/// Check if the `account_id` has been granted the `role`.
+pub fn has_role<I: IO>(io: &I, role: Role, account_id: &AccountId) -> bool {
+    get_members(io, role).contains(account_id)
+}
+
+fn set_members<I: IO>(io: &mut I, role: Role, members: &BTreeSet<AccountId>) {
+    let key = role_key(role);
+
+    if members.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, members);
+    }
+}
+
+fn role_key(role: Role) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(ROLE_KEY.len() + 1);
+    bytes.extend_from_slice(ROLE_KEY);
+    bytes.push(role as u8);
+    bytes_to_key(KeyPrefix::Config, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_role_members_are_stored_per_role() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let pauser: AccountId = "pauser.near".parse().unwrap();
+        let upgrader: AccountId = "upgrader.near".parse().unwrap();
+
+        assert!(get_members(&io, Role::Pauser).is_empty());
+
+        set_members(&mut io, Role::Pauser, &BTreeSet::from([pauser.clone()]));
+        set_members(&mut io, Role::Upgrader, &BTreeSet::from([upgrader.clone()]));
+
+        assert!(has_role(&io, Role::Pauser, &pauser));
+        assert!(!has_role(&io, Role::Pauser, &upgrader));
+        assert!(has_role(&io, Role::Upgrader, &upgrader));
+        assert!(!has_role(&io, Role::SiloAdmin, &pauser));
+
+        set_members(&mut io, Role::Pauser, &BTreeSet::new());
+        assert!(!has_role(&io, Role::Pauser, &pauser));
+        assert!(!io.storage_has_key(&role_key(Role::Pauser)));
+    }
+}
@@ -2,6 +2,8 @@ use aurora_engine_sdk::io::{StorageIntermediate, IO};
 #[cfg(feature = "contract")]
 use aurora_engine_sdk::{env::Env, types::SdkUnwrap};
 use aurora_engine_types::account_id::AccountId;
+#[cfg(feature = "contract")]
+use aurora_engine_types::parameters::roles::Role;
 use aurora_engine_types::parameters::silo::{
     SiloParamsArgs, WhitelistArgs, WhitelistKind, WhitelistKindArgs, WhitelistStatusArgs,
 };
@@ -9,6 +11,8 @@ use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
 use aurora_engine_types::types::{Address, EthGas};
 use aurora_engine_types::AsBytes;
 
+#[cfg(feature = "contract")]
+use crate::contract_methods::roles;
 #[cfg(feature = "contract")]
 use crate::engine::EngineErrorKind;
 use crate::prelude::Vec;
@@ -113,12 +117,15 @@ pub fn get_whitelist_status<I: IO + Copy>(io: &I, args: &WhitelistKindArgs) -> W
     whitelist::get_whitelist_status(io, args)
 }
 
-/// Check if the calling user is admin or owner of the contract.
+/// Check if the calling user is admin, owner of the contract or has the `SiloAdmin` role.
 #[cfg(feature = "contract")]
 pub fn assert_admin<I: IO + Env + Copy>(io: &I) -> Result<(), EngineErrorKind> {
     let predecessor = io.predecessor_account_id();
 
-    if is_owner(io, &predecessor) || is_admin(io, &predecessor) {
+    if is_owner(io, &predecessor)
+        || roles::has_role(io, Role::SiloAdmin, &predecessor)
+        || is_admin(io, &predecessor)
+    {
         return Ok(());
     }
 
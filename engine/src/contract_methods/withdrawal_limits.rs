@@ -0,0 +1,118 @@
+use crate::{
+    contract_methods::{emit_event, require_role, require_running, ContractError},
+    engine, errors,
+    hashchain::with_hashchain,
+    state, withdrawal_limits,
+};
+use aurora_engine_precompiles::withdrawal_limits::{get_limit, set_limit};
+use aurora_engine_sdk::{env::Env, io::IO, promise::PromiseHandler};
+use aurora_engine_types::{
+    borsh,
+    events::AdminEvent,
+    parameters::{
+        engine::SubmitResult,
+        roles::Role,
+        withdrawal_limits::{SetWithdrawalLimitArgs, WithdrawalLimitTarget},
+        PromiseArgs,
+    },
+};
+use function_name::named;
+
+/// Sets or removes the withdrawal limit of a target.
+#[named]
+pub fn set_withdrawal_limit<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::RiskAdmin, &env.predecessor_account_id())?;
+        let args: SetWithdrawalLimitArgs = io.read_input_borsh()?;
+        if args
+            .limit
+            .as_ref()
+            .is_some_and(|limit| limit.window_secs == 0)
+        {
+            return Err(errors::ERR_INVALID_WITHDRAWAL_LIMIT.into());
+        }
+        set_limit(&mut io, &args.target, args.limit.as_ref());
+        emit_event(&AdminEvent::WithdrawalLimitChanged {
+            target: args.target,
+            limit: args.limit,
+        });
+        Ok(())
+    })
+}
+
+pub fn get_withdrawal_limit<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let target: WithdrawalLimitTarget = io.read_input_borsh()?;
+    let limit = get_limit(&io, &target);
+    let bytes = borsh::to_vec(&limit).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_queued_withdrawal<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let id: u64 = io.read_input_borsh()?;
+    let withdrawal = withdrawal_limits::get_queued_withdrawal(&io, id)
+        .ok_or(errors::ERR_QUEUED_WITHDRAWAL_NOT_FOUND)?;
+    let bytes = borsh::to_vec(&withdrawal).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+/// Creates the promise of a queued withdrawal.
+#[named]
+pub fn approve_queued_withdrawal<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::RiskAdmin, &env.predecessor_account_id())?;
+        let id: u64 = io.read_input_borsh()?;
+        let withdrawal = withdrawal_limits::take_queued_withdrawal(&mut io, id)
+            .ok_or(errors::ERR_QUEUED_WITHDRAWAL_NOT_FOUND)?;
+
+        // Safety: the promise was created by the exit precompiles, not by users directly.
+        match &withdrawal.promise {
+            PromiseArgs::Create(promise) => unsafe {
+                handler.promise_create_call(promise);
+            },
+            PromiseArgs::Callback(promise) => unsafe {
+                handler.promise_create_with_callback(promise);
+            },
+            PromiseArgs::Recursive(_) => {
+                return Err(errors::ERR_INVALID_QUEUED_WITHDRAWAL.into());
+            }
+        }
+        emit_event(&AdminEvent::QueuedWithdrawalApproved { id });
+        Ok(())
+    })
+}
+
+/// Drops a queued withdrawal, mints the withdrawn tokens back to the sender and removes
+/// the withdrawal from the usage of the limits.
+#[named]
+pub fn reject_queued_withdrawal<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<SubmitResult, ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&io, &state, Role::RiskAdmin, &env.predecessor_account_id())?;
+        let id: u64 = io.read_input_borsh()?;
+        let withdrawal = withdrawal_limits::take_queued_withdrawal(&mut io, id)
+            .ok_or(errors::ERR_QUEUED_WITHDRAWAL_NOT_FOUND)?;
+        withdrawal_limits::release_withdrawal(&mut io, &withdrawal.record);
+
+        let result = engine::refund_on_error(io, env, state, &withdrawal.refund, handler)?;
+        if !result.status.is_ok() {
+            return Err(errors::ERR_REFUND_FAILURE.into());
+        }
+        emit_event(&AdminEvent::QueuedWithdrawalRejected { id });
+        Ok(result)
+    })
+}
@@ -1,11 +1,15 @@
 use crate::{
-    contract_methods::{predecessor_address, require_owner_only, require_running, ContractError},
+    contract_methods::{
+        emit_event, governance, predecessor_address, require_owner_only, require_role,
+        require_running, ContractError,
+    },
     engine::Engine,
     errors,
     hashchain::{with_hashchain, with_logs_hashchain},
     state, xcc,
 };
 use aurora_engine_modexp::AuroraModExp;
+use aurora_engine_precompiles::xcc::cross_contract_call;
 use aurora_engine_sdk::{
     env::Env,
     io::{StorageIntermediate, IO},
@@ -13,9 +17,16 @@ use aurora_engine_sdk::{
 };
 use aurora_engine_types::{
     account_id::AccountId,
-    borsh, format,
-    parameters::{engine::SubmitResult, xcc::WithdrawWnearToRouterArgs},
+    borsh,
+    events::AdminEvent,
+    format,
+    parameters::{
+        engine::{CallArgs, FunctionCallArgsV2, SubmitResult},
+        roles::Role,
+        xcc::{WithdrawWnearToRouterArgs, XccEvmCallbackArgs},
+    },
     types::Address,
+    Vec,
 };
 use function_name::named;
 
@@ -63,15 +74,56 @@ pub fn withdraw_wnear_to_router<I: IO + Copy, E: Env, H: PromiseHandler>(
     })
 }
 
+/// Delivers the results of the promise scheduled by the cross contract call precompile to
+/// the EVM contract requested in the precompile input.
+#[named]
+pub fn xcc_evm_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<SubmitResult, ContractError> {
+    with_logs_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        env.assert_private_call()?;
+        let args: XccEvmCallbackArgs = io.read_input_borsh()?;
+        let promise_results: Vec<_> = (0..handler.promise_results_count())
+            .filter_map(|index| handler.promise_result(index))
+            .collect();
+        let input = xcc::evm_callback_input(args.callback.selector, args.sender, &promise_results);
+        // The call is made from the precompile address, so the contract is able to check
+        // that the results are delivered by the engine.
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            cross_contract_call::ADDRESS,
+            env.current_account_id(),
+            io,
+            env,
+        );
+        let call_args = CallArgs::V2(FunctionCallArgsV2 {
+            contract: args.callback.address,
+            value: [0u8; 32],
+            input,
+        });
+        let result = engine.call_with_args(call_args, handler)?;
+        let result_bytes = borsh::to_vec(&result).map_err(|_| errors::ERR_SERIALIZE)?;
+        io.return_output(&result_bytes);
+        Ok(result)
+    })
+}
+
 #[named]
 pub fn factory_update<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&io, &state, Role::Upgrader, &env.predecessor_account_id())?;
+        governance::require_no_governance(&io)?;
         let bytes = io.read_input().to_vec();
+        let code_hash = aurora_engine_sdk::sha256(&bytes);
         let router_bytecode = xcc::RouterCode::new(bytes);
         xcc::update_router_code(&mut io, &router_bytecode);
+        emit_event(&AdminEvent::RouterCodeUpdated { code_hash });
         Ok(())
     })
 }
@@ -98,6 +150,27 @@ pub fn factory_update_address_version<I: IO + Copy, E: Env, H: PromiseHandler>(
     })
 }
 
+#[named]
+pub fn factory_remove_address_version<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        require_running(&state::get_state(&io)?)?;
+        env.assert_private_call()?;
+        let check_close: Result<(), &[u8]> = match handler.promise_result_check() {
+            Some(true) => Ok(()),
+            Some(false) => Err(b"ERR_ROUTER_CLOSE_FAILED"),
+            None => Err(b"ERR_ROUTER_CLOSE_NOT_CALLBACK"),
+        };
+        check_close?;
+        let address: Address = io.read_input_borsh()?;
+        xcc::remove_code_version_of_address(&mut io, &address);
+        Ok(())
+    })
+}
+
 #[named]
 pub fn factory_set_wnear_address<I: IO + Copy, E: Env>(
     io: I,
@@ -107,8 +180,9 @@ pub fn factory_set_wnear_address<I: IO + Copy, E: Env>(
         let state = state::get_state(&io)?;
         require_running(&state)?;
         require_owner_only(&state, &env.predecessor_account_id())?;
-        let address = io.read_input_arr20()?;
-        xcc::set_wnear_address(&mut io, &Address::from_array(address));
+        let address = Address::from_array(io.read_input_arr20()?);
+        xcc::set_wnear_address(&mut io, &address);
+        emit_event(&AdminEvent::WnearAddressChanged { address });
         Ok(())
     })
 }
@@ -3,7 +3,6 @@ use crate::parameters::{
 };
 use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::PhantomData;
-use core::mem;
 use evm::backend::{Apply, ApplyBackend, Backend, Basic, Log};
 use evm::{executor, Opcode};
 use evm::{Config, CreateScheme, ExitError, ExitFatal, ExitReason};
@@ -18,20 +17,26 @@ use aurora_engine_sdk::promise::{PromiseHandler, PromiseId, ReadOnlyPromiseHandl
 use crate::accounting;
 #[cfg(not(feature = "ext-connector"))]
 use crate::contract_methods::connector;
+use crate::contract_methods::roles;
 use crate::contract_methods::silo;
-use crate::parameters::{DeployErc20TokenArgs, TransactionStatus};
+use crate::parameters::{
+    DeployErc20TokenArgs, DeployErc20TokenResult, Erc20DeployStatus, TransactionStatus,
+};
 use crate::pausables::{
     EngineAuthorizer, EnginePrecompilesPauser, PausedPrecompilesChecker, PrecompileFlags,
 };
 use crate::prelude::parameters::RefundCallArgs;
+use crate::prelude::precompiles::block_gas_used;
+pub use crate::prelude::precompiles::block_hash_history::compute_block_hash;
 use crate::prelude::precompiles::native::{exit_to_ethereum, exit_to_near};
+use crate::prelude::precompiles::withdrawal_limits;
 use crate::prelude::precompiles::xcc::cross_contract_call;
 use crate::prelude::precompiles::Precompiles;
 use crate::prelude::transactions::{EthTransactionKind, NormalizedEthTransaction};
 use crate::prelude::{
     address_to_key, bytes_to_key, format, sdk, storage_to_key, u256_to_arr, vec, AccountId,
-    Address, BTreeMap, BorshDeserialize, Cow, KeyPrefix, PromiseArgs, PromiseCreateArgs, String,
-    Vec, Wei, Yocto, ERC20_DIGITS_SELECTOR, ERC20_MINT_SELECTOR, ERC20_NAME_SELECTOR,
+    Address, BorshDeserialize, BorshSerialize, Cow, KeyPrefix, PromiseArgs, PromiseCreateArgs,
+    String, Vec, Wei, Yocto, ERC20_DIGITS_SELECTOR, ERC20_MINT_SELECTOR, ERC20_NAME_SELECTOR,
     ERC20_SET_METADATA_SELECTOR, ERC20_SYMBOL_SELECTOR, H160, H256, U256,
 };
 use crate::state::EngineState;
@@ -40,17 +45,24 @@ use aurora_engine_precompiles::PrecompileConstructorContext;
 use aurora_engine_types::parameters::connector::{
     Erc20Identifier, Erc20Metadata, MirrorErc20TokenArgs,
 };
-use aurora_engine_types::parameters::engine::FunctionCallArgsV2;
-use aurora_engine_types::types::EthGas;
+use aurora_engine_types::parameters::engine::{CoinbaseMode, FunctionCallArgsV2};
+use aurora_engine_types::parameters::roles::Role;
+use aurora_engine_types::parameters::withdrawal_limits::{QueuedWithdrawal, WithdrawalRecord};
+use aurora_engine_types::parameters::xcc::XccEvmCallbackArgs;
+use aurora_engine_types::types::{EthGas, RawH256, RawU256};
 use core::cell::RefCell;
 use core::iter::once;
 
-/// Used as the first byte in the concatenation of data used to compute the blockhash.
-/// Could be useful in the future as a version byte, or to distinguish different types of blocks.
-const BLOCK_HASH_PREFIX: u8 = 0;
-const BLOCK_HASH_PREFIX_SIZE: usize = 1;
-const BLOCK_HEIGHT_SIZE: usize = 8;
-const CHAIN_ID_SIZE: usize = 32;
+/// The EVM address of the Aurora account used as `COINBASE` in the `CoinbaseMode::Fixed` mode.
+const FIXED_COINBASE: H160 = H160([
+    0x44, 0x44, 0x58, 0x84, 0x43, 0xC3, 0xa9, 0x12, 0x88, 0xc5, 0x00, 0x24, 0x83, 0x44, 0x9A, 0xba,
+    0x10, 0x54, 0x19, 0x2b,
+]);
+const COINBASE_MODE_KEY: &[u8] = b"COINBASE_MODE";
+const BLOCK_GAS_LIMIT_KEY: &[u8] = b"BLOCK_GAS_LIMIT";
+/// Sub-keys of the shared code storage.
+const CODE_KEY: &[u8] = b"code";
+const CODE_REFS_KEY: &[u8] = b"refs";
 
 /// Block height where the bug fix for parsing transactions to the zero address
 /// is deployed. The current value is only approximate; will be updated once the
@@ -105,6 +117,7 @@ pub enum EngineErrorKind {
     NonExistedKey,
     Erc20FromNep141,
     RejectCallerWithCode,
+    BlockGasLimitExceeded,
 }
 
 impl EngineErrorKind {
@@ -145,6 +158,7 @@ impl EngineErrorKind {
             Self::NonExistedKey => errors::ERR_FUNCTION_CALL_KEY_NOT_FOUND,
             Self::Erc20FromNep141 => errors::ERR_GETTING_ERC20_FROM_NEP141,
             Self::RejectCallerWithCode => errors::ERR_REJECT_CALL_WITH_CODE,
+            Self::BlockGasLimitExceeded => errors::ERR_BLOCK_GAS_LIMIT_EXCEEDED,
             Self::EvmFatal(_) | Self::EvmError(_) => unreachable!(), // unused misc
         }
     }
@@ -417,8 +431,7 @@ pub struct Engine<'env, I: IO, E: Env, M = AuroraModExp> {
     current_account_id: AccountId,
     io: I,
     env: &'env E,
-    generation_cache: RefCell<BTreeMap<Address, u32>>,
-    account_info_cache: RefCell<FullCache<Address, Basic>>,
+    account_cache: RefCell<FullCache<Address, AccountRecord>>,
     contract_code_cache: RefCell<FullCache<Address, Vec<u8>>>,
     contract_storage_cache: RefCell<FullCache<(Address, H256), H256>>,
     modexp_algorithm: PhantomData<M>,
@@ -451,8 +464,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
             current_account_id,
             io,
             env,
-            generation_cache: RefCell::new(BTreeMap::new()),
-            account_info_cache: RefCell::new(FullCache::default()),
+            account_cache: RefCell::new(FullCache::default()),
             contract_code_cache: RefCell::new(FullCache::default()),
             contract_storage_cache: RefCell::new(FullCache::default()),
             modexp_algorithm: PhantomData,
@@ -522,6 +534,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         access_list: Vec<(H160, Vec<H256>)>, // See EIP-2930
         handler: &mut P,
     ) -> EngineResult<SubmitResult> {
+        let gas_limit = self.cap_gas_limit_by_block(gas_limit)?;
         let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
         let precompiles = self.create_precompiles(pause_flags, handler);
 
@@ -561,6 +574,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         let logs = filter_promises_from_logs(&self.io, handler, logs, &self.current_account_id);
 
         self.apply(values, Vec::<Log>::new(), true);
+        self.add_block_gas_used(used_gas);
 
         Ok(SubmitResult::new(status, used_gas, logs))
     }
@@ -615,6 +629,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         access_list: Vec<(H160, Vec<H256>)>, // See EIP-2930
         handler: &mut P,
     ) -> EngineResult<SubmitResult> {
+        let gas_limit = self.cap_gas_limit_by_block(gas_limit)?;
         let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
         let precompiles = self.create_precompiles(pause_flags, handler);
 
@@ -636,6 +651,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         let logs = filter_promises_from_logs(&self.io, handler, logs, &self.current_account_id);
         // The logs could be encoded as base64 or hex string.
         self.apply(values, Vec::<Log>::new(), true);
+        self.add_block_gas_used(used_gas);
 
         Ok(SubmitResult::new(status, used_gas, logs))
     }
@@ -894,6 +910,40 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         )
     }
 
+    /// Caps the gas limit of an EVM execution by the gas left in the current block if the block
+    /// gas limit is set. Fails if there is no gas left.
+    fn cap_gas_limit_by_block(&self, gas_limit: u64) -> Result<u64, EngineErrorKind> {
+        let Some(block_gas_limit) = get_block_gas_limit(&self.io) else {
+            return Ok(gas_limit);
+        };
+        let gas_used = block_gas_used::get(&self.io, self.env.block_height()).unwrap_or_default();
+        let gas_left = block_gas_limit.as_u64().saturating_sub(gas_used.as_u64());
+
+        if gas_left == 0 {
+            return Err(EngineErrorKind::BlockGasLimitExceeded);
+        }
+        Ok(gas_limit.min(gas_left))
+    }
+
+    /// Adds the gas used by an EVM execution to the gas used by the current block.
+    fn add_block_gas_used(&mut self, gas_used: u64) {
+        let block_height = self.env.block_height();
+        let block_gas_used = block_gas_used::get(&self.io, block_height).unwrap_or_default();
+        block_gas_used::record(
+            &mut self.io,
+            block_height,
+            EthGas::new(block_gas_used.as_u64().saturating_add(gas_used)),
+        );
+    }
+
+    /// Returns the account record of the address, which is read from the storage only once.
+    fn cached_account(&self, address: &Address) -> AccountRecord {
+        self.account_cache
+            .borrow_mut()
+            .get_or_insert_with(*address, || get_account(&self.io, address))
+            .clone()
+    }
+
     fn create_precompiles<P: PromiseHandler>(
         &self,
         pause_flags: PrecompileFlags,
@@ -907,6 +957,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
 
         let precompiles = Precompiles::new_london(PrecompileConstructorContext {
             current_account_id,
+            chain_id: self.state.chain_id,
             random_seed,
             io,
             env,
@@ -1069,6 +1120,17 @@ pub fn submit_with_alt_modexp<
         return Err(EngineErrorKind::MaxPriorityGasFeeTooLarge.into());
     }
 
+    // Check the transaction fits into the remaining gas of the block.
+    let gas_used_in_block = block_gas_used::get(&io, env.block_height()).unwrap_or_default();
+    if get_block_gas_limit(&io).is_some_and(|limit| {
+        gas_used_in_block
+            .as_u256()
+            .saturating_add(transaction.gas_limit)
+            > limit.as_u256()
+    }) {
+        return Err(EngineErrorKind::BlockGasLimitExceeded.into());
+    }
+
     let mut engine: Engine<_, _, M> =
         Engine::new_with_state(state, sender, current_account_id, io, env);
     // EIP-3607
@@ -1121,7 +1183,6 @@ pub fn submit_with_alt_modexp<
         Ok(submit_result) => submit_result.gas_used,
         Err(engine_err) => engine_err.gas_used,
     };
-
     refund_unused_gas(
         &mut io,
         &sender,
@@ -1199,38 +1260,15 @@ pub fn refund_on_error<I: IO + Copy, E: Env, P: PromiseHandler>(
     }
 }
 
-/// There is one Aurora block per NEAR block height (note: when heights in NEAR are skipped
-/// they are interpreted as empty blocks on Aurora). The blockhash is derived from the height
-/// according to
-/// ```text
-/// block_hash = sha256(concat(
-///     BLOCK_HASH_PREFIX,
-///     block_height as u64,
-///     chain_id,
-///     engine_account_id,
-/// ))
-/// ```
-#[must_use]
-pub fn compute_block_hash(chain_id: [u8; 32], block_height: u64, account_id: &[u8]) -> H256 {
-    debug_assert_eq!(BLOCK_HASH_PREFIX_SIZE, mem::size_of_val(&BLOCK_HASH_PREFIX));
-    debug_assert_eq!(BLOCK_HEIGHT_SIZE, mem::size_of_val(&block_height));
-    debug_assert_eq!(CHAIN_ID_SIZE, mem::size_of_val(&chain_id));
-    let mut data = Vec::with_capacity(
-        BLOCK_HASH_PREFIX_SIZE + BLOCK_HEIGHT_SIZE + CHAIN_ID_SIZE + account_id.len(),
-    );
-    data.push(BLOCK_HASH_PREFIX);
-    data.extend_from_slice(&chain_id);
-    data.extend_from_slice(account_id);
-    data.extend_from_slice(&block_height.to_be_bytes());
-
-    sdk::sha256(&data)
-}
-
 #[must_use]
 pub fn get_authorizer<I: IO + Copy>(io: &I) -> EngineAuthorizer {
-    // TODO: a temporary use the owner account only until the engine adapts std with near-plugins
+    // The owner and the accounts with the `Pauser` role are allowed to pause precompiles.
     state::get_state(io)
-        .map(|state| EngineAuthorizer::from_accounts(once(state.owner_id)))
+        .map(|state| {
+            EngineAuthorizer::from_accounts(
+                once(state.owner_id).chain(roles::get_members(io, Role::Pauser)),
+            )
+        })
         .unwrap_or_default()
 }
 
@@ -1351,6 +1389,37 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
     Ok(address)
 }
 
+/// Deploys an ERC-20 token for each of the NEP-141 tokens with the `deploy_erc20_token`
+/// semantics. A failed deployment doesn't prevent the deployment of the other tokens.
+pub fn deploy_erc20_tokens<I: IO + Copy, E: Env, P: PromiseHandler>(
+    nep141s: Vec<AccountId>,
+    io: I,
+    env: &E,
+    handler: &mut P,
+) -> Vec<DeployErc20TokenResult> {
+    nep141s
+        .into_iter()
+        .map(|nep141| {
+            // The token is checked before the deployment, so a registered token doesn't
+            // leave an orphaned ERC-20 contract behind.
+            let status = if get_erc20_from_nep141(&io, &nep141).is_ok() {
+                Erc20DeployStatus::Failed(
+                    String::from_utf8_lossy(errors::ERR_NEP141_TOKEN_ALREADY_REGISTERED).into(),
+                )
+            } else {
+                let args = DeployErc20TokenArgs {
+                    nep141: nep141.clone(),
+                };
+                match deploy_erc20_token(args, io, env, handler) {
+                    Ok(address) => Erc20DeployStatus::DeployedWithoutMetadata(address),
+                    Err(e) => Erc20DeployStatus::Failed(String::from_utf8_lossy(e.as_ref()).into()),
+                }
+            };
+            DeployErc20TokenResult { nep141, status }
+        })
+        .collect()
+}
+
 /// Used to mirror deployed ERC-20 contract on main contract to silo.
 pub fn mirror_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
     args: MirrorErc20TokenArgs,
@@ -1396,34 +1465,237 @@ pub fn mirror_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
     Ok(address)
 }
 
+/// Sets the code of the address. The code is stored once per code hash together with the
+/// number of the addresses using it, the account record only stores the hash.
 pub fn set_code<I: IO>(io: &mut I, address: &Address, code: &[u8]) {
-    io.write_storage(&address_to_key(KeyPrefix::Code, address), code);
+    let mut account = get_account(io, address);
+    if account.code_hash() == Some(sdk::keccak(code)) {
+        return;
+    }
+    replace_code(io, address, &mut account, Some(code));
+    set_account(io, address, &account);
 }
 
 pub fn remove_code<I: IO>(io: &mut I, address: &Address) {
-    io.remove_storage(&address_to_key(KeyPrefix::Code, address));
+    let mut account = get_account(io, address);
+    replace_code(io, address, &mut account, None);
+    set_account(io, address, &account);
 }
 
 pub fn get_code<I: IO>(io: &I, address: &Address) -> Vec<u8> {
-    io.read_storage(&address_to_key(KeyPrefix::Code, address))
+    read_code(io, address, get_account(io, address).code_hash())
+}
+
+pub fn get_code_size<I: IO>(io: &I, address: &Address) -> usize {
+    read_code_size(io, address, get_account(io, address).code_hash())
+}
+
+/// Moves the code of the address stored before the code deduplication to the shared code
+/// storage. Returns `false` if the address has no code in the old layout.
+pub fn migrate_code<I: IO>(io: &mut I, address: &Address) -> bool {
+    let Some(code) = io.read_storage(&address_to_key(KeyPrefix::Code, address)) else {
+        return false;
+    };
+    let mut account = get_account(io, address);
+    replace_code(io, address, &mut account, Some(&code.to_vec()));
+    set_account(io, address, &account);
+    true
+}
+
+/// Reads the code with the given hash, or the code stored under the address for accounts
+/// created before the code deduplication.
+fn read_code<I: IO>(io: &I, address: &Address, code_hash: Option<H256>) -> Vec<u8> {
+    code_hash
+        .map_or_else(
+            || io.read_storage(&address_to_key(KeyPrefix::Code, address)),
+            |code_hash| io.read_storage(&code_store_key(&code_hash, CODE_KEY)),
+        )
         .map(|s| s.to_vec())
         .unwrap_or_default()
 }
 
-pub fn get_code_size<I: IO>(io: &I, address: &Address) -> usize {
-    io.read_storage_len(&address_to_key(KeyPrefix::Code, address))
+fn read_code_size<I: IO>(io: &I, address: &Address, code_hash: Option<H256>) -> usize {
+    code_hash
+        .map_or_else(
+            || io.read_storage_len(&address_to_key(KeyPrefix::Code, address)),
+            |code_hash| io.read_storage_len(&code_store_key(&code_hash, CODE_KEY)),
+        )
         .unwrap_or(0)
 }
 
-pub fn set_nonce<I: IO>(io: &mut I, address: &Address, nonce: &U256) {
+/// Points the account to the new code and releases the code it used before. The caller is
+/// responsible for writing the account record.
+fn replace_code<I: IO>(
+    io: &mut I,
+    address: &Address,
+    account: &mut AccountRecord,
+    code: Option<&[u8]>,
+) {
+    // Accounts created before the code deduplication store the code under their address.
+    io.remove_storage(&address_to_key(KeyPrefix::Code, address));
+
+    if let Some(code_hash) = account.code_hash() {
+        let refs = get_code_refs(io, &code_hash).saturating_sub(1);
+        if refs == 0 {
+            io.remove_storage(&code_store_key(&code_hash, CODE_KEY));
+            io.remove_storage(&code_store_key(&code_hash, CODE_REFS_KEY));
+        } else {
+            set_code_refs(io, &code_hash, refs);
+        }
+    }
+
+    account.code_hash = code.map(|code| {
+        let code_hash = sdk::keccak(code);
+        let refs = get_code_refs(io, &code_hash);
+        if refs == 0 {
+            io.write_storage(&code_store_key(&code_hash, CODE_KEY), code);
+        }
+        set_code_refs(io, &code_hash, refs + 1);
+        code_hash.0
+    });
+}
+
+fn get_code_refs<I: IO>(io: &I, code_hash: &H256) -> u64 {
+    io.read_u64(&code_store_key(code_hash, CODE_REFS_KEY))
+        .unwrap_or(0)
+}
+
+fn set_code_refs<I: IO>(io: &mut I, code_hash: &H256, refs: u64) {
     io.write_storage(
-        &address_to_key(KeyPrefix::Nonce, address),
-        &u256_to_arr(nonce),
+        &code_store_key(code_hash, CODE_REFS_KEY),
+        &refs.to_le_bytes(),
     );
 }
 
+fn code_store_key(code_hash: &H256, key: &[u8]) -> Vec<u8> {
+    bytes_to_key(KeyPrefix::CodeStore, &[code_hash.as_bytes(), key].concat())
+}
+
+#[must_use]
+pub fn get_coinbase_mode<I: IO>(io: &I) -> CoinbaseMode {
+    io.read_storage(&bytes_to_key(KeyPrefix::Config, COINBASE_MODE_KEY))
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_default()
+}
+
+pub fn set_coinbase_mode<I: IO>(io: &mut I, mode: CoinbaseMode) {
+    let key = bytes_to_key(KeyPrefix::Config, COINBASE_MODE_KEY);
+
+    if mode == CoinbaseMode::Fixed {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &mode);
+    }
+}
+
+/// Returns the limit of the EVM gas used by the EVM executions of one NEAR block.
+#[must_use]
+pub fn get_block_gas_limit<I: IO>(io: &I) -> Option<EthGas> {
+    io.read_storage(&bytes_to_key(KeyPrefix::Config, BLOCK_GAS_LIMIT_KEY))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+pub fn set_block_gas_limit<I: IO>(io: &mut I, block_gas_limit: Option<EthGas>) {
+    let key = bytes_to_key(KeyPrefix::Config, BLOCK_GAS_LIMIT_KEY);
+
+    if let Some(block_gas_limit) = block_gas_limit {
+        io.write_borsh(&key, &block_gas_limit);
+    } else {
+        io.remove_storage(&key);
+    }
+}
+
+/// Nonce, balance, storage generation and code hash of an account packed into one storage
+/// value, so that a cold account access costs a single storage read.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone, PartialEq, Eq, Debug)]
+#[borsh(crate = "aurora_engine_types::borsh")]
+pub struct AccountRecord {
+    pub nonce: RawU256,
+    pub balance: RawU256,
+    pub generation: u32,
+    pub code_hash: Option<RawH256>,
+}
+
+impl AccountRecord {
+    #[must_use]
+    pub fn nonce(&self) -> U256 {
+        U256::from_big_endian(&self.nonce)
+    }
+
+    #[must_use]
+    pub fn balance(&self) -> Wei {
+        Wei::new(U256::from_big_endian(&self.balance))
+    }
+
+    #[must_use]
+    pub fn code_hash(&self) -> Option<H256> {
+        self.code_hash.map(H256)
+    }
+
+    #[must_use]
+    pub fn basic(&self) -> Basic {
+        Basic {
+            nonce: self.nonce(),
+            balance: self.balance().raw(),
+        }
+    }
+}
+
+/// Returns the account record of the address. Accounts which have not been written since the
+/// packed layout was introduced are read from the legacy keys holding one field each.
+pub fn get_account<I: IO>(io: &I, address: &Address) -> AccountRecord {
+    io.read_storage(&address_to_key(KeyPrefix::Account, address))
+        .and_then(|bytes| bytes.to_value().ok())
+        .unwrap_or_else(|| get_legacy_account(io, address))
+}
+
+/// Writes the account record of the address. The first write of an account migrates it to
+/// the packed layout by removing its legacy keys.
+pub fn set_account<I: IO>(io: &mut I, address: &Address, account: &AccountRecord) {
+    let key = address_to_key(KeyPrefix::Account, address);
+    let previous = if account == &AccountRecord::default() {
+        io.remove_storage(&key)
+    } else {
+        io.write_borsh(&key, account)
+    };
+
+    if previous.is_none() {
+        for prefix in [KeyPrefix::Nonce, KeyPrefix::Balance, KeyPrefix::Generation] {
+            io.remove_storage(&address_to_key(prefix, address));
+        }
+    }
+}
+
+fn get_legacy_account<I: IO>(io: &I, address: &Address) -> AccountRecord {
+    let read_u256 = |prefix| {
+        io.read_u256(&address_to_key(prefix, address))
+            .map(|value| u256_to_arr(&value))
+            .unwrap_or_default()
+    };
+
+    AccountRecord {
+        nonce: read_u256(KeyPrefix::Nonce),
+        balance: read_u256(KeyPrefix::Balance),
+        generation: io
+            .read_storage(&address_to_key(KeyPrefix::Generation, address))
+            .map_or(0, |value| {
+                let mut bytes = [0u8; 4];
+                value.copy_to_slice(&mut bytes);
+                u32::from_be_bytes(bytes)
+            }),
+        // The code of the legacy accounts is stored under the address.
+        code_hash: None,
+    }
+}
+
+pub fn set_nonce<I: IO>(io: &mut I, address: &Address, nonce: &U256) {
+    let mut account = get_account(io, address);
+    account.nonce = u256_to_arr(nonce);
+    set_account(io, address, &account);
+}
+
 pub fn remove_nonce<I: IO>(io: &mut I, address: &Address) {
-    io.remove_storage(&address_to_key(KeyPrefix::Nonce, address));
+    set_nonce(io, address, &U256::zero());
 }
 
 /// Checks the nonce to ensure that the address matches the transaction
@@ -1446,8 +1718,7 @@ pub fn check_nonce<I: IO>(
 }
 
 pub fn get_nonce<I: IO>(io: &I, address: &Address) -> U256 {
-    io.read_u256(&address_to_key(KeyPrefix::Nonce, address))
-        .unwrap_or_else(|_| U256::zero())
+    get_account(io, address).nonce()
 }
 
 #[cfg(test)]
@@ -1494,21 +1765,17 @@ pub fn add_balance<I: IO>(
 }
 
 pub fn set_balance<I: IO>(io: &mut I, address: &Address, balance: &Wei) {
-    io.write_storage(
-        &address_to_key(KeyPrefix::Balance, address),
-        &balance.to_bytes(),
-    );
+    let mut account = get_account(io, address);
+    account.balance = balance.to_bytes();
+    set_account(io, address, &account);
 }
 
 pub fn remove_balance<I: IO + Copy>(io: &mut I, address: &Address) {
-    io.remove_storage(&address_to_key(KeyPrefix::Balance, address));
+    set_balance(io, address, &Wei::zero());
 }
 
 pub fn get_balance<I: IO>(io: &I, address: &Address) -> Wei {
-    let raw = io
-        .read_u256(&address_to_key(KeyPrefix::Balance, address))
-        .unwrap_or_else(|_| U256::zero());
-    Wei::new(raw)
+    get_account(io, address).balance()
 }
 
 pub fn remove_storage<I: IO>(io: &mut I, address: &Address, key: &H256, generation: u32) {
@@ -1545,27 +1812,25 @@ pub fn storage_has_key<I: IO>(io: &I, address: &Address, key: &H256, generation:
 
 /// EIP-7610: balance, nonce, code, storage should be empty
 pub fn is_account_empty<I: IO>(io: &I, address: &Address) -> bool {
-    get_balance(io, address).is_zero()
-        && get_nonce(io, address).is_zero()
-        && get_code_size(io, address) == 0
-        && !storage_has_key(io, address, &H256::zero(), get_generation(io, address))
+    is_account_record_empty(io, address, &get_account(io, address))
+}
+
+fn is_account_record_empty<I: IO>(io: &I, address: &Address, account: &AccountRecord) -> bool {
+    account.balance().is_zero()
+        && account.nonce().is_zero()
+        && read_code_size(io, address, account.code_hash()) == 0
+        && !storage_has_key(io, address, &H256::zero(), account.generation)
 }
 
 /// Increments storage generation for a given address.
 pub fn set_generation<I: IO>(io: &mut I, address: &Address, generation: u32) {
-    io.write_storage(
-        &address_to_key(KeyPrefix::Generation, address),
-        &generation.to_be_bytes(),
-    );
+    let mut account = get_account(io, address);
+    account.generation = generation;
+    set_account(io, address, &account);
 }
 
 pub fn get_generation<I: IO>(io: &I, address: &Address) -> u32 {
-    io.read_storage(&address_to_key(KeyPrefix::Generation, address))
-        .map_or(0, |value| {
-            let mut bytes = [0u8; 4];
-            value.copy_to_slice(&mut bytes);
-            u32::from_be_bytes(bytes)
-        })
+    get_account(io, address).generation
 }
 
 /// Adds a public function call key for a relayer.
@@ -1583,8 +1848,8 @@ pub fn remove_function_call_key<I: IO>(io: &mut I, key: &PublicKey) -> Result<()
     Ok(())
 }
 
-/// Removes all storage for the given address.
-fn remove_all_storage<I: IO>(io: &mut I, address: &Address, generation: u32) {
+/// Removes all storage for the given account.
+fn remove_all_storage(account: &mut AccountRecord) {
     // FIXME: there is presently no way to prefix delete trie state.
     // NOTE: There is not going to be a method on runtime for this.
     //     You may need to store all keys in a list if you want to do this in a contract.
@@ -1593,15 +1858,15 @@ fn remove_all_storage<I: IO>(io: &mut I, address: &Address, generation: u32) {
     //     Either way you may have to store the nonce per storage address root. When the account
     //     has to be deleted the storage nonce needs to be increased, and the old nonce keys
     //     can be deleted over time. That's how TurboGeth does storage.
-    set_generation(io, address, generation + 1);
+    account.generation += 1;
 }
 
-/// Removes an account.
-fn remove_account<I: IO + Copy>(io: &mut I, address: &Address, generation: u32) {
-    remove_nonce(io, address);
-    remove_balance(io, address);
-    remove_code(io, address);
-    remove_all_storage(io, address, generation);
+/// Removes an account. The caller is responsible for writing the account record.
+fn remove_account<I: IO>(io: &mut I, address: &Address, account: &mut AccountRecord) {
+    replace_code(io, address, account, None);
+    account.nonce = RawU256::default();
+    account.balance = RawU256::default();
+    remove_all_storage(account);
 }
 
 fn filter_promises_from_logs<I, T, P>(
@@ -1616,6 +1881,7 @@ where
     I: IO + Copy,
 {
     let mut previous_promise: Option<PromiseId> = None;
+    let mut evm_callback: Option<XccEvmCallbackArgs> = None;
     logs.into_iter()
         .filter_map(|log| {
             if log.address == exit_to_near::ADDRESS.raw()
@@ -1663,6 +1929,18 @@ where
                     }
                     // do not pass on these "internal logs" to caller
                     None
+                } else if log.topics[0] == withdrawal_limits::WITHDRAWAL_TOPIC {
+                    if let Ok(record) = WithdrawalRecord::try_from_slice(&log.data) {
+                        crate::withdrawal_limits::record_withdrawal(*io, &record);
+                    }
+                    None
+                } else if log.topics[0] == withdrawal_limits::QUEUED_WITHDRAWAL_TOPIC {
+                    if let Ok(withdrawal) = QueuedWithdrawal::try_from_slice(&log.data) {
+                        let mut io = *io;
+                        let id = crate::withdrawal_limits::queue_withdrawal(&mut io, &withdrawal);
+                        sdk::log!("Withdrawal queued for approval with id {}", id);
+                    }
+                    None
                 } else {
                     // The exit precompiles do produce externally consumable logs in
                     // addition to the promises. The external logs have a non-empty
@@ -1683,10 +1961,14 @@ where
                             previous_promise,
                             &promise,
                             required_near,
+                            evm_callback.take().as_ref(),
                             current_account_id,
                         );
                         previous_promise = Some(id);
                     }
+                } else if log.topics[0] == cross_contract_call::EVM_CALLBACK_TOPIC {
+                    // The callback is attached to the promise from the next log.
+                    evm_callback = XccEvmCallbackArgs::try_from_slice(&log.data).ok();
                 }
                 // do not pass on these "internal logs" to caller
                 None
@@ -1806,15 +2088,19 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Backend for Engine<'env, I,
         U256::from(self.env.block_height())
     }
 
-    /// Returns a mocked coinbase which is the EVM address for the Aurora
-    /// account, being 0x4444588443C3a91288c5002483449Aba1054192b.
+    /// Returns the coinbase selected by the `CoinbaseMode`. By default, it is a mocked
+    /// coinbase which is the EVM address for the Aurora account, being
+    /// 0x4444588443C3a91288c5002483449Aba1054192b.
     ///
     /// See: `https://doc.aurora.dev/develop/compat/evm#coinbase`
     fn block_coinbase(&self) -> H160 {
-        H160([
-            0x44, 0x44, 0x58, 0x84, 0x43, 0xC3, 0xa9, 0x12, 0x88, 0xc5, 0x00, 0x24, 0x83, 0x44,
-            0x9A, 0xba, 0x10, 0x54, 0x19, 0x2b,
-        ])
+        match get_coinbase_mode(&self.io) {
+            CoinbaseMode::Fixed => FIXED_COINBASE,
+            CoinbaseMode::Relayer => self
+                .get_relayer(self.env.predecessor_account_id().as_bytes())
+                .map_or(FIXED_COINBASE, |address| address.raw()),
+            CoinbaseMode::Treasury(address) => address.raw(),
+        }
     }
 
     /// Returns the current block timestamp.
@@ -1836,13 +2122,12 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Backend for Engine<'env, I,
 
     /// Returns the current block gas limit.
     ///
-    /// Currently, this returns 0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
-    /// as there isn't a gas limit alternative right now but this may change in
-    /// the future.
+    /// Returns 0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
+    /// unless the limit has been set with `set_block_gas_limit`.
     ///
     /// See: `https://doc.aurora.dev/develop/compat/evm#gaslimit`
     fn block_gas_limit(&self) -> U256 {
-        U256::max_value()
+        get_block_gas_limit(&self.io).map_or_else(U256::max_value, EthGas::as_u256)
     }
 
     /// Returns the current base fee for the current block.
@@ -1863,31 +2148,20 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Backend for Engine<'env, I,
     /// Checks if an address exists.
     fn exists(&self, address: H160) -> bool {
         let address = Address::new(address);
-        let mut cache = self.account_info_cache.borrow_mut();
-        let basic_info = cache.get_or_insert_with(address, || Basic {
-            nonce: get_nonce(&self.io, &address),
-            balance: get_balance(&self.io, &address).raw(),
-        });
-        if !basic_info.balance.is_zero() || !basic_info.nonce.is_zero() {
+        let account = self.cached_account(&address);
+        if !account.balance().is_zero() || !account.nonce().is_zero() {
             return true;
         }
         let mut cache = self.contract_code_cache.borrow_mut();
-        let code = cache.get_or_insert_with(address, || get_code(&self.io, &address));
+        let code = cache.get_or_insert_with(address, || {
+            read_code(&self.io, &address, account.code_hash())
+        });
         !code.is_empty()
     }
 
     /// Returns basic account information.
     fn basic(&self, address: H160) -> Basic {
-        let address = Address::new(address);
-        let result = self
-            .account_info_cache
-            .borrow_mut()
-            .get_or_insert_with(address, || Basic {
-                nonce: get_nonce(&self.io, &address),
-                balance: get_balance(&self.io, &address).raw(),
-            })
-            .clone();
-        result
+        self.cached_account(&Address::new(address)).basic()
     }
 
     /// Returns the code of the contract from an address.
@@ -1895,18 +2169,20 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Backend for Engine<'env, I,
         let address = Address::new(address);
         self.contract_code_cache
             .borrow_mut()
-            .get_or_insert_with(address, || get_code(&self.io, &address))
+            .get_or_insert_with(address, || {
+                read_code(
+                    &self.io,
+                    &address,
+                    self.cached_account(&address).code_hash(),
+                )
+            })
             .clone()
     }
 
     /// Get storage value of address at index.
     fn storage(&self, address: H160, index: H256) -> H256 {
         let address = Address::new(address);
-        let generation = *self
-            .generation_cache
-            .borrow_mut()
-            .entry(address)
-            .or_insert_with(|| get_generation(&self.io, &address));
+        let generation = self.cached_account(&address).generation;
         let result = *self
             .contract_storage_cache
             .borrow_mut()
@@ -1931,11 +2207,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Backend for Engine<'env, I,
         {
             return false;
         }
-        let generation = *self
-            .generation_cache
-            .borrow_mut()
-            .entry(address)
-            .or_insert_with(|| get_generation(&self.io, &address));
+        let generation = self.cached_account(&address).generation;
         !storage_has_key(&self.io, &address, &index, generation)
     }
 
@@ -1983,29 +2255,32 @@ impl<'env, J: IO + Copy, E: Env, M: ModExpAlgorithm> ApplyBackend for Engine<'en
                     });
 
                     let address = Address::new(address);
-                    let generation = get_generation(&self.io, &address);
+                    // All the account fields are read and written at once.
+                    let mut account = get_account(&self.io, &address);
+                    let current_account = account.clone();
+                    let generation = account.generation;
 
-                    if current_basic.nonce != basic.nonce {
-                        set_nonce(&mut self.io, &address, &basic.nonce);
+                    if account.nonce() != basic.nonce {
+                        account.nonce = u256_to_arr(&basic.nonce);
                         writes_counter += 1;
                     }
-                    if current_basic.balance != basic.balance {
-                        set_balance(&mut self.io, &address, &Wei::new(basic.balance));
+                    if account.balance().raw() != basic.balance {
+                        account.balance = u256_to_arr(&basic.balance);
                         writes_counter += 1;
                     }
 
                     if let Some(code) = code {
-                        set_code(&mut self.io, &address, &code);
+                        if account.code_hash() != Some(sdk::keccak(&code)) {
+                            replace_code(&mut self.io, &address, &mut account, Some(&code));
+                        }
                         code_bytes_written = code.len();
                         sdk::log!("code_write_at_address {:?} {}", address, code_bytes_written);
                     }
 
-                    let next_generation = if reset_storage {
-                        remove_all_storage(&mut self.io, &address, generation);
-                        generation + 1
-                    } else {
-                        generation
-                    };
+                    if reset_storage {
+                        remove_all_storage(&mut account);
+                    }
+                    let next_generation = account.generation;
 
                     for (index, value) in storage {
                         if value == H256::default() {
@@ -2022,20 +2297,25 @@ impl<'env, J: IO + Copy, E: Env, M: ModExpAlgorithm> ApplyBackend for Engine<'en
                     // 3. we didn't already clear out the storage (because if we did then there is
                     //    nothing to do)
                     if delete_empty
-                        && is_account_empty(&self.io, &address)
+                        && is_account_record_empty(&self.io, &address, &account)
                         && generation == next_generation
                     {
-                        remove_account(&mut self.io, &address, generation);
+                        remove_account(&mut self.io, &address, &mut account);
                         writes_counter += 1;
                     }
+
+                    if account != current_account {
+                        set_account(&mut self.io, &address, &account);
+                    }
                 }
                 Apply::Delete { address } => {
                     let current_basic = self.basic(address);
                     accounting.remove(current_basic.balance);
 
                     let address = Address::new(address);
-                    let generation = get_generation(&self.io, &address);
-                    remove_account(&mut self.io, &address, generation);
+                    let mut account = get_account(&self.io, &address);
+                    remove_account(&mut self.io, &address, &mut account);
+                    set_account(&mut self.io, &address, &account);
                     writes_counter += 1;
                 }
             }
@@ -2083,7 +2363,9 @@ impl<'env, J: IO + Copy, E: Env, M: ModExpAlgorithm> ApplyBackend for Engine<'en
     }
 }
 
-fn submit_result_or_err(submit_result: SubmitResult) -> Result<SubmitResult, EngineError> {
+pub(crate) fn submit_result_or_err(
+    submit_result: SubmitResult,
+) -> Result<SubmitResult, EngineError> {
     match submit_result.status {
         TransactionStatus::Succeed(_) => Ok(submit_result),
         TransactionStatus::Revert(bytes) => {
@@ -2178,7 +2460,7 @@ mod tests {
     use aurora_engine_test_doubles::io::{Storage, StoragePointer};
     use aurora_engine_test_doubles::promise::PromiseTracker;
     use aurora_engine_types::parameters::engine::RelayerKeyArgs;
-    use aurora_engine_types::types::{make_address, Balance, NearGas, RawU256};
+    use aurora_engine_types::types::{make_address, Balance, NearGas};
     use std::cell::RefCell;
 
     #[test]
@@ -2260,6 +2542,167 @@ mod tests {
         assert_eq!(expected_result, actual_result);
     }
 
+    #[test]
+    fn test_identical_code_is_stored_once() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        let code_hash = sdk::keccak(&code);
+        let first = Address::from_array([1; 20]);
+        let second = Address::from_array([2; 20]);
+
+        set_code(&mut io, &first, &code);
+        set_code(&mut io, &second, &code);
+        set_code(&mut io, &second, &code);
+        assert_eq!(get_code_refs(&io, &code_hash), 2);
+        assert_eq!(get_code(&io, &second), code);
+        assert_eq!(get_code_size(&io, &second), code.len());
+        assert_eq!(get_account(&io, &second).code_hash(), Some(code_hash));
+
+        remove_code(&mut io, &first);
+        assert!(get_code(&io, &first).is_empty());
+        assert_eq!(get_code(&io, &second), code);
+
+        remove_code(&mut io, &second);
+        assert_eq!(get_code_refs(&io, &code_hash), 0);
+        assert!(io
+            .read_storage(&code_store_key(&code_hash, CODE_KEY))
+            .is_none());
+    }
+
+    #[test]
+    fn test_migrating_code_from_address_layout() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        let address = Address::from_array([1; 20]);
+        let legacy_key = address_to_key(KeyPrefix::Code, &address);
+        io.write_storage(&legacy_key, &code);
+
+        // The code stored under the address is still served.
+        assert_eq!(get_code(&io, &address), code);
+        assert_eq!(get_account(&io, &address).code_hash(), None);
+
+        assert!(migrate_code(&mut io, &address));
+        assert!(io.read_storage(&legacy_key).is_none());
+        assert_eq!(
+            get_account(&io, &address).code_hash(),
+            Some(sdk::keccak(&code))
+        );
+        assert_eq!(get_code(&io, &address), code);
+        assert_eq!(get_code_size(&io, &address), code.len());
+        assert!(!migrate_code(&mut io, &address));
+    }
+
+    #[test]
+    fn test_account_record_migrates_legacy_keys() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let address = Address::from_array([1; 20]);
+        let nonce_key = address_to_key(KeyPrefix::Nonce, &address);
+        let balance_key = address_to_key(KeyPrefix::Balance, &address);
+        let generation_key = address_to_key(KeyPrefix::Generation, &address);
+        io.write_storage(&nonce_key, &u256_to_arr(&U256::from(3)));
+        io.write_storage(&balance_key, &Wei::new_u64(100).to_bytes());
+        io.write_storage(&generation_key, &2u32.to_be_bytes());
+
+        // Accounts in the legacy layout are still served.
+        assert_eq!(get_nonce(&io, &address), U256::from(3));
+        assert_eq!(get_balance(&io, &address), Wei::new_u64(100));
+        assert_eq!(get_generation(&io, &address), 2);
+
+        // The first write moves all the fields to the packed record.
+        set_balance(&mut io, &address, &Wei::new_u64(50));
+        for key in [&nonce_key, &balance_key, &generation_key] {
+            assert!(io.read_storage(key).is_none());
+        }
+        assert_eq!(
+            get_account(&io, &address),
+            AccountRecord {
+                nonce: u256_to_arr(&U256::from(3)),
+                balance: Wei::new_u64(50).to_bytes(),
+                generation: 2,
+                code_hash: None,
+            }
+        );
+
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        set_code(&mut io, &address, &code);
+        assert_eq!(
+            get_account(&io, &address).code_hash(),
+            Some(sdk::keccak(&code))
+        );
+        assert_eq!(get_nonce(&io, &address), U256::from(3));
+    }
+
+    #[test]
+    fn test_coinbase_follows_coinbase_mode() {
+        let origin = Address::zero();
+        let current_account_id = AccountId::default();
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(EngineState::default(), origin, current_account_id, io, &env);
+        let relayer_address = make_address(1, 1);
+        let treasury_address = make_address(2, 2);
+
+        assert_eq!(engine.block_coinbase(), FIXED_COINBASE);
+
+        set_coinbase_mode(&mut io, CoinbaseMode::Relayer);
+        assert_eq!(engine.block_coinbase(), FIXED_COINBASE);
+        engine.register_relayer(env.predecessor_account_id.as_bytes(), relayer_address);
+        assert_eq!(engine.block_coinbase(), relayer_address.raw());
+
+        set_coinbase_mode(&mut io, CoinbaseMode::Treasury(treasury_address));
+        assert_eq!(engine.block_coinbase(), treasury_address.raw());
+
+        set_coinbase_mode(&mut io, CoinbaseMode::Fixed);
+        assert_eq!(get_coinbase_mode(&io), CoinbaseMode::Fixed);
+        assert_eq!(engine.block_coinbase(), FIXED_COINBASE);
+    }
+
+    #[test]
+    fn test_block_gas_limit() {
+        let origin = Address::zero();
+        let current_account_id = AccountId::default();
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let engine: Engine<_, _> =
+            Engine::new_with_state(EngineState::default(), origin, current_account_id, io, &env);
+
+        assert_eq!(engine.block_gas_limit(), U256::max_value());
+        set_block_gas_limit(&mut io, Some(EthGas::new(15_000_000)));
+        assert_eq!(engine.block_gas_limit(), U256::from(15_000_000));
+        set_block_gas_limit(&mut io, None);
+        assert_eq!(engine.block_gas_limit(), U256::max_value());
+    }
+
+    #[test]
+    fn test_evm_executions_are_accounted_in_block_gas_used() {
+        let origin = Address::zero();
+        let current_account_id = AccountId::default();
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(EngineState::default(), origin, current_account_id, io, &env);
+
+        let result = engine
+            .deploy_code_with_input(vec![], None, &mut Noop)
+            .unwrap();
+        let gas_used = block_gas_used::get(&io, env.block_height).unwrap();
+        assert_eq!(gas_used.as_u64(), result.gas_used);
+
+        // Executions fail once the block has no gas left.
+        set_block_gas_limit(&mut io, Some(gas_used));
+        let error = engine
+            .deploy_code_with_input(vec![], None, &mut Noop)
+            .unwrap_err();
+        assert_eq!(error.kind, EngineErrorKind::BlockGasLimitExceeded);
+    }
+
     #[test]
     fn test_call_to_empty_contract_returns_empty_data() {
         let origin = Address::zero();
@@ -2455,6 +2898,43 @@ mod tests {
         assert_eq!(expected_address, actual_address);
     }
 
+    #[test]
+    fn test_deploying_tokens_reports_failures_per_token() {
+        let env = Fixed::default();
+        let origin = aurora_engine_sdk::types::near_account_to_evm_address(
+            env.predecessor_account_id().as_bytes(),
+        );
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        add_balance(&mut io, &origin, Wei::new_u64(22000)).unwrap();
+        state::set_state(&mut io, &EngineState::default()).unwrap();
+
+        let first = AccountId::new("first").unwrap();
+        let second = AccountId::new("second").unwrap();
+        let mut handler = Noop;
+        let results =
+            deploy_erc20_tokens(vec![first.clone(), first, second], io, &env, &mut handler);
+
+        assert_eq!(
+            results[0].status,
+            Erc20DeployStatus::DeployedWithoutMetadata(create_legacy_address(
+                &origin,
+                &U256::zero()
+            ))
+        );
+        assert_eq!(
+            results[1].status,
+            Erc20DeployStatus::Failed("ERR_NEP141_TOKEN_ALREADY_REGISTERED".into())
+        );
+        assert_eq!(
+            results[2].status,
+            Erc20DeployStatus::DeployedWithoutMetadata(create_legacy_address(
+                &origin,
+                &U256::one()
+            ))
+        );
+    }
+
     #[test]
     fn test_get_erc20_metadata() {
         let env = Fixed::default();
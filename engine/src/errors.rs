@@ -21,6 +21,8 @@ pub const ERR_PROMISE_FAILED: &[u8; 18] = b"ERR_PROMISE_FAILED";
 pub const ERR_VERIFY_PROOF: &[u8; 16] = b"ERR_VERIFY_PROOF";
 pub const ERR_INVALID_UPGRADE: &[u8; 19] = b"ERR_INVALID_UPGRADE";
 pub const ERR_NO_UPGRADE: &[u8; 14] = b"ERR_NO_UPGRADE";
+pub const ERR_UPGRADE_ALREADY_STAGED: &[u8; 26] = b"ERR_UPGRADE_ALREADY_STAGED";
+pub const ERR_UPGRADE_CODE_HASH_MISMATCH: &[u8; 30] = b"ERR_UPGRADE_CODE_HASH_MISMATCH";
 pub const ERR_NOT_ALLOWED: &[u8; 15] = b"ERR_NOT_ALLOWED";
 pub const ERR_NOT_OWNER: &[u8; 13] = b"ERR_NOT_OWNER";
 pub const ERR_PAUSED: &[u8; 10] = b"ERR_PAUSED";
@@ -96,6 +98,18 @@ pub const ERR_FAILED_UNREGISTER_ACCOUNT_POSITIVE_BALANCE: &[u8; 46] =
     b"ERR_FAILED_UNREGISTER_ACCOUNT_POSITIVE_BALANCE";
 pub const ERR_SAME_OWNER: &[u8; 14] = b"ERR_SAME_OWNER";
 pub const ERR_SAME_KEY_MANAGER: &[u8] = b"ERR_SAME_KEY_MANAGER";
+pub const ERR_ROLE_ALREADY_GRANTED: &[u8] = b"ERR_ROLE_ALREADY_GRANTED";
+pub const ERR_ROLE_NOT_GRANTED: &[u8] = b"ERR_ROLE_NOT_GRANTED";
+pub const ERR_GOVERNANCE_NOT_CONFIGURED: &[u8] = b"ERR_GOVERNANCE_NOT_CONFIGURED";
+pub const ERR_GOVERNANCE_ALREADY_CONFIGURED: &[u8] = b"ERR_GOVERNANCE_ALREADY_CONFIGURED";
+pub const ERR_INVALID_GOVERNANCE_CONFIG: &[u8] = b"ERR_INVALID_GOVERNANCE_CONFIG";
+pub const ERR_GOVERNANCE_PROPOSAL_REQUIRED: &[u8] = b"ERR_GOVERNANCE_PROPOSAL_REQUIRED";
+pub const ERR_UNSUPPORTED_PROPOSAL_ACTION: &[u8] = b"ERR_UNSUPPORTED_PROPOSAL_ACTION";
+pub const ERR_PROPOSAL_NOT_FOUND: &[u8] = b"ERR_PROPOSAL_NOT_FOUND";
+pub const ERR_PROPOSAL_NOT_PENDING: &[u8] = b"ERR_PROPOSAL_NOT_PENDING";
+pub const ERR_PROPOSAL_ALREADY_APPROVED: &[u8] = b"ERR_PROPOSAL_ALREADY_APPROVED";
+pub const ERR_PROPOSAL_NOT_APPROVED: &[u8] = b"ERR_PROPOSAL_NOT_APPROVED";
+pub const ERR_MIGRATION_VERSION_GAP: &[u8] = b"ERR_MIGRATION_VERSION_GAP";
 pub const ERR_FUNCTION_CALL_KEY_NOT_FOUND: &[u8] = b"ERR_FUNCTION_CALL_KEY_NOT_FOUND";
 pub const ERR_KEY_MANAGER_IS_NOT_SET: &[u8] = b"ERR_KEY_MANAGER_IS_NOT_SET";
 pub const ERR_ACCOUNTS_COUNTER_OVERFLOW: &str = "ERR_ACCOUNTS_COUNTER_OVERFLOW";
@@ -109,4 +123,19 @@ pub const ERR_ALLOWED_IN_SILO_MODE_ONLY: &[u8] = b"ERR_ALLOWED_IN_SILO_MODE_ONLY
 pub const ERR_INVALID_NEP141_ACCOUNT_ID: &[u8] = b"ERR_INVALID_NEP141_ACCOUNT_ID";
 pub const ERR_NEP141_NOT_FOUND: &[u8] = b"ERR_NEP141_NOT_FOUND";
 pub const ERR_NEP141_TOKEN_ALREADY_REGISTERED: &[u8] = b"ERR_NEP141_TOKEN_ALREADY_REGISTERED";
+pub const ERR_NEP171_NOT_FOUND: &[u8] = b"ERR_NEP171_NOT_FOUND";
+pub const ERR_ERC721_NOT_FOUND: &[u8] = b"ERR_ERC721_NOT_FOUND";
+pub const ERR_INVALID_NFT_MESSAGE: &[u8] = b"ERR_INVALID_NFT_MESSAGE";
+pub const ERR_ERC721_DEPLOY_FAILED: &[u8] = b"ERR_ERC721_DEPLOY_FAILED";
+pub const ERR_NEP245_NOT_FOUND: &[u8] = b"ERR_NEP245_NOT_FOUND";
+pub const ERR_ERC1155_NOT_FOUND: &[u8] = b"ERR_ERC1155_NOT_FOUND";
+pub const ERR_INVALID_MT_MESSAGE: &[u8] = b"ERR_INVALID_MT_MESSAGE";
+pub const ERR_INVALID_MT_TRANSFER: &[u8] = b"ERR_INVALID_MT_TRANSFER";
+pub const ERR_ERC1155_DEPLOY_FAILED: &[u8] = b"ERR_ERC1155_DEPLOY_FAILED";
 pub const ERR_REJECT_CALL_WITH_CODE: &[u8] = b"ERR_REJECT_CALL_WITH_CODE";
+pub const ERR_BLOCK_GAS_LIMIT_EXCEEDED: &[u8] = b"ERR_BLOCK_GAS_LIMIT_EXCEEDED";
+pub const ERR_INVALID_WITHDRAWAL_LIMIT: &[u8] = b"ERR_INVALID_WITHDRAWAL_LIMIT";
+pub const ERR_QUEUED_WITHDRAWAL_NOT_FOUND: &[u8] = b"ERR_QUEUED_WITHDRAWAL_NOT_FOUND";
+pub const ERR_INVALID_QUEUED_WITHDRAWAL: &[u8] = b"ERR_INVALID_QUEUED_WITHDRAWAL";
+pub const ERR_EMPTY_TOKENS_LIST: &[u8] = b"ERR_EMPTY_TOKENS_LIST";
+pub const ERR_TOO_MANY_TOKENS: &[u8] = b"ERR_TOO_MANY_TOKENS";
@@ -1,4 +1,5 @@
 use crate::contract_methods::ContractError;
+use crate::prelude::precompiles::hashchain_history;
 use aurora_engine_hashchain::{
     bloom::{self, Bloom},
     error::BlockchainHashchainError,
@@ -12,11 +13,23 @@ use aurora_engine_sdk::{
 use aurora_engine_types::{
     parameters::engine::SubmitResult,
     storage::{self, KeyPrefix},
+    types::RawH256,
 };
 use core::cell::RefCell;
 
 pub const HASHCHAIN_STATE: &[u8] = b"HC_STATE";
 
+/// Methods executing EVM transactions with `with_logs_hashchain`, so the logs of their
+/// transactions are accrued in the logs bloom of the block. The other methods add an empty bloom.
+pub const LOGS_HASHCHAIN_METHODS: &[&str] = &[
+    "deploy_code",
+    "call",
+    "submit",
+    "submit_with_args",
+    "withdraw_wnear_to_router",
+    "xcc_evm_callback",
+];
+
 pub fn with_hashchain<I, E, T, F>(
     mut io: I,
     env: &E,
@@ -29,7 +42,7 @@ where
     F: for<'a> FnOnce(CachedIO<'a, I>) -> Result<T, ContractError>,
 {
     let block_height = env.block_height();
-    let maybe_hashchain = load_hashchain(&io, block_height)?;
+    let maybe_hashchain = load_hashchain(&mut io, block_height)?;
 
     let cache = RefCell::new(IOCache::default());
     let hashchain_io = CachedIO::new(io, &cache);
@@ -61,8 +74,9 @@ where
     E: Env,
     F: for<'a> FnOnce(CachedIO<'a, I>) -> Result<SubmitResult, ContractError>,
 {
+    debug_assert!(LOGS_HASHCHAIN_METHODS.contains(&function_name));
     let block_height = env.block_height();
-    let maybe_hashchain = load_hashchain(&io, block_height)?;
+    let maybe_hashchain = load_hashchain(&mut io, block_height)?;
 
     let cache = RefCell::new(IOCache::default());
     let hashchain_io = CachedIO::new(io, &cache);
@@ -84,16 +98,51 @@ where
     Ok(result)
 }
 
-fn load_hashchain<I: IO>(io: &I, block_height: u64) -> Result<Option<Hashchain>, ContractError> {
+fn load_hashchain<I: IO>(
+    io: &mut I,
+    block_height: u64,
+) -> Result<Option<Hashchain>, ContractError> {
     let mut maybe_hashchain = read_current_hashchain(io)?;
     if let Some(hashchain) = maybe_hashchain.as_mut() {
-        if block_height > hashchain.get_current_block_height() {
-            hashchain.move_to_block(block_height)?;
-        }
+        move_to_block(io, hashchain, block_height)?;
     }
     Ok(maybe_hashchain)
 }
 
+/// Moves the hashchain to the given block height if it is bigger than the current one.
+/// Only the hashchain of the block left behind is recorded in the history, the values of the
+/// skipped empty blocks are computed from it when they are read.
+pub fn move_to_block<I: IO>(
+    io: &mut I,
+    hashchain: &mut Hashchain,
+    block_height: u64,
+) -> Result<(), ContractError> {
+    if let Some((left_height, left_hashchain)) = advance_to_block(hashchain, block_height)? {
+        hashchain_history::record(io, left_height, left_hashchain, block_height);
+    }
+    Ok(())
+}
+
+/// Moves the hashchain to the given block height if it is bigger than the current one and
+/// returns the height and the hashchain of the block left behind. The blocks skipped after it
+/// have no transactions, so their values follow from the returned one.
+pub fn advance_to_block(
+    hashchain: &mut Hashchain,
+    block_height: u64,
+) -> Result<Option<(u64, RawH256)>, BlockchainHashchainError> {
+    let current_block_height = hashchain.get_current_block_height();
+    if current_block_height >= block_height {
+        return Ok(None);
+    }
+
+    hashchain.move_to_block(current_block_height + 1)?;
+    let left_hashchain = hashchain.get_previous_block_hashchain();
+    if current_block_height + 1 < block_height {
+        hashchain.move_to_block(block_height)?;
+    }
+    Ok(Some((current_block_height, left_hashchain)))
+}
+
 pub fn read_current_hashchain<I: IO>(io: &I) -> Result<Option<Hashchain>, ContractError> {
     let key = storage::bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
     let maybe_hashchain = io.read_storage(&key).map_or(Ok(None), |value| {
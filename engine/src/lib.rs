@@ -31,9 +31,12 @@ pub mod contract_methods;
 pub mod engine;
 pub mod errors;
 pub mod hashchain;
+pub mod mt;
+pub mod nft;
 pub mod pausables;
 mod prelude;
 pub mod state;
+pub mod withdrawal_limits;
 pub mod xcc;
 
 #[cfg(target_arch = "wasm32")]
@@ -69,13 +72,14 @@ mod contract {
     use crate::prelude::storage::{bytes_to_key, KeyPrefix};
     use crate::prelude::{sdk, u256_to_arr, Address, ToString, Vec, H256};
     use crate::{
-        contract_methods::{self, silo, ContractError},
+        contract_methods::{self, emit_event, silo, ContractError},
         errors, state,
     };
     use aurora_engine_sdk::env::Env;
     use aurora_engine_sdk::io::{StorageIntermediate, IO};
     use aurora_engine_sdk::near_runtime::{Runtime, ViewEnv};
     use aurora_engine_types::borsh;
+    use aurora_engine_types::events::AdminEvent;
     use aurora_engine_types::parameters::silo::{
         FixedGasArgs, SiloParamsArgs, WhitelistArgs, WhitelistKindArgs, WhitelistStatusArgs,
     };
@@ -159,6 +163,56 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Return the borsh-encoded `CoinbaseMode` which selects the address returned by `COINBASE`.
+    #[no_mangle]
+    pub extern "C" fn get_coinbase_mode() {
+        let io = Runtime;
+        contract_methods::admin::get_coinbase_mode(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Select the address returned by `COINBASE`: the fixed address, the EVM address registered
+    /// by the relayer or a treasury address.
+    #[no_mangle]
+    pub extern "C" fn set_coinbase_mode() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::set_coinbase_mode(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the borsh-encoded limit of the EVM gas used by the EVM executions of one NEAR
+    /// block, `None` if there is no limit.
+    #[no_mangle]
+    pub extern "C" fn get_block_gas_limit() {
+        let io = Runtime;
+        contract_methods::admin::get_block_gas_limit(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Set or remove the limit of the EVM gas used by the EVM executions of one NEAR block.
+    #[no_mangle]
+    pub extern "C" fn set_block_gas_limit() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::set_block_gas_limit(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the borsh-encoded EVM gas used by the EVM executions at the provided block height,
+    /// `None` if nothing has been executed at the height or it isn't in the recent history.
+    #[no_mangle]
+    pub extern "C" fn get_block_gas_used() {
+        let io = Runtime;
+        contract_methods::admin::get_block_gas_used(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     #[no_mangle]
     pub extern "C" fn get_upgrade_index() {
         let io = Runtime;
@@ -201,17 +255,72 @@ mod contract {
         if io.block_height() <= index {
             sdk::panic_utf8(errors::ERR_NOT_ALLOWED_TOO_EARLY);
         }
-        Runtime::self_deploy(&bytes_to_key(KeyPrefix::Config, CODE_KEY));
+        contract_methods::admin::consume_code_hash_commitment(&mut io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+        let code_key = bytes_to_key(KeyPrefix::Config, CODE_KEY);
+        let code_hash = io
+            .read_storage(&code_key)
+            .map(|code| sdk::sha256(&code.to_vec()))
+            .sdk_expect(errors::ERR_NO_UPGRADE);
+        Runtime::self_deploy(&code_key);
         io.remove_storage(&bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY));
+        emit_event(&AdminEvent::UpgradeDeployed { code_hash });
+    }
+
+    /// Returns the hash, size and unlock height of the staged upgrade.
+    #[no_mangle]
+    pub extern "C" fn get_staged_upgrade() {
+        let io = Runtime;
+        contract_methods::admin::get_staged_upgrade(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes the staged upgrade together with the code hash commitment.
+    #[no_mangle]
+    pub extern "C" fn cancel_staged_upgrade() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::cancel_staged_upgrade(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Commits to the hash of the code which is going to be staged. Once committed, only the code
+    /// with the same hash can be staged and deployed.
+    #[no_mangle]
+    pub extern "C" fn commit_upgrade_code_hash() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::commit_upgrade_code_hash(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
     }
 
     /// Called as part of the upgrade process (see `engine-sdk::self_deploy`). This function is meant
     /// to make any necessary changes to the state such that it aligns with the newly deployed
     /// code.
+    /// Migrations which don't fit into the attached gas are suspended and continued by the
+    /// subsequent calls of the function. Returns the progress of the migration.
+    /// Optionally takes borsh-encoded `StateMigrationArgs` with the addresses whose code should
+    /// be moved to the deduplicated code storage.
     #[no_mangle]
-    #[allow(clippy::missing_const_for_fn)]
     pub extern "C" fn state_migration() {
-        // TODO: currently we don't have migrations
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::migration::state_migration(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the progress of the state migration.
+    #[no_mangle]
+    pub extern "C" fn get_migration_progress() {
+        let io = Runtime;
+        contract_methods::migration::get_migration_progress(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
     }
 
     /// Resumes previously [`paused`] precompiles.
@@ -266,6 +375,112 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Grant a role to an account. Only the owner can grant roles.
+    #[no_mangle]
+    pub extern "C" fn grant_role() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::roles::grant_role(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Revoke a role from an account. Only the owner can revoke roles.
+    #[no_mangle]
+    pub extern "C" fn revoke_role() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::roles::revoke_role(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the list of accounts which have the provided role.
+    #[no_mangle]
+    pub extern "C" fn get_role_members() {
+        let io = Runtime;
+        contract_methods::roles::get_role_members(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the list of roles granted to the provided account.
+    #[no_mangle]
+    pub extern "C" fn get_account_roles() {
+        let io = Runtime;
+        contract_methods::roles::get_account_roles(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Set the initial governance config. Could be called only once by the owner.
+    #[no_mangle]
+    pub extern "C" fn set_governance_config() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::governance::set_governance_config(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the governance config.
+    #[no_mangle]
+    pub extern "C" fn get_governance_config() {
+        let io = Runtime;
+        contract_methods::governance::get_governance_config(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Create a proposal of the configuration change. Returns the id of the proposal.
+    #[no_mangle]
+    pub extern "C" fn create_proposal() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::governance::create_proposal(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Approve the proposal by one of its approvers.
+    #[no_mangle]
+    pub extern "C" fn approve_proposal() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::governance::approve_proposal(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Execute the approved proposal once its timelock has passed.
+    #[no_mangle]
+    pub extern "C" fn execute_proposal() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::governance::execute_proposal(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Cancel the pending proposal.
+    #[no_mangle]
+    pub extern "C" fn cancel_proposal() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::governance::cancel_proposal(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Return the proposal with the provided id.
+    #[no_mangle]
+    pub extern "C" fn get_proposal() {
+        let io = Runtime;
+        contract_methods::governance::get_proposal(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     // TODO: rust-2023-08-24  #[allow(clippy::empty_line_after_doc_comments)]
     /// MUTATIVE METHODS
     /// Deploy code into the EVM.
@@ -347,6 +562,18 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Removes the bytecode version of the given address. This is only called as a callback
+    /// when the router account of the address is closed.
+    #[no_mangle]
+    pub extern "C" fn factory_remove_address_version() {
+        let io = Runtime;
+        let env = Runtime;
+        let handler = Runtime;
+        contract_methods::xcc::factory_remove_address_version(io, &env, &handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Sets the address for the `wNEAR` ERC-20 contract. This contract will be used by the
     /// cross-contract calls feature to have users pay for their NEAR transactions.
     #[no_mangle]
@@ -393,6 +620,19 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// A private function (only callable by the contract itself) used as part of the XCC flow.
+    /// This function calls the EVM contract requested in the XCC precompile input with the
+    /// results of the cross contract call.
+    #[no_mangle]
+    pub extern "C" fn xcc_evm_callback() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::xcc::xcc_evm_callback(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Mirror existing ERC-20 token on the main Aurora contract.
     /// Notice: It works if the SILO mode is on.
     #[no_mangle]
@@ -548,6 +788,16 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Return the hashchain of the block with the given height if it is still in the history.
+    #[no_mangle]
+    pub extern "C" fn get_hashchain_at() {
+        let mut io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::get_hashchain_at(&mut io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Return metadata of the ERC-20 contract.
     #[no_mangle]
     pub extern "C" fn get_erc20_metadata() {
@@ -723,6 +973,28 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Deploy ERC20 tokens mapped to many NEP141 tokens
+    #[no_mangle]
+    pub extern "C" fn deploy_erc20_tokens() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::connector::deploy_erc20_tokens(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Callback used by the `deploy_erc20_tokens` function to set the metadata of the tokens.
+    #[no_mangle]
+    pub extern "C" fn deploy_erc20_tokens_callback() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::connector::deploy_erc20_tokens_callback(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Set metadata of ERC-20 contract.
     #[no_mangle]
     pub extern "C" fn set_erc20_metadata() {
@@ -850,6 +1122,171 @@ mod contract {
         );
     }
 
+    /// Updates the bytecode of the ERC-721 contract deployed for every bridged
+    /// NEP-171 contract.
+    #[no_mangle]
+    pub extern "C" fn set_erc721_template() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::nft::set_erc721_template(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// NEP-171 `nft_transfer_call` receiver. The transferred token is minted on the ERC-721
+    /// contract mirroring the NEP-171 contract, which is deployed on the first transfer.
+    /// Returns `true` if the token should be returned to the previous owner.
+    #[no_mangle]
+    pub extern "C" fn nft_on_transfer() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::nft::nft_on_transfer(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Synchronizes the metadata of the ERC-721 contract with `nft_metadata`
+    /// of the corresponding NEP-171 contract.
+    #[no_mangle]
+    pub extern "C" fn sync_erc721_metadata() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::nft::sync_erc721_metadata(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// A private function (only callable by the contract itself) updating the metadata of
+    /// the ERC-721 contract with the result of `nft_metadata`.
+    #[no_mangle]
+    pub extern "C" fn erc721_metadata_callback() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::nft::erc721_metadata_callback(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_erc721_from_nep171() {
+        let io = Runtime;
+        contract_methods::nft::get_erc721_from_nep171(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_nep171_from_erc721() {
+        let io = Runtime;
+        contract_methods::nft::get_nep171_from_erc721(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Updates the bytecode of the ERC-1155 contract deployed for every bridged
+    /// NEP-245 contract.
+    #[no_mangle]
+    pub extern "C" fn set_erc1155_template() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::mt::set_erc1155_template(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// NEP-245 `mt_transfer_call` / `mt_batch_transfer_call` receiver. The transferred tokens
+    /// are minted on the ERC-1155 contract mirroring the NEP-245 contract, which is deployed
+    /// on the first transfer. Returns the amounts which should be returned to the previous owners.
+    #[no_mangle]
+    pub extern "C" fn mt_on_transfer() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::mt::mt_on_transfer(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Callback invoked by exit to NEAR precompile to mint back the multi-tokens
+    /// if the transfer on NEAR failed.
+    #[no_mangle]
+    pub extern "C" fn mt_exit_callback() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::mt::mt_exit_callback(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_erc1155_from_nep245() {
+        let io = Runtime;
+        contract_methods::mt::get_erc1155_from_nep245(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_nep245_from_erc1155() {
+        let io = Runtime;
+        contract_methods::mt::get_nep245_from_erc1155(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Sets or removes the withdrawal limit of ETH, an ERC-20 token, an ERC-1155 contract
+    /// or all of them.
+    #[no_mangle]
+    pub extern "C" fn set_withdrawal_limit() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::withdrawal_limits::set_withdrawal_limit(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_withdrawal_limit() {
+        let io = Runtime;
+        contract_methods::withdrawal_limits::get_withdrawal_limit(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn get_queued_withdrawal() {
+        let io = Runtime;
+        contract_methods::withdrawal_limits::get_queued_withdrawal(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Creates the transfer of a withdrawal queued for exceeding a limit.
+    #[no_mangle]
+    pub extern "C" fn approve_queued_withdrawal() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::withdrawal_limits::approve_queued_withdrawal(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Drops a queued withdrawal and refunds the withdrawn tokens.
+    #[no_mangle]
+    pub extern "C" fn reject_queued_withdrawal() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::withdrawal_limits::reject_queued_withdrawal(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     #[no_mangle]
     pub extern "C" fn ft_metadata() {
         let io = Runtime;
@@ -965,6 +1402,9 @@ mod contract {
         args.fixed_gas.sdk_expect("FIXED_GAS_IS_NONE"); // Use `set_silo_params` to disable the silo mode.
         silo::get_silo_params(&io).sdk_expect("SILO_MODE_IS_OFF"); // Use `set_silo_params` to enable the silo mode.
         silo::set_fixed_gas(&mut io, args.fixed_gas);
+        emit_event(&AdminEvent::FixedGasChanged {
+            fixed_gas: args.fixed_gas,
+        });
     }
 
     #[no_mangle]
@@ -984,8 +1424,10 @@ mod contract {
         let mut io = Runtime;
         require_running(&state::get_state(&io).sdk_unwrap());
         silo::assert_admin(&io).sdk_unwrap();
+        contract_methods::governance::require_no_governance(&io).sdk_unwrap();
 
         let args: Option<SiloParamsArgs> = io.read_input_borsh().sdk_unwrap();
+        emit_event(&AdminEvent::silo_params_changed(args.as_ref()));
         silo::set_silo_params(&mut io, args);
     }
 
@@ -997,6 +1439,10 @@ mod contract {
 
         let args: WhitelistStatusArgs = io.read_input_borsh().sdk_unwrap();
         silo::set_whitelist_status(&io, &args);
+        emit_event(&AdminEvent::WhitelistStatusChanged {
+            kind: args.kind,
+            active: args.active,
+        });
     }
 
     #[no_mangle]
@@ -1018,6 +1464,7 @@ mod contract {
 
         let args: WhitelistArgs = io.read_input_borsh().sdk_unwrap();
         silo::add_entry_to_whitelist(&io, &args);
+        emit_event(&AdminEvent::whitelist_entry_added(&args));
     }
 
     #[no_mangle]
@@ -1027,6 +1474,11 @@ mod contract {
         silo::assert_admin(&io).sdk_unwrap();
 
         let args: Vec<WhitelistArgs> = io.read_input_borsh().sdk_unwrap();
+
+        for entry in &args {
+            emit_event(&AdminEvent::whitelist_entry_added(entry));
+        }
+
         silo::add_entry_to_whitelist_batch(&io, args);
     }
 
@@ -1038,6 +1490,7 @@ mod contract {
 
         let args: WhitelistArgs = io.read_input_borsh().sdk_unwrap();
         silo::remove_entry_from_whitelist(&io, &args);
+        emit_event(&AdminEvent::whitelist_entry_removed(&args));
     }
 
     // TODO: rust-2023-08-24#[allow(clippy::empty_line_after_doc_comments)]
@@ -0,0 +1,343 @@
+//! Bridge of NEP-245 multi-tokens from NEAR to Aurora. On Aurora the NEP-245 contract
+//! becomes an ERC-1155 contract deployed from the template stored in the engine.
+//! The way back goes through the `exitToNear` precompile (see `withdrawToNear` in
+//! `etc/eth-contracts/contracts/EvmErc1155.sol`).
+
+use crate::engine::{self, AddressParseError, Engine, EngineError};
+use crate::errors;
+use crate::map::BijectionMap;
+use crate::parameters::{SubmitResult, TransactionStatus};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_precompiles::native::mt as mt_state;
+use aurora_engine_sdk::env::Env;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_sdk::promise::PromiseHandler;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::parameters::mt::{MtExitCallbackArgs, NEP245MtOnTransferArgs};
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::{Address, Wei, ERC1155_MINT_BATCH_SELECTOR, ZERO_BALANCE};
+use aurora_engine_types::{String, ToString, Vec, U256};
+
+pub const ERR_NO_TEMPLATE: &str = "ERR_MISSING_ERC1155_TEMPLATE";
+
+pub struct ERC1155Address(pub Address);
+
+impl AsRef<[u8]> for ERC1155Address {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl TryFrom<Vec<u8>> for ERC1155Address {
+    type Error = AddressParseError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() == 20 {
+            Ok(Self(
+                Address::try_from_slice(&bytes).map_err(|_| AddressParseError)?,
+            ))
+        } else {
+            Err(AddressParseError)
+        }
+    }
+}
+
+pub struct NEP245Account(pub AccountId);
+
+impl AsRef<[u8]> for NEP245Account {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl TryFrom<Vec<u8>> for NEP245Account {
+    type Error = aurora_engine_types::account_id::ParseAccountError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        AccountId::try_from(bytes).map(Self)
+    }
+}
+
+#[must_use]
+pub const fn nep245_erc1155_map<I: IO>(io: I) -> BijectionMap<NEP245Account, ERC1155Address, I> {
+    BijectionMap::new(KeyPrefix::Nep245Erc1155Map, KeyPrefix::Erc1155Nep245Map, io)
+}
+
+pub fn get_erc1155_from_nep245<I: IO + Copy>(io: I, nep245: &AccountId) -> Option<Address> {
+    nep245_erc1155_map(io)
+        .lookup_left(&NEP245Account(nep245.clone()))
+        .map(|address| address.0)
+}
+
+pub fn get_nep245_from_erc1155<I: IO + Copy>(io: I, erc1155: &Address) -> Option<AccountId> {
+    nep245_erc1155_map(io)
+        .lookup_right(&ERC1155Address(*erc1155))
+        .map(|account| account.0)
+}
+
+/// Returns the bytecode of the ERC-1155 contract deployed for new NEP-245 contracts.
+pub fn get_template<I: IO>(io: &I) -> Option<Vec<u8>> {
+    io.read_storage(&template_key()).map(|v| v.to_vec())
+}
+
+pub fn set_template<I: IO>(io: &mut I, code: &[u8]) {
+    io.write_storage(&template_key(), code);
+}
+
+/// The id of the ERC-1155 token mirroring the NEP-245 token with the given id.
+/// NEAR token ids are arbitrary strings, so the ERC-1155 id is derived from their hash
+/// and the NEAR id is kept in the engine storage for the way back.
+#[must_use]
+pub fn erc1155_token_id(near_token_id: &str) -> [u8; 32] {
+    aurora_engine_sdk::keccak(near_token_id.as_bytes()).0
+}
+
+/// Mints the received NEP-245 tokens on the ERC-1155 contract mirroring the NEP-245 contract.
+/// The ERC-1155 contract is deployed from the template on the first transfer of a token of
+/// the NEP-245 contract.
+///
+/// The output is serialized as a JSON array with the amount of every token which must be
+/// returned to the previous owner (NEP-245): all of them on error, none otherwise.
+///
+/// IMPORTANT: This function should not panic, otherwise it won't
+/// be possible to return the tokens to the previous owner.
+pub fn receive_mt<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    mut io: I,
+    token: &AccountId,
+    args: &NEP245MtOnTransferArgs,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveMtError> {
+    let result = receive_mt_inner(engine, io, token, args, current_account_id, handler);
+    let unused_amounts = match &result {
+        Ok(_) => args.amounts.iter().map(|_| ZERO_BALANCE).collect(),
+        Err(_e) => {
+            aurora_engine_sdk::log!("receive_mt failed: {:?}", _e);
+            args.amounts.clone()
+        }
+    };
+    // Serialization of a vector of balances can't fail.
+    io.return_output(&serde_json::to_vec(&unused_amounts).unwrap_or_default());
+    result
+}
+
+fn receive_mt_inner<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    io: I,
+    token: &AccountId,
+    args: &NEP245MtOnTransferArgs,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveMtError> {
+    // Message format:
+    //      Recipient of the tokens - 40 characters (Address in hex)
+    let recipient = {
+        let message = args.msg.as_bytes();
+        if message.len() < 40 {
+            return Err(ReceiveMtError::InvalidMessage);
+        }
+        let mut address_bytes = [0; 20];
+        hex::decode_to_slice(&message[..40], &mut address_bytes)
+            .map_err(|_| ReceiveMtError::InvalidMessage)?;
+        Address::from_array(address_bytes)
+    };
+    if args.token_ids.is_empty() || args.token_ids.len() != args.amounts.len() {
+        return Err(ReceiveMtError::InvalidTransfer);
+    }
+
+    let erc1155 = match get_erc1155_from_nep245(io, token) {
+        Some(address) => address,
+        None => deploy_erc1155(engine, io, token, current_account_id, handler)?,
+    };
+
+    let token_ids = args
+        .token_ids
+        .iter()
+        .map(|id| erc1155_token_id(id.as_str()))
+        .collect::<Vec<_>>();
+    let amounts = args
+        .amounts
+        .iter()
+        .map(|amount| U256::from(amount.as_u128()))
+        .collect::<Vec<_>>();
+    mint_batch(
+        engine,
+        io,
+        erc1155,
+        recipient,
+        &token_ids,
+        &amounts,
+        &args.token_ids,
+        current_account_id,
+        handler,
+    )
+}
+
+/// Mints back the tokens of a failed withdrawal to NEAR (see `mt_exit_callback`).
+pub fn refund<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    io: I,
+    args: &MtExitCallbackArgs,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveMtError> {
+    let near_token_ids = args
+        .token_ids
+        .iter()
+        .map(|id| {
+            mt_state::get_near_token_id(&io, &args.erc1155, id)
+                .ok_or(ReceiveMtError::InvalidTransfer)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let amounts = args
+        .amounts
+        .iter()
+        .map(|amount| U256::from_big_endian(amount.as_slice()))
+        .collect::<Vec<_>>();
+    mint_batch(
+        engine,
+        io,
+        args.erc1155,
+        args.refund_address,
+        &args.token_ids,
+        &amounts,
+        &near_token_ids,
+        current_account_id,
+        handler,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn mint_batch<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    mut io: I,
+    erc1155: Address,
+    recipient: Address,
+    token_ids: &[[u8; 32]],
+    amounts: &[U256],
+    near_token_ids: &[String],
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveMtError> {
+    let input = [
+        ERC1155_MINT_BATCH_SELECTOR,
+        &ethabi::encode(&[
+            ethabi::Token::Address(recipient.raw()),
+            ethabi::Token::Array(
+                token_ids
+                    .iter()
+                    .map(|id| ethabi::Token::Uint(U256::from_big_endian(id)))
+                    .collect(),
+            ),
+            ethabi::Token::Array(amounts.iter().copied().map(ethabi::Token::Uint).collect()),
+            ethabi::Token::Array(
+                near_token_ids
+                    .iter()
+                    .cloned()
+                    .map(ethabi::Token::String)
+                    .collect(),
+            ),
+        ]),
+    ]
+    .concat();
+    let admin = engine::current_address(current_account_id);
+    let result = engine
+        .call(
+            &admin,
+            &erc1155,
+            Wei::zero(),
+            input,
+            u64::MAX,
+            Vec::new(),
+            handler,
+        )
+        .and_then(engine::submit_result_or_err)
+        .map_err(ReceiveMtError::Engine)?;
+    for (token_id, near_token_id) in token_ids.iter().zip(near_token_ids) {
+        mt_state::set_near_token_id(&mut io, &erc1155, token_id, near_token_id);
+    }
+
+    Ok(result)
+}
+
+fn deploy_erc1155<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    io: I,
+    nep245: &AccountId,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<Address, ReceiveMtError> {
+    let template = get_template(&io).ok_or(ReceiveMtError::MissingTemplate)?;
+    let deploy_args = ethabi::encode(&[
+        ethabi::Token::String(nep245.to_string()),
+        ethabi::Token::Address(engine::current_address(current_account_id).raw()),
+    ]);
+    let input = [template.as_slice(), deploy_args.as_slice()].concat();
+
+    let address = match engine.deploy_code_with_input(input, None, handler) {
+        Ok(result) => match result.status {
+            TransactionStatus::Succeed(ret) => Address::try_from_slice(&ret)
+                .map_err(|_| ReceiveMtError::DeployFailed(TransactionStatus::Succeed(ret)))?,
+            other => return Err(ReceiveMtError::DeployFailed(other)),
+        },
+        Err(e) => return Err(ReceiveMtError::Engine(e)),
+    };
+
+    aurora_engine_sdk::log!("Deployed ERC-1155 in Aurora at: {:#?}", address);
+    nep245_erc1155_map(io).insert(&NEP245Account(nep245.clone()), &ERC1155Address(address));
+
+    Ok(address)
+}
+
+fn template_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::MultiToken, mt_state::TEMPLATE_KEY)
+}
+
+#[derive(Debug)]
+pub enum ReceiveMtError {
+    InvalidMessage,
+    InvalidTransfer,
+    MissingTemplate,
+    DeployFailed(TransactionStatus),
+    Engine(EngineError),
+}
+
+impl AsRef<[u8]> for ReceiveMtError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::InvalidMessage => errors::ERR_INVALID_MT_MESSAGE,
+            Self::InvalidTransfer => errors::ERR_INVALID_MT_TRANSFER,
+            Self::MissingTemplate => ERR_NO_TEMPLATE.as_bytes(),
+            Self::DeployFailed(_) => errors::ERR_ERC1155_DEPLOY_FAILED,
+            Self::Engine(e) => e.kind.as_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use aurora_engine_types::types::Balance;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_nep245_erc1155_map() {
+        let storage = RefCell::new(Storage::default());
+        let io = StoragePointer(&storage);
+        let nep245: AccountId = "mt.near".parse().unwrap();
+        let erc1155 = Address::from_array([1; 20]);
+
+        assert_eq!(get_erc1155_from_nep245(io, &nep245), None);
+        nep245_erc1155_map(io).insert(&NEP245Account(nep245.clone()), &ERC1155Address(erc1155));
+        assert_eq!(get_erc1155_from_nep245(io, &nep245), Some(erc1155));
+        assert_eq!(get_nep245_from_erc1155(io, &erc1155), Some(nep245));
+    }
+
+    #[test]
+    fn test_unused_amounts_output() {
+        let amounts = vec![ZERO_BALANCE, Balance::new(5)];
+        assert_eq!(serde_json::to_vec(&amounts).unwrap(), br#"["0","5"]"#);
+    }
+}
@@ -0,0 +1,329 @@
+//! Bridge of NEP-171 non-fungible tokens from NEAR to Aurora. On Aurora the NEP-171 contract
+//! becomes an ERC-721 contract deployed from the template stored in the engine.
+//! The way back goes through the `exitToNear` precompile (see `withdrawToNear` in
+//! `etc/eth-contracts/contracts/EvmErc721.sol`).
+
+use crate::engine::{self, AddressParseError, Engine, EngineError, EngineResult};
+use crate::errors;
+use crate::map::BijectionMap;
+use crate::parameters::{CallArgs, FunctionCallArgsV2, SubmitResult, TransactionStatus};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_precompiles::native::nft as nft_state;
+use aurora_engine_sdk::env::Env;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_sdk::promise::PromiseHandler;
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::borsh;
+use aurora_engine_types::parameters::nft::{
+    Erc721Metadata, NEP171NftOnTransferArgs, SyncErc721MetadataArgs,
+};
+use aurora_engine_types::parameters::{PromiseCreateArgs, PromiseWithCallbackArgs};
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::{
+    Address, NearGas, Wei, ERC721_MINT_SELECTOR, ERC721_SET_METADATA_SELECTOR, ZERO_YOCTO,
+};
+use aurora_engine_types::{Vec, U256};
+
+pub const ERR_NO_TEMPLATE: &str = "ERR_MISSING_ERC721_TEMPLATE";
+/// Gas costs estimated from simulation tests.
+pub const METADATA_GAS: NearGas = NearGas::new(5_000_000_000_000);
+pub const METADATA_CALLBACK_GAS: NearGas = NearGas::new(10_000_000_000_000);
+
+/// Output of `nft_on_transfer`: `true` means that the token must be returned to the
+/// previous owner.
+const KEEP_TOKEN: &[u8] = b"false";
+const RETURN_TOKEN: &[u8] = b"true";
+
+pub struct ERC721Address(pub Address);
+
+impl AsRef<[u8]> for ERC721Address {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl TryFrom<Vec<u8>> for ERC721Address {
+    type Error = AddressParseError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() == 20 {
+            Ok(Self(
+                Address::try_from_slice(&bytes).map_err(|_| AddressParseError)?,
+            ))
+        } else {
+            Err(AddressParseError)
+        }
+    }
+}
+
+pub struct NEP171Account(pub AccountId);
+
+impl AsRef<[u8]> for NEP171Account {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl TryFrom<Vec<u8>> for NEP171Account {
+    type Error = aurora_engine_types::account_id::ParseAccountError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        AccountId::try_from(bytes).map(Self)
+    }
+}
+
+#[must_use]
+pub const fn nep171_erc721_map<I: IO>(io: I) -> BijectionMap<NEP171Account, ERC721Address, I> {
+    BijectionMap::new(KeyPrefix::Nep171Erc721Map, KeyPrefix::Erc721Nep171Map, io)
+}
+
+pub fn get_erc721_from_nep171<I: IO + Copy>(io: I, nep171: &AccountId) -> Option<Address> {
+    nep171_erc721_map(io)
+        .lookup_left(&NEP171Account(nep171.clone()))
+        .map(|address| address.0)
+}
+
+pub fn get_nep171_from_erc721<I: IO + Copy>(io: I, erc721: &Address) -> Option<AccountId> {
+    nep171_erc721_map(io)
+        .lookup_right(&ERC721Address(*erc721))
+        .map(|account| account.0)
+}
+
+/// Returns the bytecode of the ERC-721 contract deployed for new NEP-171 contracts.
+pub fn get_template<I: IO>(io: &I) -> Option<Vec<u8>> {
+    io.read_storage(&template_key()).map(|v| v.to_vec())
+}
+
+pub fn set_template<I: IO>(io: &mut I, code: &[u8]) {
+    io.write_storage(&template_key(), code);
+}
+
+/// The id of the ERC-721 token mirroring the NEP-171 token with the given id.
+/// NEAR token ids are arbitrary strings, so the ERC-721 id is derived from their hash
+/// and the NEAR id is kept in the engine storage for the way back.
+#[must_use]
+pub fn erc721_token_id(near_token_id: &str) -> [u8; 32] {
+    aurora_engine_sdk::keccak(near_token_id.as_bytes()).0
+}
+
+/// Mints the received NEP-171 token on the ERC-721 contract mirroring the NEP-171 contract.
+/// The ERC-721 contract is deployed from the template on the first transfer of a token of
+/// the NEP-171 contract, in this case the promise synchronizing the metadata is created.
+///
+/// The output is serialized as a JSON boolean, `true` if the token must be returned
+/// to the previous owner (NEP-171).
+///
+/// IMPORTANT: This function should not panic, otherwise it won't
+/// be possible to return the token to the previous owner.
+pub fn receive_nft<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    mut io: I,
+    token: &AccountId,
+    args: &NEP171NftOnTransferArgs,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveNftError> {
+    let result = receive_nft_inner(engine, io, token, args, current_account_id, handler);
+    match &result {
+        Ok(_) => io.return_output(KEEP_TOKEN),
+        Err(_e) => {
+            aurora_engine_sdk::log!("receive_nft failed: {:?}", _e);
+            io.return_output(RETURN_TOKEN);
+        }
+    }
+    result
+}
+
+fn receive_nft_inner<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    mut io: I,
+    token: &AccountId,
+    args: &NEP171NftOnTransferArgs,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<SubmitResult, ReceiveNftError> {
+    // Message format:
+    //      Recipient of the token - 40 characters (Address in hex)
+    let recipient = {
+        let message = args.msg.as_bytes();
+        if message.len() < 40 {
+            return Err(ReceiveNftError::InvalidMessage);
+        }
+        let mut address_bytes = [0; 20];
+        hex::decode_to_slice(&message[..40], &mut address_bytes)
+            .map_err(|_| ReceiveNftError::InvalidMessage)?;
+        Address::from_array(address_bytes)
+    };
+
+    let erc721 = match get_erc721_from_nep171(io, token) {
+        Some(address) => address,
+        None => {
+            let address = deploy_erc721(engine, io, token, current_account_id, handler)?;
+            let promise = metadata_promise(token, current_account_id);
+            // Safety: the promise is created from the engine itself and only calls
+            // the trusted engine callback.
+            let _ = unsafe { handler.promise_create_with_callback(&promise) };
+            address
+        }
+    };
+
+    let token_id = erc721_token_id(&args.token_id);
+    let input = [
+        ERC721_MINT_SELECTOR,
+        &ethabi::encode(&[
+            ethabi::Token::Address(recipient.raw()),
+            ethabi::Token::Uint(U256::from_big_endian(&token_id)),
+            ethabi::Token::String(args.token_id.clone()),
+        ]),
+    ]
+    .concat();
+    let admin = engine::current_address(current_account_id);
+    let result = engine
+        .call(
+            &admin,
+            &erc721,
+            Wei::zero(),
+            input,
+            u64::MAX,
+            Vec::new(),
+            handler,
+        )
+        .and_then(engine::submit_result_or_err)
+        .map_err(ReceiveNftError::Engine)?;
+    nft_state::set_near_token_id(&mut io, &erc721, &token_id, &args.token_id);
+
+    Ok(result)
+}
+
+fn deploy_erc721<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    io: I,
+    nep171: &AccountId,
+    current_account_id: &AccountId,
+    handler: &mut P,
+) -> Result<Address, ReceiveNftError> {
+    let template = get_template(&io).ok_or(ReceiveNftError::MissingTemplate)?;
+    let metadata = Erc721Metadata::from_account_id(nep171);
+    let deploy_args = ethabi::encode(&[
+        ethabi::Token::String(metadata.name),
+        ethabi::Token::String(metadata.symbol),
+        ethabi::Token::Address(engine::current_address(current_account_id).raw()),
+    ]);
+    let input = [template.as_slice(), deploy_args.as_slice()].concat();
+
+    let address = match engine.deploy_code_with_input(input, None, handler) {
+        Ok(result) => match result.status {
+            TransactionStatus::Succeed(ret) => Address::try_from_slice(&ret)
+                .map_err(|_| ReceiveNftError::DeployFailed(TransactionStatus::Succeed(ret)))?,
+            other => return Err(ReceiveNftError::DeployFailed(other)),
+        },
+        Err(e) => return Err(ReceiveNftError::Engine(e)),
+    };
+
+    aurora_engine_sdk::log!("Deployed ERC-721 in Aurora at: {:#?}", address);
+    nep171_erc721_map(io).insert(&NEP171Account(nep171.clone()), &ERC721Address(address));
+
+    Ok(address)
+}
+
+/// Promise reading the metadata of the NEP-171 contract with a callback updating
+/// the metadata of the corresponding ERC-721 contract.
+pub fn metadata_promise(
+    nep171: &AccountId,
+    current_account_id: &AccountId,
+) -> PromiseWithCallbackArgs {
+    let callback_args = SyncErc721MetadataArgs {
+        nep171: nep171.clone(),
+    };
+    PromiseWithCallbackArgs {
+        base: PromiseCreateArgs {
+            target_account_id: nep171.clone(),
+            method: "nft_metadata".into(),
+            args: b"{}".to_vec(),
+            attached_balance: ZERO_YOCTO,
+            attached_gas: METADATA_GAS,
+        },
+        callback: PromiseCreateArgs {
+            target_account_id: current_account_id.clone(),
+            method: "erc721_metadata_callback".into(),
+            args: borsh::to_vec(&callback_args).unwrap(),
+            attached_balance: ZERO_YOCTO,
+            attached_gas: METADATA_CALLBACK_GAS,
+        },
+    }
+}
+
+/// Sets the metadata of the ERC-721 contract. The engine must be created with the engine
+/// address as the origin because the function is restricted to the admin of the contract.
+pub fn set_metadata<I: IO + Copy, E: Env, M: ModExpAlgorithm, P: PromiseHandler>(
+    engine: &mut Engine<I, E, M>,
+    erc721: Address,
+    metadata: Erc721Metadata,
+    handler: &mut P,
+) -> EngineResult<SubmitResult> {
+    let args = ethabi::encode(&[
+        ethabi::Token::String(metadata.name),
+        ethabi::Token::String(metadata.symbol),
+        ethabi::Token::String(metadata.base_uri.unwrap_or_default()),
+    ]);
+    let input = [ERC721_SET_METADATA_SELECTOR, &args].concat();
+
+    engine.call_with_args(
+        CallArgs::V2(FunctionCallArgsV2 {
+            contract: erc721,
+            value: [0; 32],
+            input,
+        }),
+        handler,
+    )
+}
+
+fn template_key() -> Vec<u8> {
+    bytes_to_key(KeyPrefix::Nft, nft_state::TEMPLATE_KEY)
+}
+
+#[derive(Debug)]
+pub enum ReceiveNftError {
+    InvalidMessage,
+    MissingTemplate,
+    DeployFailed(TransactionStatus),
+    Engine(EngineError),
+}
+
+impl AsRef<[u8]> for ReceiveNftError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::InvalidMessage => errors::ERR_INVALID_NFT_MESSAGE,
+            Self::MissingTemplate => ERR_NO_TEMPLATE.as_bytes(),
+            Self::DeployFailed(_) => errors::ERR_ERC721_DEPLOY_FAILED,
+            Self::Engine(e) => e.kind.as_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_nep171_erc721_map() {
+        let storage = RefCell::new(Storage::default());
+        let io = StoragePointer(&storage);
+        let nep171: AccountId = "nft.near".parse().unwrap();
+        let erc721 = Address::from_array([1; 20]);
+
+        assert_eq!(get_erc721_from_nep171(io, &nep171), None);
+        nep171_erc721_map(io).insert(&NEP171Account(nep171.clone()), &ERC721Address(erc721));
+        assert_eq!(get_erc721_from_nep171(io, &nep171), Some(erc721));
+        assert_eq!(get_nep171_from_erc721(io, &erc721), Some(nep171));
+    }
+
+    #[test]
+    fn test_erc721_token_id() {
+        let id = erc721_token_id("1");
+        assert_eq!(id, aurora_engine_sdk::keccak(b"1").0);
+        assert_ne!(id, erc721_token_id("2"));
+    }
+}
@@ -35,17 +35,13 @@ impl EngineState {
         borsh::to_vec(&borshable).map_err(|_| EngineStateError::SerializationFailed)
     }
 
-    /// Deserialization with lazy state migration.
-    pub fn try_from_slice<I: IO + Copy>(bytes: &[u8], io: &I) -> Result<Self, EngineStateError> {
+    /// Deserialization of the current and the legacy layouts. The state stored in a legacy
+    /// layout is rewritten in the current one by the `EngineState` migration step.
+    pub fn try_from_slice(bytes: &[u8]) -> Result<Self, EngineStateError> {
         let Ok(borshable) = BorshableEngineState::try_from_slice(bytes) else {
             let legacy = BorshableEngineStateV1::try_from_slice(bytes)
                 .map_err(|_| EngineStateError::DeserializationFailed)?;
-            let result = legacy.into();
-
-            let mut io = *io;
-            set_state(&mut io, &result)?;
-
-            return Ok(result);
+            return Ok(legacy.into());
         };
         Ok(borshable.into())
     }
@@ -208,7 +204,7 @@ pub fn get_state<I: IO + Copy>(io: &I) -> Result<EngineState, EngineStateError>
     io.read_storage(&bytes_to_key(KeyPrefix::Config, STATE_KEY))
         .map_or_else(
             || Err(EngineStateError::NotFound),
-            |bytes| EngineState::try_from_slice(&bytes.to_vec(), io),
+            |bytes| EngineState::try_from_slice(&bytes.to_vec()),
         )
 }
 
@@ -0,0 +1,158 @@
+//! Engine side of the withdrawal limits: the usage is updated from the withdrawal records
+//! logged by the exit precompiles of successful transactions, and the withdrawals exceeding
+//! a limit with the `Queue` action wait here for the approval of an admin.
+
+use crate::pausables::{
+    EnginePrecompilesPauser, PausedPrecompilesChecker, PausedPrecompilesManager, PrecompileFlags,
+};
+use crate::prelude::Vec;
+use aurora_engine_precompiles::withdrawal_limits::{get_limit, get_usage, set_usage};
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::events::AdminEvent;
+use aurora_engine_types::parameters::withdrawal_limits::{
+    QueuedWithdrawal, WithdrawalLimitTarget, WithdrawalRecord,
+};
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+
+const QUEUE_KEY: &[u8] = b"queue";
+const QUEUE_NONCE_KEY: &[u8] = b"queue_nonce";
+
+/// Updates the usage of the limits covering the withdrawal. The exit precompiles are
+/// paused if the usage crossed the pause threshold of one of the limits.
+pub fn record_withdrawal<I: IO + Copy>(mut io: I, record: &WithdrawalRecord) {
+    let mut pause = false;
+    for target in [record.target, WithdrawalLimitTarget::Global] {
+        let Some(limit) = get_limit(&io, &target) else {
+            continue;
+        };
+        let usage =
+            get_usage(&io, &target).record(limit.window_secs, record.timestamp, record.amount);
+        set_usage(&mut io, &target, &usage);
+        pause |= limit.should_pause(&usage, record.timestamp);
+    }
+
+    let exits = PrecompileFlags::EXIT_TO_NEAR | PrecompileFlags::EXIT_TO_ETHEREUM;
+    let mut pauser = EnginePrecompilesPauser::from_io(io);
+    if pause && !pauser.is_paused(exits) {
+        pauser.pause_precompiles(exits);
+        aurora_engine_sdk::log!("Withdrawal limit threshold crossed, exit precompiles paused");
+        aurora_engine_sdk::log(
+            &AdminEvent::PrecompilesPaused {
+                paused_mask: exits.bits(),
+            }
+            .to_log(),
+        );
+    }
+}
+
+/// Removes the rejected withdrawal from the usage of the limits covering it.
+pub fn release_withdrawal<I: IO>(io: &mut I, record: &WithdrawalRecord) {
+    for target in [record.target, WithdrawalLimitTarget::Global] {
+        let Some(limit) = get_limit(io, &target) else {
+            continue;
+        };
+        let usage =
+            get_usage(io, &target).release(limit.window_secs, record.timestamp, record.amount);
+        set_usage(io, &target, &usage);
+    }
+}
+
+/// Stores the withdrawal until it is approved or rejected. Returns its id.
+pub fn queue_withdrawal<I: IO>(io: &mut I, withdrawal: &QueuedWithdrawal) -> u64 {
+    let nonce_key = bytes_to_key(KeyPrefix::WithdrawalLimits, QUEUE_NONCE_KEY);
+    let id = io.read_u64(&nonce_key).unwrap_or_default();
+    io.write_storage(&nonce_key, &(id + 1).to_le_bytes());
+    io.write_borsh(&queue_key(id), withdrawal);
+    id
+}
+
+pub fn get_queued_withdrawal<I: IO>(io: &I, id: u64) -> Option<QueuedWithdrawal> {
+    io.read_storage(&queue_key(id))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+/// Removes the withdrawal from the queue.
+pub fn take_queued_withdrawal<I: IO>(io: &mut I, id: u64) -> Option<QueuedWithdrawal> {
+    io.remove_storage(&queue_key(id))
+        .and_then(|bytes| bytes.to_value().ok())
+}
+
+fn queue_key(id: u64) -> Vec<u8> {
+    bytes_to_key(
+        KeyPrefix::WithdrawalLimits,
+        &[QUEUE_KEY, &id.to_le_bytes()].concat(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_precompiles::withdrawal_limits::set_limit;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use aurora_engine_types::parameters::withdrawal_limits::{ExceedAction, WithdrawalLimit};
+    use aurora_engine_types::parameters::{PromiseArgs, PromiseCreateArgs, RefundCallArgs};
+    use aurora_engine_types::types::{Address, NearGas, Yocto};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_record_withdrawal_pauses_exits() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let limit = WithdrawalLimit {
+            max_amount: 100,
+            window_secs: 3_600,
+            on_exceed: ExceedAction::Queue,
+            pause_threshold: Some(150),
+        };
+        set_limit(&mut io, &WithdrawalLimitTarget::Eth, Some(&limit));
+        let record = WithdrawalRecord {
+            target: WithdrawalLimitTarget::Eth,
+            amount: 100,
+            timestamp: 10,
+        };
+
+        record_withdrawal(io, &record);
+        assert_eq!(get_usage(&io, &WithdrawalLimitTarget::Eth).current, 100);
+        // The global limit isn't set, so its usage isn't tracked.
+        assert_eq!(get_usage(&io, &WithdrawalLimitTarget::Global).current, 0);
+        assert!(EnginePrecompilesPauser::from_io(io).paused().is_empty());
+
+        record_withdrawal(io, &record);
+        assert!(EnginePrecompilesPauser::from_io(io)
+            .is_paused(PrecompileFlags::EXIT_TO_NEAR | PrecompileFlags::EXIT_TO_ETHEREUM));
+
+        release_withdrawal(&mut io, &record);
+        assert_eq!(get_usage(&io, &WithdrawalLimitTarget::Eth).current, 100);
+    }
+
+    #[test]
+    fn test_queue_withdrawal() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let withdrawal = |amount| QueuedWithdrawal {
+            record: WithdrawalRecord {
+                target: WithdrawalLimitTarget::Eth,
+                amount,
+                timestamp: 10,
+            },
+            promise: PromiseArgs::Create(PromiseCreateArgs {
+                target_account_id: "token.near".parse().unwrap(),
+                method: "ft_transfer".into(),
+                args: Vec::new(),
+                attached_balance: Yocto::new(amount),
+                attached_gas: NearGas::new(0),
+            }),
+            refund: RefundCallArgs {
+                recipient_address: Address::zero(),
+                erc20_address: None,
+                amount: [0; 32],
+            },
+        };
+
+        assert_eq!(queue_withdrawal(&mut io, &withdrawal(1)), 0);
+        assert_eq!(queue_withdrawal(&mut io, &withdrawal(2)), 1);
+        assert!(take_queued_withdrawal(&mut io, 0).is_some());
+        assert!(get_queued_withdrawal(&io, 0).is_none());
+        assert!(get_queued_withdrawal(&io, 1).is_some());
+    }
+}
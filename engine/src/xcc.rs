@@ -8,11 +8,11 @@ use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use aurora_engine_sdk::promise::{PromiseHandler, PromiseId};
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::{self, BorshDeserialize, BorshSerialize};
-use aurora_engine_types::parameters::xcc::WithdrawWnearToRouterArgs;
+use aurora_engine_types::parameters::xcc::{WithdrawWnearToRouterArgs, XccEvmCallbackArgs};
 use aurora_engine_types::parameters::{PromiseAction, PromiseBatchAction, PromiseCreateArgs};
 use aurora_engine_types::storage::{self, KeyPrefix};
-use aurora_engine_types::types::{Address, NearGas, Yocto, ZERO_YOCTO};
-use aurora_engine_types::{format, Cow, Vec, U256};
+use aurora_engine_types::types::{Address, NearGas, PromiseResult, Yocto, ZERO_YOCTO};
+use aurora_engine_types::{format, vec, Cow, Vec, U256};
 
 pub use aurora_engine_types::parameters::xcc::{AddressVersionUpdateArgs, FundXccArgs};
 
@@ -34,6 +34,8 @@ pub const WITHDRAW_TO_NEAR_SELECTOR: [u8; 4] = [0x6b, 0x35, 0x18, 0x48];
 // (The initial version of the router was not upgradable, see
 // https://github.com/aurora-is-near/aurora-engine/pull/866)
 const FIRST_UPGRADABLE: &[u8] = b"first_upgrd";
+/// Name of the router method which deletes the router account.
+const ROUTER_CLOSE_NAME: &str = "close";
 
 pub use aurora_engine_precompiles::xcc::state::{
     get_code_version_of_address, get_latest_code_version, get_wnear_address, ERR_CORRUPTED_STORAGE,
@@ -183,6 +185,7 @@ pub fn handle_precompile_promise<I, P>(
     base_id: Option<PromiseId>,
     promise: &PromiseCreateArgs,
     required_near: Yocto,
+    evm_callback: Option<&XccEvmCallbackArgs>,
     current_account_id: &AccountId,
 ) -> PromiseId
 where
@@ -339,12 +342,70 @@ where
     // user directly. The XCC precompile will only construct promises that target the `execute`
     // and `schedule` methods of the user's router contract. Therefore, the user cannot have
     // the engine make arbitrary calls.
-    unsafe {
+    let promise_id = unsafe {
         match withdraw_id {
             None => handler.promise_create_call(promise),
             Some(withdraw_id) => handler.promise_attach_callback(withdraw_id, promise),
         }
+    };
+    // 4. If the router account is being closed, forget its version once the account is deleted,
+    //    so the router is created again on the next cross contract call of the address.
+    if promise.method == ROUTER_CLOSE_NAME {
+        let callback = PromiseCreateArgs {
+            target_account_id: current_account_id.clone(),
+            method: "factory_remove_address_version".into(),
+            args: borsh::to_vec(&sender).unwrap(),
+            attached_balance: ZERO_YOCTO,
+            attached_gas: VERSION_UPDATE_GAS,
+        };
+        // Safety: A call from the engine to the engine's `factory_remove_address_version`
+        // method is safe because that method only removes the metadata of the router
+        // sub-account that has just been closed.
+        return unsafe { handler.promise_attach_callback(promise_id, &callback) };
     }
+    // 5. If requested, deliver the results of the call to the EVM contract.
+    let Some(evm_callback) = evm_callback else {
+        return promise_id;
+    };
+    let callback = PromiseCreateArgs {
+        target_account_id: current_account_id.clone(),
+        method: "xcc_evm_callback".into(),
+        args: borsh::to_vec(evm_callback).unwrap(),
+        attached_balance: ZERO_YOCTO,
+        attached_gas: evm_callback.callback.gas,
+    };
+    // Safety: A call from the engine to the engine's `xcc_evm_callback` method is safe because
+    // that method only makes an EVM call from the cross contract call precompile address.
+    // The precompile address can't be used as the sender by the users.
+    unsafe { handler.promise_attach_callback(promise_id, &callback) }
+}
+
+/// Input of the EVM callback: the selector followed by the ABI-encoded address of the
+/// cross contract call sender and the `(success, output)` tuples of the promise results.
+#[must_use]
+pub fn evm_callback_input(
+    selector: [u8; 4],
+    sender: Address,
+    promise_results: &[PromiseResult],
+) -> Vec<u8> {
+    let results = promise_results
+        .iter()
+        .map(|result| {
+            let (success, output) = match result {
+                PromiseResult::Successful(output) => (true, output.clone()),
+                PromiseResult::Failed | PromiseResult::NotReady => (false, Vec::new()),
+            };
+            ethabi::Token::Tuple(vec![
+                ethabi::Token::Bool(success),
+                ethabi::Token::Bytes(output),
+            ])
+        })
+        .collect();
+    let args = ethabi::encode(&[
+        ethabi::Token::Address(sender.raw()),
+        ethabi::Token::Array(results),
+    ]);
+    [&selector, args.as_slice()].concat()
 }
 
 /// Read the current wasm bytecode for the router contracts
@@ -386,6 +447,12 @@ pub fn set_code_version_of_address<I: IO>(io: &mut I, address: &Address, version
     io.write_storage(&key, &value_bytes);
 }
 
+/// Remove the version of the router contract of the given address after its account is deleted.
+pub fn remove_code_version_of_address<I: IO>(io: &mut I, address: &Address) {
+    let key = storage::bytes_to_key(KeyPrefix::CrossContractCall, address.as_bytes());
+    io.remove_storage(&key);
+}
+
 pub fn withdraw_wnear_to_router<I: IO + Copy, E: Env, M: ModExpAlgorithm, H: PromiseHandler>(
     recipient: &AccountId,
     amount: Yocto,
@@ -569,7 +636,14 @@ impl<'a, H: PromiseHandler> PromiseHandler for PromiseInterceptor<'a, H> {
 
 #[cfg(test)]
 mod tests {
-    use aurora_engine_types::{account_id::AccountId, types::Yocto, U256};
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use aurora_engine_types::{
+        account_id::AccountId,
+        parameters::xcc::CodeVersion,
+        types::{Address, PromiseResult, Yocto},
+        U256,
+    };
+    use std::cell::RefCell;
 
     #[test]
     fn test_withdraw_to_near_encoding() {
@@ -607,4 +681,61 @@ mod tests {
             expected_tx_data
         );
     }
+
+    #[test]
+    fn test_evm_callback_input_encoding() {
+        let sender = Address::from_array([0x11; 20]);
+        let selector = [0xde, 0xad, 0xbe, 0xef];
+        let promise_results = [
+            PromiseResult::Successful(b"output".to_vec()),
+            PromiseResult::Failed,
+        ];
+        let input = super::evm_callback_input(selector, sender, &promise_results);
+
+        assert_eq!(input[..4], selector);
+        let result_type =
+            ethabi::ParamType::Tuple(vec![ethabi::ParamType::Bool, ethabi::ParamType::Bytes]);
+        let tokens = ethabi::decode(
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Array(Box::new(result_type)),
+            ],
+            &input[4..],
+        )
+        .unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                ethabi::Token::Address(sender.raw()),
+                ethabi::Token::Array(vec![
+                    ethabi::Token::Tuple(vec![
+                        ethabi::Token::Bool(true),
+                        ethabi::Token::Bytes(b"output".to_vec()),
+                    ]),
+                    ethabi::Token::Tuple(vec![
+                        ethabi::Token::Bool(false),
+                        ethabi::Token::Bytes(Vec::new()),
+                    ]),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_code_version_of_address() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let address = Address::from_array([1; 20]);
+        let other = Address::from_array([2; 20]);
+
+        super::set_code_version_of_address(&mut io, &address, CodeVersion(1));
+        super::set_code_version_of_address(&mut io, &other, CodeVersion(1));
+        super::remove_code_version_of_address(&mut io, &address);
+
+        assert_eq!(super::get_code_version_of_address(&io, &address), None);
+        assert_eq!(
+            super::get_code_version_of_address(&io, &other),
+            Some(CodeVersion(1))
+        );
+    }
 }
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "@openzeppelin/contracts/token/ERC1155/ERC1155.sol";
+import "./AdminControlled.sol";
+
+
+/**
+ * @title EvmErc1155
+ * @dev ERC-1155 contract mirroring a NEP-245 contract. Tokens are minted by the engine (admin)
+ * when NEP-245 tokens are transferred to it with `mt_transfer_call` or `mt_batch_transfer_call`,
+ * and burned when they are withdrawn back to NEAR with `withdrawToNear`.
+ */
+contract EvmErc1155 is ERC1155, AdminControlled {
+    address constant EXIT_TO_NEAR = 0xE9217BC70B7ED1f598ddD3199e80b093fA71124F;
+
+    string private _name;
+    // NEAR ids of the tokens, the ERC-1155 id is the keccak256 hash of the NEAR id.
+    mapping(uint256 => string) private _nearTokenIds;
+
+    constructor (string memory nep245_account_id, address admin)
+        ERC1155("")
+        AdminControlled(admin, 0)
+    {
+        _name = nep245_account_id;
+    }
+
+    function name() public view returns (string memory) {
+        return _name;
+    }
+
+    function nearTokenId(uint256 id) public view returns (string memory) {
+        return _nearTokenIds[id];
+    }
+
+    function mintBatch(address account, uint256[] memory ids, uint256[] memory amounts, string[] memory near_token_ids) public onlyAdmin {
+        require(ids.length == near_token_ids.length, "ERC1155: ids and near ids length mismatch");
+        for (uint256 i = 0; i < ids.length; ++i) {
+            _nearTokenIds[ids[i]] = near_token_ids[i];
+        }
+        _mintBatch(account, ids, amounts, "");
+    }
+
+    function withdrawToNear(bytes memory recipient, uint256[] memory ids, uint256[] memory amounts) external {
+        address sender = _msgSender();
+        _burnBatch(sender, ids, amounts);
+
+        // The refund address is always passed, the precompile ignores it when the engine
+        // is built without the refund on error.
+        bytes memory input = abi.encodePacked("\x03", sender, abi.encode(recipient, ids, amounts));
+        // slither-disable-next-line low-level-calls
+        (bool success, ) = EXIT_TO_NEAR.call(input);
+        require(success);
+    }
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "@openzeppelin/contracts/token/ERC721/ERC721.sol";
+import "./AdminControlled.sol";
+
+
+/**
+ * @title EvmErc721
+ * @dev ERC-721 contract mirroring a NEP-171 contract. Tokens are minted by the engine (admin)
+ * when NEP-171 tokens are transferred to it with `nft_transfer_call`, and burned when they are
+ * withdrawn back to NEAR with `withdrawToNear`.
+ */
+contract EvmErc721 is ERC721, AdminControlled {
+    address constant EXIT_TO_NEAR = 0xE9217BC70B7ED1f598ddD3199e80b093fA71124F;
+
+    string private _name;
+    string private _symbol;
+    string private _baseUri;
+    // NEAR ids of the tokens, the ERC-721 id is the keccak256 hash of the NEAR id.
+    mapping(uint256 => string) private _nearTokenIds;
+
+    // slither-disable-next-line shadowing-local
+    constructor (string memory metadata_name, string memory metadata_symbol, address admin)
+        ERC721(metadata_name, metadata_symbol)
+        AdminControlled(admin, 0)
+    {
+        _name = metadata_name;
+        _symbol = metadata_symbol;
+    }
+
+    function name() public view override returns (string memory) {
+        return _name;
+    }
+
+    function symbol() public view override returns (string memory) {
+        return _symbol;
+    }
+
+    function nearTokenId(uint256 tokenId) public view returns (string memory) {
+        return _nearTokenIds[tokenId];
+    }
+
+    function tokenURI(uint256 tokenId) public view override returns (string memory) {
+        require(_exists(tokenId), "ERC721: invalid token ID");
+        if (bytes(_baseUri).length == 0) {
+            return "";
+        }
+        return string(abi.encodePacked(_baseUri, _nearTokenIds[tokenId]));
+    }
+
+    function setMetadata(string memory metadata_name, string memory metadata_symbol, string memory base_uri) external onlyAdmin {
+        _name = metadata_name;
+        _symbol = metadata_symbol;
+        _baseUri = base_uri;
+    }
+
+    function mint(address account, uint256 tokenId, string memory near_token_id) public onlyAdmin {
+        _nearTokenIds[tokenId] = near_token_id;
+        _mint(account, tokenId);
+    }
+
+    // Used by the engine to return the token if the transfer on NEAR failed.
+    function mint(address account, uint256 tokenId) public onlyAdmin {
+        require(bytes(_nearTokenIds[tokenId]).length != 0, "ERC721: unknown token ID");
+        _mint(account, tokenId);
+    }
+
+    function withdrawToNear(bytes memory recipient, uint256 tokenId) external {
+        address sender = _msgSender();
+        require(_isApprovedOrOwner(sender, tokenId), "ERC721: caller is not token owner nor approved");
+        _burn(tokenId);
+
+        // The refund address is always passed, the precompile ignores it when the engine
+        // is built without the refund on error.
+        bytes memory input = abi.encodePacked("\x02", sender, bytes32(tokenId), recipient);
+        // slither-disable-next-line low-level-calls
+        (bool success, ) = EXIT_TO_NEAR.call(input);
+        require(success);
+    }
+}
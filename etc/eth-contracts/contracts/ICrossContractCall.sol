@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+/// ABI-encoded input of the cross contract call precompile
+/// (`0x516Cded1D16af10CAd47D6D49128E2eB7d27b372`). The precompile also accepts the
+/// borsh-encoded `CrossContractCallArgs`.
+///
+/// The precompile doesn't have code, so it must be called with a low-level call,
+/// e.g. using the `CrossContractCall` library below. If `delayed` is set, the promise is
+/// stored in the router contract and can be executed in a later transaction. The nonce of
+/// the stored promise is logged by the router.
+interface ICrossContractCall {
+    struct FunctionCall {
+        string targetAccountId;
+        string method;
+        bytes args;
+        uint128 attachedYocto;
+        uint64 attachedGas;
+    }
+
+    /// The `data` of the action is the ABI encoding of its parameters:
+    /// - `CreateAccount`: empty;
+    /// - `Transfer`: `(uint128 amount)`;
+    /// - `DeployContract`: `(bytes code)`;
+    /// - `FunctionCall`: `(string method, bytes args, uint128 attachedYocto, uint64 gas)`;
+    /// - `Stake`: `(uint128 amount, bytes publicKey)`;
+    /// - `AddFullAccessKey`: `(bytes publicKey, uint64 nonce)`;
+    /// - `AddFunctionCallKey`: `(bytes publicKey, uint64 nonce, uint128 allowance,
+    ///   string receiverId, string functionNames)`;
+    /// - `DeleteKey`: `(bytes publicKey)`;
+    /// - `DeleteAccount`: `(string beneficiaryId)`.
+    /// The public keys are borsh-encoded: the key type (0 for ED25519, 1 for SECP256K1)
+    /// followed by the key bytes.
+    enum ActionKind {
+        CreateAccount,
+        Transfer,
+        DeployContract,
+        FunctionCall,
+        Stake,
+        AddFullAccessKey,
+        AddFunctionCallKey,
+        DeleteKey,
+        DeleteAccount
+    }
+
+    struct Action {
+        ActionKind kind;
+        bytes data;
+    }
+
+    struct Batch {
+        string targetAccountId;
+        Action[] actions;
+    }
+
+    /// The `data` of the node is the ABI encoding of:
+    /// - `FunctionCall`: `(FunctionCall call)`;
+    /// - `Batch`: `(Batch batch)`;
+    /// - `Then`: `(uint256 base, uint256 callback)`, the callback must be
+    ///   a `FunctionCall` or a `Batch` node;
+    /// - `And`: `(uint256[] promises)`.
+    /// The nodes are referenced by their indices and must precede the nodes referencing them.
+    /// The last node is the root of the promise. Every other node must be referenced once.
+    enum PromiseKind {
+        FunctionCall,
+        Batch,
+        Then,
+        And
+    }
+
+    struct PromiseNode {
+        PromiseKind kind;
+        bytes data;
+    }
+
+    function functionCall(FunctionCall calldata call, bool delayed) external;
+
+    function batch(Batch calldata batchAction, bool delayed) external;
+
+    function promiseTree(PromiseNode[] calldata nodes, bool delayed) external;
+
+    /// Stores the promise in the router. The promise can't be executed after the `deadline`
+    /// block height, after which anyone can cancel it.
+    function scheduleWithDeadline(PromiseNode[] calldata nodes, uint64 deadline) external;
+
+    /// Cancels the promise stored in the router. The NEAR attached to the promise is returned
+    /// to the caller as wNEAR.
+    function cancelScheduled(uint64 nonce) external;
+
+    /// Deletes the router account of the caller. The wNEAR owned by the router is unwrapped
+    /// and the whole balance of the account is transferred to the beneficiary.
+    function closeAccount(string calldata beneficiaryId) external;
+}
+
+library CrossContractCall {
+    address constant PRECOMPILE = 0x516Cded1D16af10CAd47D6D49128E2eB7d27b372;
+
+    function functionCall(ICrossContractCall.FunctionCall memory call, bool delayed) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.functionCall.selector, call, delayed));
+    }
+
+    function batch(ICrossContractCall.Batch memory batchAction, bool delayed) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.batch.selector, batchAction, delayed));
+    }
+
+    function promiseTree(ICrossContractCall.PromiseNode[] memory nodes, bool delayed) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.promiseTree.selector, nodes, delayed));
+    }
+
+    function scheduleWithDeadline(ICrossContractCall.PromiseNode[] memory nodes, uint64 deadline) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.scheduleWithDeadline.selector, nodes, deadline));
+    }
+
+    function cancelScheduled(uint64 nonce) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.cancelScheduled.selector, nonce));
+    }
+
+    function closeAccount(string memory beneficiaryId) internal {
+        execute(abi.encodeWithSelector(ICrossContractCall.closeAccount.selector, beneficiaryId));
+    }
+
+    function execute(bytes memory input) private {
+        (bool success, bytes memory output) = PRECOMPILE.call(input);
+        if (!success) {
+            assembly {
+                revert(add(output, 32), mload(output))
+            }
+        }
+    }
+
+    function createAccountAction() internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.CreateAccount, "");
+    }
+
+    function transferAction(uint128 amount) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.Transfer, abi.encode(amount));
+    }
+
+    function deployContractAction(bytes memory code) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.DeployContract, abi.encode(code));
+    }
+
+    function functionCallAction(
+        string memory method,
+        bytes memory args,
+        uint128 attachedYocto,
+        uint64 gas
+    ) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(
+            ICrossContractCall.ActionKind.FunctionCall,
+            abi.encode(method, args, attachedYocto, gas)
+        );
+    }
+
+    function stakeAction(uint128 amount, bytes memory publicKey) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.Stake, abi.encode(amount, publicKey));
+    }
+
+    function addFullAccessKeyAction(
+        bytes memory publicKey,
+        uint64 nonce
+    ) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(
+            ICrossContractCall.ActionKind.AddFullAccessKey,
+            abi.encode(publicKey, nonce)
+        );
+    }
+
+    function addFunctionCallKeyAction(
+        bytes memory publicKey,
+        uint64 nonce,
+        uint128 allowance,
+        string memory receiverId,
+        string memory functionNames
+    ) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(
+            ICrossContractCall.ActionKind.AddFunctionCallKey,
+            abi.encode(publicKey, nonce, allowance, receiverId, functionNames)
+        );
+    }
+
+    function deleteKeyAction(bytes memory publicKey) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.DeleteKey, abi.encode(publicKey));
+    }
+
+    function deleteAccountAction(string memory beneficiaryId) internal pure returns (ICrossContractCall.Action memory) {
+        return ICrossContractCall.Action(ICrossContractCall.ActionKind.DeleteAccount, abi.encode(beneficiaryId));
+    }
+
+    function functionCallNode(
+        ICrossContractCall.FunctionCall memory call
+    ) internal pure returns (ICrossContractCall.PromiseNode memory) {
+        return ICrossContractCall.PromiseNode(ICrossContractCall.PromiseKind.FunctionCall, abi.encode(call));
+    }
+
+    function batchNode(
+        ICrossContractCall.Batch memory batchAction
+    ) internal pure returns (ICrossContractCall.PromiseNode memory) {
+        return ICrossContractCall.PromiseNode(ICrossContractCall.PromiseKind.Batch, abi.encode(batchAction));
+    }
+
+    function thenNode(uint256 base, uint256 callback) internal pure returns (ICrossContractCall.PromiseNode memory) {
+        return ICrossContractCall.PromiseNode(ICrossContractCall.PromiseKind.Then, abi.encode(base, callback));
+    }
+
+    function andNode(uint256[] memory promises) internal pure returns (ICrossContractCall.PromiseNode memory) {
+        return ICrossContractCall.PromiseNode(ICrossContractCall.PromiseKind.And, abi.encode(promises));
+    }
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "../ICrossContractCall.sol";
+
+/// Produces the ABI-encoded input of the cross contract call precompile.
+contract CrossContractCallTester {
+    bytes constant PUBLIC_KEY = hex"000707070707070707070707070707070707070707070707070707070707070707";
+
+    function encodeFunctionCall() external pure returns (bytes memory) {
+        return abi.encodeWithSelector(ICrossContractCall.functionCall.selector, makeCall("a.near", "a"), false);
+    }
+
+    function encodeBatch() external pure returns (bytes memory) {
+        return abi.encodeWithSelector(ICrossContractCall.batch.selector, makeBatch(), true);
+    }
+
+    /// `(a & (b -> batch)) -> c`
+    function encodePromise() external pure returns (bytes memory) {
+        ICrossContractCall.PromiseNode[] memory nodes = new ICrossContractCall.PromiseNode[](7);
+        nodes[0] = CrossContractCall.functionCallNode(makeCall("a.near", "a"));
+        nodes[1] = CrossContractCall.functionCallNode(makeCall("b.near", "b"));
+        nodes[2] = CrossContractCall.batchNode(makeBatch());
+        nodes[3] = CrossContractCall.thenNode(1, 2);
+        uint256[] memory promises = new uint256[](2);
+        promises[0] = 0;
+        promises[1] = 3;
+        nodes[4] = CrossContractCall.andNode(promises);
+        nodes[5] = CrossContractCall.functionCallNode(makeCall("c.near", "c"));
+        nodes[6] = CrossContractCall.thenNode(4, 5);
+        return abi.encodeWithSelector(ICrossContractCall.promiseTree.selector, nodes, false);
+    }
+
+    function makeCall(
+        string memory targetAccountId,
+        string memory method
+    ) private pure returns (ICrossContractCall.FunctionCall memory) {
+        return ICrossContractCall.FunctionCall(targetAccountId, method, "{}", 1, 5_000_000_000_000);
+    }
+
+    function makeBatch() private pure returns (ICrossContractCall.Batch memory) {
+        ICrossContractCall.Action[] memory actions = new ICrossContractCall.Action[](5);
+        actions[0] = CrossContractCall.createAccountAction();
+        actions[1] = CrossContractCall.transferAction(1_000_000);
+        actions[2] = CrossContractCall.addFullAccessKeyAction(PUBLIC_KEY, 1);
+        actions[3] = CrossContractCall.functionCallAction("new", "{}", 0, 10_000_000_000_000);
+        actions[4] = CrossContractCall.deleteAccountAction("beneficiary.near");
+        return ICrossContractCall.Batch("new.aurora", actions);
+    }
+}
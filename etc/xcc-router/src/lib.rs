@@ -1,15 +1,17 @@
+use aurora_engine_types::parameters::xcc::ScheduleWithDeadlineArgs;
 use aurora_engine_types::parameters::{
     NearPromise, PromiseAction, PromiseArgs, PromiseCreateArgs, PromiseWithCallbackArgs,
     SimpleNearPromise,
 };
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::collections::LazyOption;
-use near_sdk::json_types::U64;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::Serialize;
 use near_sdk::store::LookupMap;
 use near_sdk::BorshStorageKey;
 use near_sdk::{
     env, near_bindgen, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseIndex,
-    PromiseResult,
+    PromiseOrValue, PromiseResult,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -23,12 +25,14 @@ enum StorageKey {
     Parent,
     Nonce,
     Map,
+    Deadlines,
 }
 
 const INITIALIZE: &str = "initialize";
 const CURRENT_VERSION: u32 = include!("VERSION");
 
 const ERR_ILLEGAL_CALLER: &str = "ERR_ILLEGAL_CALLER";
+const ERR_PROMISE_EXPIRED: &str = "ERR_PROMISE_EXPIRED";
 const INITIALIZE_GAS: Gas = Gas::from_tgas(15);
 /// Gas cost estimated from mainnet data. Example:
 /// https://explorer.mainnet.near.org/transactions/5NbZ7SfrodNxeLcSkCmLAEdbZfbkk9cjqz3zSDwktKrk#D7un3c3Nxv7Ee3JpQSKiM97LbwCDFPbMo5iLoijGPXPM
@@ -39,6 +43,17 @@ const WNEAR_REGISTER_GAS: Gas = Gas::from_tgas(5);
 const WNEAR_REGISTER_AMOUNT: NearToken = NearToken::from_yoctonear(1_250_000_000_000_000_000_000);
 /// Must match aurora_engine_precompiles::xcc::state::STORAGE_AMOUNT
 const REFUND_AMOUNT: NearToken = NearToken::from_near(2);
+const WNEAR_DEPOSIT_GAS: Gas = Gas::from_tgas(5);
+/// Covers the `ft_on_transfer` call to the engine, which mints the wNEAR ERC-20 tokens.
+const WNEAR_TRANSFER_CALL_GAS: Gas = Gas::from_tgas(45);
+const WNEAR_BALANCE_GAS: Gas = Gas::from_tgas(5);
+const WNEAR_WITHDRAW_GAS: Gas = Gas::from_tgas(10);
+const WNEAR_UNREGISTER_GAS: Gas = Gas::from_tgas(10);
+const DELETE_ROUTER_GAS: Gas = Gas::from_tgas(5);
+/// Covers the unwrapping of wNEAR, unregistering in the wNEAR contract and the account deletion.
+const CLOSE_CALLBACK_GAS: Gas = Gas::from_tgas(35);
+/// Maximum number of the promises returned by `get_scheduled`.
+const MAX_SCHEDULED_PAGE: u64 = 100;
 
 #[derive(BorshDeserialize, BorshSerialize)]
 #[borsh(crate = "near_sdk::borsh")]
@@ -47,6 +62,17 @@ pub struct DeployUpgradeParams {
     pub initialize_args: Vec<u8>,
 }
 
+/// Promise stored by `schedule` which hasn't been executed or cancelled yet.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ScheduledPromiseView {
+    pub nonce: U64,
+    /// The last block height when the promise can be executed.
+    pub deadline: Option<U64>,
+    /// NEAR attached to the promise, which is refunded if the promise is cancelled.
+    pub attached_near: U128,
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 #[borsh(crate = "near_sdk::borsh")]
@@ -60,6 +86,8 @@ pub struct Router {
     nonce: LazyOption<u64>,
     /// The storage for the scheduled promises.
     scheduled_promises: LookupMap<u64, PromiseArgs>,
+    /// Deadlines (block heights) of the scheduled promises which have them.
+    deadlines: LookupMap<u64, u64>,
     /// Account ID for the wNEAR contract.
     wnear_account: AccountId,
 }
@@ -110,11 +138,13 @@ impl Router {
 
         let nonce = LazyOption::new(StorageKey::Nonce, None);
         let scheduled_promises = LookupMap::new(StorageKey::Map);
+        let deadlines = LookupMap::new(StorageKey::Deadlines);
         Self {
             parent,
             version,
             nonce,
             scheduled_promises,
+            deadlines,
             wnear_account,
         }
     }
@@ -138,12 +168,13 @@ impl Router {
     /// Similar security considerations here as for `execute`.
     pub fn schedule(&mut self, #[serializer(borsh)] promise: PromiseArgs) {
         self.assert_preconditions();
+        self.schedule_promise(promise, None);
+    }
 
-        let nonce = self.nonce.get().unwrap_or_default();
-        self.scheduled_promises.insert(nonce, promise);
-        self.nonce.set(&(nonce + 1));
-
-        near_sdk::log!("Promise scheduled at nonce {}", nonce);
+    /// Same as `schedule`, but the promise can't be executed after the deadline.
+    pub fn schedule_with_deadline(&mut self, #[serializer(borsh)] args: ScheduleWithDeadlineArgs) {
+        self.assert_preconditions();
+        self.schedule_promise(args.promise, Some(args.deadline));
     }
 
     /// It is intentional that this function can be called by anyone (not just the parent).
@@ -151,13 +182,62 @@ impl Router {
     /// act on promises that were created via `schedule`.
     #[payable]
     pub fn execute_scheduled(&mut self, nonce: U64) {
-        let Some(promise) = self.scheduled_promises.remove(&nonce.0) else {
-            env::panic_str("ERR_PROMISE_NOT_FOUND")
-        };
+        if self.is_expired(nonce.0) {
+            env::panic_str(ERR_PROMISE_EXPIRED);
+        }
+        let promise = self.remove_scheduled(nonce.0);
         let promise_id = Self::promise_create(promise);
         env::promise_return(promise_id);
     }
 
+    /// Removes the scheduled promise and returns the NEAR attached to it to the address of
+    /// this router as wNEAR. The parent calls this function when the owner of the router cancels
+    /// the promise via the precompile. Once the deadline of the promise has passed, anyone can
+    /// call it since the funds can only be returned to the owner.
+    pub fn cancel_scheduled(&mut self, nonce: U64) -> PromiseOrValue<()> {
+        let parent = self.get_parent().unwrap_or_else(env_panic);
+        if !self.is_expired(nonce.0) {
+            require_caller(&parent)
+                .and_then(|_| require_no_failed_promises())
+                .unwrap_or_else(env_panic);
+        }
+
+        let promise = self.remove_scheduled(nonce.0);
+        near_sdk::log!("Scheduled promise at nonce {} cancelled", nonce.0);
+
+        match promise.total_near().as_u128() {
+            0 => PromiseOrValue::Value(()),
+            refund => PromiseOrValue::Promise(self.refund_wnear(parent, refund)),
+        }
+    }
+
+    /// Returns the pending scheduled promises with nonces starting from `from_nonce`.
+    pub fn get_scheduled(
+        &self,
+        from_nonce: Option<U64>,
+        limit: Option<U64>,
+    ) -> Vec<ScheduledPromiseView> {
+        let next_nonce = self.nonce.get().unwrap_or_default();
+        let limit = limit.map_or(MAX_SCHEDULED_PAGE, |limit| limit.0.min(MAX_SCHEDULED_PAGE));
+        let from_nonce = from_nonce.map_or(0, |nonce| nonce.0);
+
+        (from_nonce..next_nonce)
+            .filter_map(|nonce| {
+                self.scheduled_promises
+                    .get(&nonce)
+                    .map(|promise| ScheduledPromiseView {
+                        nonce: nonce.into(),
+                        deadline: self
+                            .deadlines
+                            .get(&nonce)
+                            .map(|deadline| (*deadline).into()),
+                        attached_near: promise.total_near().as_u128().into(),
+                    })
+            })
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .collect()
+    }
+
     /// Allows the parent contract to trigger an update to the logic of this contract
     /// (by deploying a new contract to this account);
     #[payable]
@@ -185,6 +265,87 @@ impl Router {
 
         Promise::new(parent).transfer(REFUND_AMOUNT)
     }
+
+    /// Closes the router account: unwraps the wNEAR owned by the router, unregisters it in the
+    /// wNEAR contract and deletes the account. The remaining balance, including the NEAR attached
+    /// to the scheduled promises, is transferred to the beneficiary. Similar security
+    /// considerations here as for `execute`.
+    pub fn close(&mut self, beneficiary_id: AccountId) -> Promise {
+        self.assert_preconditions();
+
+        let balance_args = near_sdk::serde_json::json!({
+            "account_id": env::current_account_id(),
+        });
+        let callback_args = near_sdk::serde_json::json!({
+            "beneficiary_id": beneficiary_id,
+        });
+
+        Promise::new(self.wnear_account.clone())
+            .function_call(
+                "ft_balance_of".into(),
+                balance_args.to_string().into_bytes(),
+                NearToken::default(),
+                WNEAR_BALANCE_GAS,
+            )
+            .then(Promise::new(env::current_account_id()).function_call(
+                "close_with_wnear_balance".into(),
+                callback_args.to_string().into_bytes(),
+                NearToken::default(),
+                CLOSE_CALLBACK_GAS,
+            ))
+    }
+
+    #[private]
+    pub fn close_with_wnear_balance(
+        &mut self,
+        beneficiary_id: AccountId,
+        #[callback_unwrap] wnear_balance: U128,
+    ) -> Promise {
+        let wnear = Promise::new(self.wnear_account.clone());
+        // `near_withdraw` returns the promise of the transfer, so the unwrapped NEAR
+        // is received before the account is unregistered.
+        let wnear = if wnear_balance.0 == 0 {
+            wnear
+        } else {
+            let withdraw_args = near_sdk::serde_json::json!({ "amount": wnear_balance });
+            wnear
+                .function_call(
+                    "near_withdraw".into(),
+                    withdraw_args.to_string().into_bytes(),
+                    NearToken::from_yoctonear(1),
+                    WNEAR_WITHDRAW_GAS,
+                )
+                .then(Promise::new(self.wnear_account.clone()))
+        };
+        let callback_args = near_sdk::serde_json::json!({
+            "beneficiary_id": beneficiary_id,
+        });
+
+        wnear
+            .function_call(
+                "storage_unregister".into(),
+                b"{}".to_vec(),
+                NearToken::from_yoctonear(1),
+                WNEAR_UNREGISTER_GAS,
+            )
+            .then(Promise::new(env::current_account_id()).function_call(
+                "delete_router".into(),
+                callback_args.to_string().into_bytes(),
+                NearToken::default(),
+                DELETE_ROUTER_GAS,
+            ))
+    }
+
+    #[private]
+    pub fn delete_router(&mut self, beneficiary_id: AccountId) -> Promise {
+        require_no_failed_promises().unwrap_or_else(env_panic);
+        near_sdk::log!(
+            "Router closed, the balance is transferred to {}",
+            beneficiary_id
+        );
+
+        Promise::new(env::current_account_id()).delete_account(beneficiary_id)
+    }
 }
 
 impl Router {
@@ -192,6 +353,60 @@ impl Router {
         self.parent.get().ok_or(Error::ContractNotInitialized)
     }
 
+    fn schedule_promise(&mut self, promise: PromiseArgs, deadline: Option<u64>) {
+        let nonce = self.nonce.get().unwrap_or_default();
+        self.scheduled_promises.insert(nonce, promise);
+        if let Some(deadline) = deadline {
+            self.deadlines.insert(nonce, deadline);
+        }
+        self.nonce.set(&(nonce + 1));
+
+        near_sdk::log!("Promise scheduled at nonce {}", nonce);
+    }
+
+    fn remove_scheduled(&mut self, nonce: u64) -> PromiseArgs {
+        let Some(promise) = self.scheduled_promises.remove(&nonce) else {
+            env::panic_str("ERR_PROMISE_NOT_FOUND")
+        };
+        self.deadlines.remove(&nonce);
+        promise
+    }
+
+    fn is_expired(&self, nonce: u64) -> bool {
+        self.deadlines
+            .get(&nonce)
+            .is_some_and(|deadline| env::block_height() > *deadline)
+    }
+
+    /// Wraps the NEAR and transfers it to the parent, which credits the wNEAR ERC-20 tokens to
+    /// the address of this router. The account id of the router starts with the hex address.
+    fn refund_wnear(&self, parent: AccountId, amount: u128) -> Promise {
+        let current_account_id = env::current_account_id();
+        let address = current_account_id
+            .as_str()
+            .split_once('.')
+            .map_or("", |(address, _)| address);
+        let args = near_sdk::serde_json::json!({
+            "receiver_id": parent,
+            "amount": U128(amount),
+            "msg": address,
+        });
+
+        Promise::new(self.wnear_account.clone())
+            .function_call(
+                "near_deposit".into(),
+                b"{}".to_vec(),
+                NearToken::from_yoctonear(amount),
+                WNEAR_DEPOSIT_GAS,
+            )
+            .function_call(
+                "ft_transfer_call".into(),
+                args.to_string().into_bytes(),
+                NearToken::from_yoctonear(1),
+                WNEAR_TRANSFER_CALL_GAS,
+            )
+    }
+
     /// Checks the following preconditions:
     ///   1. Contract is initialized
     ///   2. predecessor_account_id == self.parent
@@ -1,4 +1,8 @@
-use super::Router;
+use super::{
+    Router, ScheduledPromiseView, CLOSE_CALLBACK_GAS, WNEAR_BALANCE_GAS, WNEAR_DEPOSIT_GAS,
+    WNEAR_TRANSFER_CALL_GAS,
+};
+use aurora_engine_types::parameters::xcc::ScheduleWithDeadlineArgs;
 use aurora_engine_types::parameters::{PromiseArgs, PromiseCreateArgs, PromiseWithCallbackArgs};
 use aurora_engine_types::types::{NearGas, Yocto};
 use near_primitives::types::GasWeight;
@@ -185,6 +189,162 @@ fn test_schedule_and_execute() {
     validate_function_call_action(&receipt.actions, promise, 0);
 }
 
+#[test]
+#[should_panic(expected = "ERR_PROMISE_EXPIRED")]
+fn test_execute_expired() {
+    let (_parent, mut contract) = create_contract();
+
+    contract.schedule_with_deadline(ScheduleWithDeadlineArgs {
+        promise: PromiseArgs::Create(create_args(Yocto::new(0))),
+        deadline: 10,
+    });
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(bob())
+        .block_height(11)
+        .build());
+    contract.execute_scheduled(0.into());
+}
+
+#[test]
+fn test_cancel_scheduled() {
+    let (parent, mut contract) = create_contract();
+
+    contract.schedule(PromiseArgs::Create(create_args(Yocto::new(56))));
+    contract.schedule_with_deadline(ScheduleWithDeadlineArgs {
+        promise: PromiseArgs::Create(create_args(Yocto::new(0))),
+        deadline: 10,
+    });
+    assert_eq!(
+        contract.get_scheduled(None, None),
+        vec![
+            ScheduledPromiseView {
+                nonce: 0.into(),
+                deadline: None,
+                attached_near: 56.into(),
+            },
+            ScheduledPromiseView {
+                nonce: 1.into(),
+                deadline: Some(10.into()),
+                attached_near: 0.into(),
+            },
+        ]
+    );
+
+    // The attached NEAR is wrapped and transferred to the engine on behalf of the address.
+    contract.cancel_scheduled(0.into());
+    assert!(!contract.scheduled_promises.contains_key(&0));
+    let receipts = test_utils::get_created_receipts();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].receiver_id.as_str(), WNEAR_ACCOUNT);
+    assert_eq!(
+        receipts[0].actions[0],
+        MockAction::FunctionCallWeight {
+            receipt_index: 0,
+            method_name: b"near_deposit".to_vec(),
+            args: b"{}".to_vec(),
+            attached_deposit: NearToken::from_yoctonear(56),
+            prepaid_gas: WNEAR_DEPOSIT_GAS,
+            gas_weight: GasWeight(0),
+        }
+    );
+    let expected_args =
+        format!(r#"{{"amount":"56","msg":"some_address","receiver_id":"{parent}"}}"#);
+    assert_eq!(
+        receipts[0].actions[1],
+        MockAction::FunctionCallWeight {
+            receipt_index: 0,
+            method_name: b"ft_transfer_call".to_vec(),
+            args: expected_args.into_bytes(),
+            attached_deposit: NearToken::from_yoctonear(1),
+            prepaid_gas: WNEAR_TRANSFER_CALL_GAS,
+            gas_weight: GasWeight(0),
+        }
+    );
+
+    // Anyone can cancel the promise once its deadline has passed.
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(bob())
+        .block_height(11)
+        .build());
+    contract.cancel_scheduled(1.into());
+    assert!(contract.get_scheduled(None, None).is_empty());
+    assert!(!contract.deadlines.contains_key(&1));
+}
+
+#[test]
+#[should_panic(expected = "ERR_ILLEGAL_CALLER")]
+fn test_cancel_scheduled_wrong_caller() {
+    let (_parent, mut contract) = create_contract();
+
+    contract.schedule_with_deadline(ScheduleWithDeadlineArgs {
+        promise: PromiseArgs::Create(create_args(Yocto::new(0))),
+        deadline: 10,
+    });
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(bob())
+        .block_height(10)
+        .build());
+    contract.cancel_scheduled(0.into());
+}
+
+#[test]
+fn test_close() {
+    let (_parent, mut contract) = create_contract();
+
+    contract.close(carol());
+
+    // The wNEAR balance is requested first, then the router continues closing in the callback.
+    let receipts = test_utils::get_created_receipts();
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0].receiver_id.as_str(), WNEAR_ACCOUNT);
+    assert_eq!(
+        receipts[0].actions,
+        vec![MockAction::FunctionCallWeight {
+            receipt_index: 0,
+            method_name: b"ft_balance_of".to_vec(),
+            args: br#"{"account_id":"some_address.alice.near"}"#.to_vec(),
+            attached_deposit: NearToken::default(),
+            prepaid_gas: WNEAR_BALANCE_GAS,
+            gas_weight: GasWeight(0),
+        }]
+    );
+    assert_eq!(receipts[1].receiver_id.as_str(), "some_address.alice.near");
+    assert_eq!(
+        receipts[1].actions,
+        vec![MockAction::FunctionCallWeight {
+            receipt_index: 1,
+            method_name: b"close_with_wnear_balance".to_vec(),
+            args: format!(r#"{{"beneficiary_id":"{}"}}"#, carol()).into_bytes(),
+            attached_deposit: NearToken::default(),
+            prepaid_gas: CLOSE_CALLBACK_GAS,
+            gas_weight: GasWeight(0),
+        }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_ILLEGAL_CALLER")]
+fn test_close_wrong_caller() {
+    let (_parent, mut contract) = create_contract();
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(bob())
+        .build());
+    contract.close(bob());
+}
+
+fn create_args(attached_balance: Yocto) -> PromiseCreateArgs {
+    PromiseCreateArgs {
+        target_account_id: bob().as_str().parse().unwrap(),
+        method: "some_method".into(),
+        args: b"hello_world".to_vec(),
+        attached_balance,
+        attached_gas: NearGas::new(100_000_000_000_000),
+    }
+}
+
 fn validate_function_call_action(
     actions: &[MockAction],
     promise: PromiseCreateArgs,